@@ -0,0 +1,373 @@
+//! One function per CLI command
+
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use gorbage_hands_client::accounts::decode;
+use gorbage_hands_client::{instructions, pda};
+use gorbage_hands_v2::constants::PARTICIPANT_PAGE_SIZE;
+use gorbage_hands_v2::state::{
+    Participant, ParticipantPage, ProgramConfig, Season, SeasonRegistry,
+};
+use gorbage_merkle::Distribution;
+use solana_rpc_client_api::config::RpcProgramAccountsConfig;
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::error::CliError;
+use crate::report::{
+    Executed, ParticipantList, ParticipantRow, SeasonDetails, SeasonList, SeasonSummary,
+};
+use crate::session::Session;
+use crate::spec::{read_winners, SeasonSpec};
+
+/// Roster pages closed per transaction, keeping it under the size limit
+const PAGES_PER_CLOSE: usize = 16;
+
+pub fn init_config(session: &Session) -> Result<Executed, CliError> {
+    let admin = session.wallet();
+    execute(
+        session,
+        format!("Initialize program config with admin {admin}"),
+        None,
+        &[instructions::initialize_config(&admin)],
+    )
+}
+
+pub fn transfer_admin(session: &Session, new_admin: &Pubkey) -> Result<Executed, CliError> {
+    execute(
+        session,
+        format!("Transfer admin to {new_admin}"),
+        None,
+        &[instructions::transfer_admin(&session.wallet(), new_admin)],
+    )
+}
+
+/// Create the next season from a TOML spec, numbered by the program config
+pub fn create_season(session: &Session, spec: &Path) -> Result<Executed, CliError> {
+    let spec = SeasonSpec::read(spec)?;
+    let params = spec.params()?;
+    let mint = spec.mint()?;
+    let config: ProgramConfig = session.fetch(&pda::config().0)?;
+    let season_number = config.next_season_number;
+    
+    let instruction = instructions::initialize_season(
+        &session.wallet(),
+        season_number,
+        params,
+        mint,
+        session.token_program(mint)?,
+    )?;
+    // The authority funds the first roster page along with the season
+    let first_page = instructions::open_participant_page(&session.wallet(), season_number, 0);
+    execute(
+        session,
+        format!("Create season \"{}\"", spec.name),
+        Some(season_number),
+        &[instruction, first_page],
+    )
+}
+
+/// Open the current or next roster page, paid by the season authority. When
+/// the next page isn't open in time, the first registrant on it pays instead.
+pub fn open_page(session: &Session, season_number: u64, page: u64) -> Result<Executed, CliError> {
+    let instruction = instructions::open_participant_page(&session.wallet(), season_number, page);
+    execute(
+        session,
+        format!("Open participant page {page}"),
+        Some(season_number),
+        &[instruction],
+    )
+}
+
+/// Close the open roster pages of a closed or cancelled season, returning the
+/// rent to whoever opened each page. Closes up to `PAGES_PER_CLOSE` pages per run.
+pub fn close_pages(session: &Session, season_number: u64) -> Result<Executed, CliError> {
+    let (address, season) = session.season(season_number)?;
+    // Pages up to the one after the current page, which may have been opened early
+    let candidates: Vec<u64> =
+        (0..=ParticipantPage::page_for(season.next_participant_index) + 1).collect();
+    let page_addresses: Vec<Pubkey> = candidates
+        .iter()
+        .map(|page| pda::participant_page(&address, page * PARTICIPANT_PAGE_SIZE as u64).0)
+        .collect();
+    let instructions: Vec<Instruction> = session
+        .fetch_many::<ParticipantPage>(&page_addresses)?
+        .into_iter()
+        .flatten()
+        .take(PAGES_PER_CLOSE)
+        .map(|page| instructions::close_participant_page(&session.wallet(), &page))
+        .collect();
+    
+    execute(
+        session,
+        format!("Close {} participant pages", instructions.len()),
+        Some(season_number),
+        &instructions,
+    )
+}
+
+/// Seasons listed in the season registry, or with `all` every season account
+pub fn list_seasons(session: &Session, all: bool, now: i64) -> Result<SeasonList, CliError> {
+    let mut seasons: Vec<(Pubkey, Season)> = if all {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                Season::DISCRIMINATOR,
+            ))]),
+            ..RpcProgramAccountsConfig::default()
+        };
+        session
+            .rpc
+            .get_program_accounts_with_config(&gorbage_hands_client::PROGRAM_ID, config)?
+            .into_iter()
+            .map(|(address, account)| Ok((address, decode(&account.data)?)))
+            .collect::<Result<_, CliError>>()?
+    } else {
+        let registry: SeasonRegistry = session.fetch(&pda::season_registry().0)?;
+        let addresses: Vec<Pubkey> = registry.seasons.iter().map(|entry| entry.season).collect();
+        addresses
+            .iter()
+            .zip(session.fetch_many::<Season>(&addresses)?)
+            .filter_map(|(address, season)| season.map(|season| (*address, season)))
+            .collect()
+    };
+    seasons.sort_by_key(|(_, season)| season.season_number);
+    
+    Ok(SeasonList {
+        seasons: seasons
+            .iter()
+            .map(|(address, season)| SeasonSummary::new(address, season, now))
+            .collect(),
+    })
+}
+
+pub fn show_season(
+    session: &Session,
+    season_number: u64,
+    now: i64,
+) -> Result<SeasonDetails, CliError> {
+    let (address, season) = session.season(season_number)?;
+    Ok(SeasonDetails::new(&address, &season, now))
+}
+
+/// Every registered wallet, read from the season's roster pages in index order
+pub fn list_participants(
+    session: &Session,
+    season_number: u64,
+) -> Result<ParticipantList, CliError> {
+    let (address, season) = session.season(season_number)?;
+    let page_count = match season.next_participant_index {
+        0 => 0,
+        next => ParticipantPage::page_for(next - 1) + 1,
+    };
+    let page_addresses: Vec<Pubkey> = (0..page_count)
+        .map(|page| pda::participant_page(&address, page * PARTICIPANT_PAGE_SIZE as u64).0)
+        .collect();
+    
+    let mut roster: Vec<(u64, Pubkey)> = Vec::new();
+    for page in session.fetch_many::<ParticipantPage>(&page_addresses)?.into_iter().flatten() {
+        roster.extend(
+            page.entries
+                .iter()
+                .enumerate()
+                .map(|(position, owner)| (page.participant_index(position), *owner)),
+        );
+    }
+    
+    let participant_addresses: Vec<Pubkey> = roster
+        .iter()
+        .map(|(_, owner)| pda::participant(&address, owner).0)
+        .collect();
+    let participants = session.fetch_many::<Participant>(&participant_addresses)?;
+    
+    Ok(ParticipantList {
+        season_number,
+        participants: roster
+            .iter()
+            .zip(&participants)
+            .map(|((index, owner), participant)| {
+                ParticipantRow::new(*index, owner, participant.as_ref(), &season)
+            })
+            .collect(),
+    })
+}
+
+pub fn collect_fee(
+    session: &Session,
+    season_number: u64,
+    treasury: &Pubkey,
+) -> Result<Executed, CliError> {
+    let (_, season) = session.season(season_number)?;
+    let instruction = instructions::collect_fee(
+        &session.wallet(),
+        treasury,
+        &season,
+        session.token_program(season.mint)?,
+    )?;
+    execute(
+        session,
+        format!("Collect platform fee to {treasury}"),
+        Some(season_number),
+        &[instruction],
+    )
+}
+
+/// Propose the winners listed in a CSV, in placement order
+pub fn set_winners(
+    session: &Session,
+    season_number: u64,
+    winners: &Path,
+) -> Result<Executed, CliError> {
+    let winners = read_winners(winners)?;
+    let (_, season) = session.season(season_number)?;
+    execute(
+        session,
+        format!("Propose {} winners", winners.len()),
+        Some(season_number),
+        &[instructions::settle_season(&session.wallet(), &season, &winners, false)],
+    )
+}
+
+/// Attest the season's proposed results as a registered attestor
+pub fn attest(session: &Session, season_number: u64) -> Result<Executed, CliError> {
+    let (_, season) = session.season(season_number)?;
+    let attestor = session.wallet();
+    execute(
+        session,
+        format!("Attest results of settlement {} as {attestor}", season.settlement_epoch),
+        Some(season_number),
+        &[instructions::attest_results(&attestor, &season)],
+    )
+}
+
+/// Finalize attested results whose challenge window has closed
+pub fn finalize(session: &Session, season_number: u64) -> Result<Executed, CliError> {
+    let (_, season) = session.season(season_number)?;
+    let instruction =
+        instructions::finalize_settlement(&season, session.token_program(season.mint)?)?;
+    execute(
+        session,
+        format!("Finalize settlement {}", season.settlement_epoch),
+        Some(season_number),
+        &[instruction],
+    )
+}
+
+/// Cancel a season. Once the platform fee was collected the treasury co-signs
+/// to return it.
+pub fn cancel(
+    session: &Session,
+    season_number: u64,
+    treasury: Option<&Keypair>,
+) -> Result<Executed, CliError> {
+    let (_, season) = session.season(season_number)?;
+    if season.fee_amount > 0 && treasury.is_none() {
+        return Err(CliError::TreasuryKeypairRequired);
+    }
+    
+    let instruction = instructions::cancel_season(
+        &session.wallet(),
+        &season,
+        treasury.map(|treasury| treasury.pubkey()),
+        session.token_program(season.mint)?,
+    )?;
+    let signers: Vec<&Keypair> = treasury.into_iter().collect();
+    execute_signed(
+        session,
+        "Cancel season".to_string(),
+        Some(season_number),
+        &[instruction],
+        &signers,
+    )
+}
+
+/// Claim the signing wallet's prize
+/// Claim the signer's prize. For a Merkle settlement the proof comes from
+/// `results`, the wallet,amount file the root was built from.
+pub fn claim(
+    session: &Session,
+    season_number: u64,
+    results: Option<&Path>,
+) -> Result<Executed, CliError> {
+    let (_, season) = session.season(season_number)?;
+    let winner = session.wallet();
+    let token_program = session.token_program(season.mint)?;
+    
+    let instruction = match season.merkle_root {
+        None => instructions::claim_prize(&winner, &season, token_program)?,
+        Some(merkle_root) => {
+            let results = results.ok_or(CliError::ResultsRequired)?;
+            let entries = gorbage_merkle::read_entries(results)?;
+            let distribution = Distribution::build(&entries, season.total_awarded)?;
+            if distribution.root != hex::encode(merkle_root) {
+                return Err(CliError::MerkleRootMismatch);
+            }
+            
+            let claim = distribution
+                .claims
+                .iter()
+                .find(|claim| claim.wallet == winner.to_string())
+                .ok_or(CliError::NotAWinner(winner))?;
+            let proof = claim
+                .proof
+                .iter()
+                .map(|sibling| {
+                    let bytes = hex::decode(sibling).expect("proofs are hex encoded");
+                    <[u8; 32]>::try_from(bytes).expect("proof hashes are 32 bytes")
+                })
+                .collect();
+            instructions::claim_prize_with_proof(
+                &winner,
+                &season,
+                claim.index,
+                claim.amount,
+                proof,
+                token_program,
+            )?
+        }
+    };
+    
+    execute(
+        session,
+        format!("Claim prize for {winner}"),
+        Some(season_number),
+        &[instruction],
+    )
+}
+
+pub fn close(session: &Session, season_number: u64) -> Result<Executed, CliError> {
+    let (_, season) = session.season(season_number)?;
+    let instruction = instructions::close_season(
+        &session.wallet(),
+        &season,
+        session.token_program(season.mint)?,
+    )?;
+    execute(session, "Close season".to_string(), Some(season_number), &[instruction])
+}
+
+fn execute(
+    session: &Session,
+    action: String,
+    season_number: Option<u64>,
+    instructions: &[Instruction],
+) -> Result<Executed, CliError> {
+    execute_signed(session, action, season_number, instructions, &[])
+}
+
+fn execute_signed(
+    session: &Session,
+    action: String,
+    season_number: Option<u64>,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<Executed, CliError> {
+    Ok(Executed {
+        action,
+        season_number,
+        season: season_number.map(|number| pda::season(number).0.to_string()),
+        outcome: session.execute_signed(instructions, signers)?,
+    })
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::Pubkey;
+use gorbage_hands_client::error::{from_instruction_error, ClientError};
+use gorbage_merkle::BuildError;
+use solana_rpc_client_api::client_error::Error as RpcError;
+use solana_sdk::transaction::TransactionError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error("failed to read {path}: {message}")]
+    Read { path: String, message: String },
+    
+    #[error("failed to read keypair {path}: {message}")]
+    Keypair { path: String, message: String },
+    
+    #[error("invalid season spec: {0}")]
+    Spec(String),
+    
+    #[error("invalid winners CSV: {0}")]
+    Csv(#[from] csv::Error),
+    
+    #[error("invalid pubkey {0:?}")]
+    InvalidPubkey(String),
+    
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    
+    #[error("{0}")]
+    Client(#[from] ClientError),
+    
+    #[error("transaction failed: {}", describe_transaction_error(.0))]
+    Transaction(TransactionError),
+    
+    /// Boxed, the RPC client error is several times larger than the rest
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<RpcError>),
+    
+    #[error("invalid Merkle results: {0}")]
+    Merkle(#[from] BuildError),
+    
+    #[error("Merkle-settled season, pass --results with the file its root was built from")]
+    ResultsRequired,
+    
+    #[error("results do not match the season's Merkle root")]
+    MerkleRootMismatch,
+    
+    #[error("{0} is not among the season's winners")]
+    NotAWinner(Pubkey),
+    
+    #[error("the platform fee was collected, pass --treasury-keypair to return it")]
+    TreasuryKeypairRequired,
+}
+
+impl From<RpcError> for CliError {
+    fn from(err: RpcError) -> Self {
+        match err.get_transaction_error() {
+            Some(err) => CliError::Transaction(err),
+            None => CliError::Rpc(Box::new(err)),
+        }
+    }
+}
+
+/// Spell out program errors by name instead of their custom error code
+pub fn describe_transaction_error(err: &TransactionError) -> String {
+    if let TransactionError::InstructionError(index, instruction_error) = err {
+        if let Some(program_error) = from_instruction_error(instruction_error) {
+            return format!(
+                "instruction {index}: {} ({program_error})",
+                program_error.name()
+            );
+        }
+    }
+    
+    err.to_string()
+}
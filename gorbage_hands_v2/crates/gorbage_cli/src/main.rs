@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use clap::{Parser, Subcommand};
+use solana_sdk::signature::{read_keypair_file, Keypair};
+
+mod commands;
+mod error;
+mod report;
+mod session;
+mod spec;
+
+use error::CliError;
+use report::{print, OutputFormat};
+use session::Session;
+
+/// Run gorbage_hands_v2 seasons end to end
+#[derive(Parser)]
+#[command(name = "gorbage", version)]
+struct Args {
+    /// RPC endpoint
+    #[arg(long, short, global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    
+    /// Signing keypair, also the fee payer [default: ~/.config/solana/id.json]
+    #[arg(long, short, global = true)]
+    keypair: Option<PathBuf>,
+    
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Human)]
+    output: OutputFormat,
+    
+    /// Simulate transactions and print the expected balance changes instead of sending
+    #[arg(long, global = true)]
+    dry_run: bool,
+    
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the program config, making the signer the global admin
+    InitConfig,
+    
+    /// Hand admin rights to another wallet
+    TransferAdmin { new_admin: Pubkey },
+    
+    /// Create the next season from a TOML spec
+    CreateSeason { spec: PathBuf },
+    
+    /// Open the current or next roster page of a season, paid by its authority
+    OpenPage {
+        season: u64,
+        
+        /// Page number; page N holds participant indices from N * 64
+        page: u64,
+    },
+    
+    /// List open seasons from the season registry
+    ListSeasons {
+        /// Include closed and cancelled seasons
+        #[arg(long)]
+        all: bool,
+    },
+    
+    /// Show a season's settings, pools and results
+    ShowSeason { season: u64 },
+    
+    /// List a season's participants in registration order
+    ListParticipants { season: u64 },
+    
+    /// Collect the platform fee once registration has closed
+    CollectFee {
+        season: u64,
+        
+        /// Wallet receiving the platform fee
+        #[arg(long)]
+        treasury: Pubkey,
+    },
+    
+    /// Propose the winners of a finished season
+    SetWinners {
+        season: u64,
+        
+        /// CSV with a wallet header listing winners in placement order
+        winners: PathBuf,
+    },
+    
+    /// Attest a season's proposed results as a registered attestor
+    Attest { season: u64 },
+    
+    /// Make attested, undisputed results final once the challenge window closes
+    Finalize { season: u64 },
+    
+    /// Cancel a season so participants and sponsors can claim refunds
+    Cancel {
+        season: u64,
+        
+        /// Treasury keypair, needed to return the platform fee once it was collected
+        #[arg(long)]
+        treasury_keypair: Option<PathBuf>,
+    },
+    
+    /// Claim the signer's prize
+    Claim {
+        season: u64,
+        
+        /// Results file the Merkle root was built from, for Merkle-settled seasons
+        #[arg(long)]
+        results: Option<PathBuf>,
+    },
+    
+    /// Close a settled season and sweep the remaining balance
+    Close { season: u64 },
+    
+    /// Reclaim the rent of a closed or cancelled season's roster pages
+    ClosePages { season: u64 },
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), CliError> {
+    let payer = read_keypair(&args.keypair.unwrap_or_else(default_keypair_path))?;
+    let session = Session::new(args.url, payer, args.dry_run);
+    let output = args.output;
+    
+    match args.command {
+        Command::InitConfig => print(output, &commands::init_config(&session)?),
+        Command::TransferAdmin { new_admin } => {
+            print(output, &commands::transfer_admin(&session, &new_admin)?)
+        }
+        Command::CreateSeason { spec } => {
+            print(output, &commands::create_season(&session, &spec)?)
+        }
+        Command::OpenPage { season, page } => {
+            print(output, &commands::open_page(&session, season, page)?)
+        }
+        Command::ListSeasons { all } => {
+            print(output, &commands::list_seasons(&session, all, now())?)
+        }
+        Command::ShowSeason { season } => {
+            print(output, &commands::show_season(&session, season, now())?)
+        }
+        Command::ListParticipants { season } => {
+            print(output, &commands::list_participants(&session, season)?)
+        }
+        Command::CollectFee { season, treasury } => {
+            print(output, &commands::collect_fee(&session, season, &treasury)?)
+        }
+        Command::SetWinners { season, winners } => {
+            print(output, &commands::set_winners(&session, season, &winners)?)
+        }
+        Command::Attest { season } => print(output, &commands::attest(&session, season)?),
+        Command::Finalize { season } => print(output, &commands::finalize(&session, season)?),
+        Command::Cancel { season, treasury_keypair } => {
+            let treasury = treasury_keypair.as_deref().map(read_keypair).transpose()?;
+            print(output, &commands::cancel(&session, season, treasury.as_ref())?)
+        }
+        Command::Claim { season, results } => {
+            print(output, &commands::claim(&session, season, results.as_deref())?)
+        }
+        Command::Close { season } => print(output, &commands::close(&session, season)?),
+        Command::ClosePages { season } => {
+            print(output, &commands::close_pages(&session, season)?)
+        }
+    }
+    
+    Ok(())
+}
+
+fn read_keypair(path: &Path) -> Result<Keypair, CliError> {
+    read_keypair_file(path).map_err(|err| CliError::Keypair {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    })
+}
+
+fn default_keypair_path() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".config/solana/id.json")
+}
+
+/// Local clock, used to show phases the chain has not caught up with yet
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
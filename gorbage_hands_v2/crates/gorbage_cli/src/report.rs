@@ -0,0 +1,458 @@
+//! Command results, printed either as text for operators or as JSON
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use gorbage_hands_v2::state::{DustPolicy, Participant, Season, UnclaimedPrizePolicy};
+use serde::Serialize;
+
+/// Size of a token account without extensions
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Token-2022 `AccountType` tag of token accounts with extensions
+const TOKEN_ACCOUNT_TYPE: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+pub fn print<T: Serialize + fmt::Display>(format: OutputFormat, report: &T) {
+    match format {
+        OutputFormat::Human => println!("{report}"),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(report).expect("report serializes")
+        ),
+    }
+}
+
+/// Result of sending or simulating a transaction
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Outcome {
+    Sent {
+        signature: String,
+    },
+    Simulated {
+        error: Option<String>,
+        units_consumed: Option<u64>,
+        balance_changes: Vec<BalanceChange>,
+        logs: Vec<String>,
+    },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Sent { signature } => write!(f, "Signature: {signature}"),
+            Outcome::Simulated {
+                error,
+                units_consumed,
+                balance_changes,
+                logs,
+            } => {
+                match error {
+                    Some(error) => writeln!(f, "Simulation failed: {error}")?,
+                    None => writeln!(f, "Simulation succeeded (dry run, nothing sent)")?,
+                }
+                if let Some(units) = units_consumed {
+                    writeln!(f, "Compute units: {units}")?;
+                }
+                
+                writeln!(f, "Expected balance changes:")?;
+                if balance_changes.is_empty() {
+                    writeln!(f, "  none")?;
+                }
+                for change in balance_changes {
+                    writeln!(f, "  {change}")?;
+                }
+                
+                write!(f, "Logs:")?;
+                for line in logs {
+                    write!(f, "\n  {line}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Lamports and, for token accounts, token amount of an account
+#[derive(Debug, Clone, Copy)]
+pub struct Snapshot {
+    pub lamports: u64,
+    pub token_amount: Option<u64>,
+}
+
+impl Snapshot {
+    pub fn new(lamports: u64, owner: &Pubkey, data: &[u8]) -> Self {
+        Self {
+            lamports,
+            token_amount: token_amount(owner, data),
+        }
+    }
+}
+
+/// Amount held by an SPL Token or Token-2022 token account
+fn token_amount(owner: &Pubkey, data: &[u8]) -> Option<u64> {
+    if *owner != anchor_spl::token::ID && *owner != anchor_spl::token_2022::ID {
+        return None;
+    }
+    let is_token_account = data.len() == TOKEN_ACCOUNT_LEN
+        || (data.len() > TOKEN_ACCOUNT_LEN && data[TOKEN_ACCOUNT_LEN] == TOKEN_ACCOUNT_TYPE);
+    if !is_token_account {
+        return None;
+    }
+    
+    let amount = data.get(64..72)?;
+    Some(u64::from_le_bytes(amount.try_into().ok()?))
+}
+
+#[derive(Debug, Serialize)]
+pub struct BalanceChange {
+    pub account: String,
+    pub lamports_before: u64,
+    pub lamports_after: u64,
+    pub lamports_delta: i128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_delta: Option<i128>,
+}
+
+impl BalanceChange {
+    /// `None` if the account's balances did not change
+    pub fn between(
+        address: Pubkey,
+        before: Option<Snapshot>,
+        after: Option<Snapshot>,
+    ) -> Option<Self> {
+        let lamports_before = before.map_or(0, |snapshot| snapshot.lamports);
+        let lamports_after = after.map_or(0, |snapshot| snapshot.lamports);
+        let tokens_before = before.and_then(|snapshot| snapshot.token_amount);
+        let tokens_after = after.and_then(|snapshot| snapshot.token_amount);
+        
+        let token_delta = match (tokens_before, tokens_after) {
+            (None, None) => None,
+            (before, after) => {
+                Some(after.unwrap_or(0) as i128 - before.unwrap_or(0) as i128)
+            }
+        };
+        let lamports_delta = lamports_after as i128 - lamports_before as i128;
+        if lamports_delta == 0 && token_delta.unwrap_or(0) == 0 {
+            return None;
+        }
+        
+        Some(Self {
+            account: address.to_string(),
+            lamports_before,
+            lamports_after,
+            lamports_delta,
+            token_delta,
+        })
+    }
+}
+
+impl fmt::Display for BalanceChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<44} {:+} lamports ({} -> {})",
+            self.account, self.lamports_delta, self.lamports_before, self.lamports_after
+        )?;
+        if let Some(token_delta) = self.token_delta {
+            write!(f, ", {token_delta:+} tokens")?;
+        }
+        Ok(())
+    }
+}
+
+/// A state-changing command and its transaction outcome
+#[derive(Debug, Serialize)]
+pub struct Executed {
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub season_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub season: Option<String>,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Executed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.action)?;
+        if let (Some(number), Some(season)) = (self.season_number, &self.season) {
+            writeln!(f, "Season {number}: {season}")?;
+        }
+        write!(f, "{}", self.outcome)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeasonSummary {
+    pub season_number: u64,
+    pub season: String,
+    pub name: String,
+    pub phase: String,
+    pub participant_count: u64,
+    pub prize_pool: u64,
+}
+
+impl SeasonSummary {
+    /// `phase` is the season's phase as of `now`, which may be ahead of the stored one
+    pub fn new(address: &Pubkey, season: &Season, now: i64) -> Self {
+        Self {
+            season_number: season.season_number,
+            season: address.to_string(),
+            name: season.name.clone(),
+            phase: current_phase(season, now),
+            participant_count: season.participant_count,
+            prize_pool: season.prize_pool,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeasonList {
+    pub seasons: Vec<SeasonSummary>,
+}
+
+impl fmt::Display for SeasonList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.seasons.is_empty() {
+            return write!(f, "No seasons");
+        }
+        
+        write!(
+            f,
+            "{:>6}  {:<44}  {:<12}  {:>12}  {:>20}  NAME",
+            "SEASON", "ADDRESS", "PHASE", "PARTICIPANTS", "PRIZE POOL"
+        )?;
+        for season in &self.seasons {
+            write!(
+                f,
+                "\n{:>6}  {:<44}  {:<12}  {:>12}  {:>20}  {}",
+                season.season_number,
+                season.season,
+                season.phase,
+                season.participant_count,
+                season.prize_pool,
+                season.name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeasonDetails {
+    pub season_number: u64,
+    pub season: String,
+    pub name: String,
+    pub authority: String,
+    pub phase: String,
+    pub mode: String,
+    pub mint: Option<String>,
+    pub entry_fee: u64,
+    pub participant_count: u64,
+    pub min_participants: u64,
+    pub max_participants: u64,
+    pub prize_pool: u64,
+    pub sponsor_pool: u64,
+    pub guaranteed_pool: u64,
+    pub rollover_pool: u64,
+    pub fee_amount: u64,
+    pub treasury: String,
+    pub payout_bps: Vec<u16>,
+    pub registration_start: i64,
+    pub registration_end: i64,
+    pub season_end: i64,
+    pub claim_deadline: i64,
+    pub unclaimed_policy: String,
+    pub dust_policy: String,
+    pub winners: Vec<String>,
+    pub merkle_root: Option<String>,
+    pub total_awarded: u64,
+    pub total_claimed: u64,
+    pub dust_amount: u64,
+    pub settlement_epoch: u32,
+    pub result_hash: String,
+    pub attestations: usize,
+    pub challenge_deadline: i64,
+    pub open_disputes: u32,
+}
+
+impl SeasonDetails {
+    pub fn new(address: &Pubkey, season: &Season, now: i64) -> Self {
+        Self {
+            season_number: season.season_number,
+            season: address.to_string(),
+            name: season.name.clone(),
+            authority: season.authority.to_string(),
+            phase: current_phase(season, now),
+            mode: format!("{:?}", season.mode),
+            mint: season.mint.map(|mint| mint.to_string()),
+            entry_fee: season.entry_fee,
+            participant_count: season.participant_count,
+            min_participants: season.min_participants,
+            max_participants: season.max_participants,
+            prize_pool: season.prize_pool,
+            sponsor_pool: season.sponsor_pool,
+            guaranteed_pool: season.guaranteed_pool,
+            rollover_pool: season.rollover_pool,
+            fee_amount: season.fee_amount,
+            treasury: season.treasury.to_string(),
+            payout_bps: season.payout_bps.clone(),
+            registration_start: season.registration_start,
+            registration_end: season.registration_end,
+            season_end: season.season_end,
+            claim_deadline: season.claim_deadline,
+            unclaimed_policy: match season.unclaimed_policy {
+                UnclaimedPrizePolicy::Treasury => "treasury".to_string(),
+                UnclaimedPrizePolicy::Rollover { season_number } => {
+                    format!("rollover to season {season_number}")
+                }
+            },
+            dust_policy: match season.dust_policy {
+                DustPolicy::ToFirstPlace => "first place".to_string(),
+                DustPolicy::ToTreasury => "treasury".to_string(),
+                DustPolicy::ToNextSeason { season_number } => {
+                    format!("rollover to season {season_number}")
+                }
+            },
+            winners: season.winners.iter().map(|winner| winner.to_string()).collect(),
+            merkle_root: season.merkle_root.map(hex::encode),
+            total_awarded: season.total_awarded,
+            total_claimed: season.total_claimed,
+            dust_amount: season.dust_amount,
+            settlement_epoch: season.settlement_epoch,
+            result_hash: hex::encode(season.result_hash),
+            attestations: season.attestations.len(),
+            challenge_deadline: season.challenge_deadline,
+            open_disputes: season.open_disputes,
+        }
+    }
+}
+
+impl fmt::Display for SeasonDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Season {} \"{}\"", self.season_number, self.name)?;
+        writeln!(f, "  address            {}", self.season)?;
+        writeln!(f, "  authority          {}", self.authority)?;
+        writeln!(f, "  phase              {}", self.phase)?;
+        writeln!(f, "  mode               {}", self.mode)?;
+        writeln!(
+            f,
+            "  currency           {}",
+            self.mint.as_deref().unwrap_or("lamports")
+        )?;
+        writeln!(f, "  entry fee          {}", self.entry_fee)?;
+        writeln!(
+            f,
+            "  participants       {} (min {}, max {})",
+            self.participant_count,
+            self.min_participants,
+            match self.max_participants {
+                0 => "unlimited".to_string(),
+                max => max.to_string(),
+            }
+        )?;
+        writeln!(f, "  prize pool         {}", self.prize_pool)?;
+        writeln!(f, "  sponsor pool       {}", self.sponsor_pool)?;
+        writeln!(f, "  guaranteed pool    {}", self.guaranteed_pool)?;
+        writeln!(f, "  rollover pool      {}", self.rollover_pool)?;
+        writeln!(f, "  platform fee       {} to {}", self.fee_amount, self.treasury)?;
+        writeln!(f, "  payout bps         {:?}", self.payout_bps)?;
+        writeln!(f, "  registration       {} - {}", self.registration_start, self.registration_end)?;
+        writeln!(f, "  season end         {}", self.season_end)?;
+        writeln!(f, "  claim deadline     {}", self.claim_deadline)?;
+        writeln!(f, "  unclaimed prizes   {}", self.unclaimed_policy)?;
+        writeln!(f, "  rounding dust      {} to {}", self.dust_amount, self.dust_policy)?;
+        if let Some(root) = &self.merkle_root {
+            writeln!(f, "  merkle root        {root}")?;
+        }
+        for (placement, winner) in self.winners.iter().enumerate() {
+            writeln!(f, "  winner #{:<10} {winner}", placement + 1)?;
+        }
+        writeln!(f, "  awarded / claimed  {} / {}", self.total_awarded, self.total_claimed)?;
+        writeln!(f, "  settlement epoch   {}", self.settlement_epoch)?;
+        writeln!(f, "  result hash        {}", self.result_hash)?;
+        writeln!(f, "  attestations       {}", self.attestations)?;
+        writeln!(f, "  challenge deadline {}", self.challenge_deadline)?;
+        write!(f, "  open disputes      {}", self.open_disputes)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParticipantRow {
+    pub index: u64,
+    pub owner: String,
+    /// `None` once the participant account has been closed
+    pub status: Option<String>,
+    pub score: Option<u64>,
+    pub placement: Option<u8>,
+    pub prize_amount: Option<u64>,
+    pub prize_claimed: bool,
+}
+
+impl ParticipantRow {
+    pub fn new(index: u64, owner: &Pubkey, participant: Option<&Participant>, season: &Season) -> Self {
+        let winner = participant.filter(|participant| participant.is_winner(season));
+        Self {
+            index,
+            owner: owner.to_string(),
+            status: participant.map(|participant| format!("{:?}", participant.status)),
+            score: participant
+                .filter(|participant| participant.has_score())
+                .map(|participant| participant.score),
+            placement: winner.map(|participant| participant.placement),
+            prize_amount: winner.map(|participant| participant.prize_amount),
+            prize_claimed: winner.is_some_and(|participant| participant.prize_claimed),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParticipantList {
+    pub season_number: u64,
+    pub participants: Vec<ParticipantRow>,
+}
+
+impl fmt::Display for ParticipantList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Season {}: {} participants", self.season_number, self.participants.len())?;
+        if self.participants.is_empty() {
+            return Ok(());
+        }
+        
+        write!(
+            f,
+            "\n{:>6}  {:<44}  {:<12}  {:>12}  {:>5}  {:>20}  CLAIMED",
+            "INDEX", "OWNER", "STATUS", "SCORE", "PLACE", "PRIZE"
+        )?;
+        for row in &self.participants {
+            write!(
+                f,
+                "\n{:>6}  {:<44}  {:<12}  {:>12}  {:>5}  {:>20}  {}",
+                row.index,
+                row.owner,
+                row.status.as_deref().unwrap_or("closed"),
+                optional(row.score),
+                optional(row.placement),
+                optional(row.prize_amount),
+                if row.prize_claimed { "yes" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+fn current_phase(season: &Season, now: i64) -> String {
+    let mut season = season.clone();
+    season.sync_phase(now);
+    format!("{:?}", season.phase)
+}
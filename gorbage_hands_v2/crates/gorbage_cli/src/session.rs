@@ -0,0 +1,167 @@
+//! RPC access and transaction execution shared by every command
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use gorbage_hands_client::accounts::decode;
+use gorbage_hands_client::pda;
+use gorbage_hands_v2::state::Season;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::error::{describe_transaction_error, CliError};
+use crate::report::{BalanceChange, Outcome, Snapshot};
+
+/// `getMultipleAccounts` limit per request
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+pub struct Session {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    pub dry_run: bool,
+}
+
+impl Session {
+    pub fn new(url: String, payer: Keypair, dry_run: bool) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+            payer,
+            dry_run,
+        }
+    }
+    
+    pub fn wallet(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+    
+    pub fn account(&self, address: &Pubkey) -> Result<Option<Account>, CliError> {
+        Ok(self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())?
+            .value)
+    }
+    
+    /// Fetch and decode a program account that must exist
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T, CliError> {
+        let account = self
+            .account(address)?
+            .ok_or(CliError::AccountNotFound(*address))?;
+        Ok(decode(&account.data)?)
+    }
+    
+    /// Fetch and decode many program accounts, `None` for the ones that do not exist
+    pub fn fetch_many<T: AccountDeserialize>(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<T>>, CliError> {
+        let mut decoded = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+            for account in self.rpc.get_multiple_accounts(chunk)? {
+                decoded.push(account.map(|account| decode(&account.data)).transpose()?);
+            }
+        }
+        
+        Ok(decoded)
+    }
+    
+    pub fn season(&self, season_number: u64) -> Result<(Pubkey, Season), CliError> {
+        let address = pda::season(season_number).0;
+        Ok((address, self.fetch(&address)?))
+    }
+    
+    /// Token program owning `mint`, for token-priced seasons
+    pub fn token_program(&self, mint: Option<Pubkey>) -> Result<Option<Pubkey>, CliError> {
+        mint.map(|mint| {
+            self.account(&mint)?
+                .map(|account| account.owner)
+                .ok_or(CliError::AccountNotFound(mint))
+        })
+        .transpose()
+    }
+    
+    /// Send `instructions` as one transaction signed by the payer and `signers`,
+    /// or with `--dry-run` only simulate it and report the balance changes it
+    /// would cause
+    pub fn execute_signed(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Outcome, CliError> {
+        let payer = self.wallet();
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer),
+            &all_signers,
+            blockhash,
+        );
+        
+        if !self.dry_run {
+            let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+            return Ok(Outcome::Sent {
+                signature: signature.to_string(),
+            });
+        }
+        
+        let message = &transaction.message;
+        let writable: Vec<Pubkey> = message
+            .account_keys
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| message.is_maybe_writable(*index, None))
+            .map(|(_, key)| *key)
+            .collect();
+        let before = self.rpc.get_multiple_accounts(&writable)?;
+        
+        let simulation = self
+            .rpc
+            .simulate_transaction_with_config(
+                &transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    accounts: Some(RpcSimulateTransactionAccountsConfig {
+                        encoding: None,
+                        addresses: writable.iter().map(|key| key.to_string()).collect(),
+                    }),
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )?
+            .value;
+        
+        // Accounts missing on either side were created or closed by the transaction
+        let after = simulation.accounts.unwrap_or_default();
+        let balance_changes = writable
+            .iter()
+            .zip(before)
+            .zip(after)
+            .filter_map(|((address, before), after)| {
+                let before = before.map(|account| {
+                    Snapshot::new(account.lamports, &account.owner, &account.data)
+                });
+                let after = after.and_then(|account| {
+                    let owner = Pubkey::from_str(&account.owner).ok()?;
+                    Some(Snapshot::new(account.lamports, &owner, &account.data.decode()?))
+                });
+                BalanceChange::between(*address, before, after)
+            })
+            .collect();
+        
+        Ok(Outcome::Simulated {
+            error: simulation.err.as_ref().map(describe_transaction_error),
+            units_consumed: simulation.units_consumed,
+            balance_changes,
+            logs: simulation.logs.unwrap_or_default(),
+        })
+    }
+}
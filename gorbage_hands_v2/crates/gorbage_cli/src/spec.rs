@@ -0,0 +1,322 @@
+//! Operator input files: TOML season specs and winner CSVs
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use gorbage_hands_client::InitializeSeasonParams;
+use gorbage_hands_v2::state::{DustPolicy, SeasonMode, UnclaimedPrizePolicy};
+use serde::Deserialize;
+
+use crate::error::CliError;
+
+/// `create-season` input. Times are unix timestamps in seconds.
+///
+/// ```toml
+/// name = "Season 4"
+/// entry_fee = 100000000
+/// registration_start = 1767225600
+/// registration_end = 1767830400
+/// season_end = 1768435200
+/// claim_deadline = 1771027200
+/// payout_bps = [5000, 3000, 2000]
+/// min_participants = 3
+/// challenge_window = 86400
+/// # optional: mint, max_participants, guaranteed_pool, dispute_bond,
+/// # rollover_season, mode = "raffle" with raffle_commitment = "<hex>",
+/// # dust = "first_place" | "treasury" | "next_season" (goes to rollover_season)
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeasonSpec {
+    pub name: String,
+    pub entry_fee: u64,
+    pub registration_start: i64,
+    pub registration_end: i64,
+    pub season_end: i64,
+    pub claim_deadline: i64,
+    pub payout_bps: Vec<u16>,
+    pub min_participants: u64,
+    #[serde(default)]
+    pub max_participants: u64,
+    #[serde(default)]
+    pub guaranteed_pool: u64,
+    #[serde(default)]
+    pub challenge_window: i64,
+    #[serde(default)]
+    pub dispute_bond: u64,
+    /// Season number receiving unclaimed prizes; the treasury gets them if unset
+    pub rollover_season: Option<u64>,
+    /// Where payout rounding dust goes, first place if unset
+    #[serde(default)]
+    pub dust: SpecDust,
+    /// Mint pricing the season, native lamports if unset
+    pub mint: Option<String>,
+    #[serde(default)]
+    pub mode: SpecMode,
+    /// Hex `sha256(secret)` of the raffle secret
+    pub raffle_commitment: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpecMode {
+    #[default]
+    Contest,
+    Raffle,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecDust {
+    #[default]
+    FirstPlace,
+    Treasury,
+    NextSeason,
+}
+
+impl SeasonSpec {
+    pub fn read(path: &Path) -> Result<Self, CliError> {
+        Self::parse(&read_file(path)?)
+    }
+    
+    pub fn parse(text: &str) -> Result<Self, CliError> {
+        toml::from_str(text).map_err(|err| CliError::Spec(err.to_string()))
+    }
+    
+    pub fn mint(&self) -> Result<Option<Pubkey>, CliError> {
+        self.mint.as_deref().map(parse_pubkey).transpose()
+    }
+    
+    pub fn params(&self) -> Result<InitializeSeasonParams, CliError> {
+        let raffle_commitment = match (self.mode, &self.raffle_commitment) {
+            (SpecMode::Raffle, Some(commitment)) => {
+                let bytes = hex::decode(commitment)
+                    .map_err(|err| CliError::Spec(format!("raffle_commitment: {err}")))?;
+                <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+                    CliError::Spec("raffle_commitment must be 32 bytes".to_string())
+                })?
+            }
+            (SpecMode::Raffle, None) => {
+                return Err(CliError::Spec("raffles need a raffle_commitment".to_string()))
+            }
+            (SpecMode::Contest, Some(_)) => {
+                return Err(CliError::Spec("raffle_commitment is only for raffles".to_string()))
+            }
+            (SpecMode::Contest, None) => [0; 32],
+        };
+        let dust_policy = match (self.dust, self.rollover_season) {
+            (SpecDust::FirstPlace, _) => DustPolicy::ToFirstPlace,
+            (SpecDust::Treasury, _) => DustPolicy::ToTreasury,
+            (SpecDust::NextSeason, Some(season_number)) => {
+                DustPolicy::ToNextSeason { season_number }
+            }
+            (SpecDust::NextSeason, None) => {
+                return Err(CliError::Spec("next_season dust needs a rollover_season".to_string()))
+            }
+        };
+        
+        Ok(InitializeSeasonParams {
+            name: self.name.clone(),
+            entry_fee: self.entry_fee,
+            registration_start: self.registration_start,
+            registration_end: self.registration_end,
+            season_end: self.season_end,
+            payout_bps: self.payout_bps.clone(),
+            min_participants: self.min_participants,
+            max_participants: self.max_participants,
+            guaranteed_pool: self.guaranteed_pool,
+            claim_deadline: self.claim_deadline,
+            unclaimed_policy: match self.rollover_season {
+                Some(season_number) => UnclaimedPrizePolicy::Rollover { season_number },
+                None => UnclaimedPrizePolicy::Treasury,
+            },
+            dust_policy,
+            challenge_window: self.challenge_window,
+            dispute_bond: self.dispute_bond,
+            mode: match self.mode {
+                SpecMode::Contest => SeasonMode::Contest,
+                SpecMode::Raffle => SeasonMode::Raffle,
+            },
+            raffle_commitment,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WinnerRow {
+    wallet: String,
+}
+
+/// Winner wallets from a CSV with a `wallet` header, in placement order
+pub fn read_winners(path: &Path) -> Result<Vec<Pubkey>, CliError> {
+    parse_winners(&read_file(path)?)
+}
+
+pub fn parse_winners(text: &str) -> Result<Vec<Pubkey>, CliError> {
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    
+    let mut winners = Vec::new();
+    for row in reader.deserialize::<WinnerRow>() {
+        winners.push(parse_pubkey(row?.wallet.trim())?);
+    }
+    
+    Ok(winners)
+}
+
+pub fn parse_pubkey(value: &str) -> Result<Pubkey, CliError> {
+    Pubkey::from_str(value).map_err(|_| CliError::InvalidPubkey(value.to_string()))
+}
+
+fn read_file(path: &Path) -> Result<String, CliError> {
+    std::fs::read_to_string(path).map_err(|err| CliError::Read {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SPEC: &str = r#"
+        name = "Season 4"
+        entry_fee = 100000000
+        registration_start = 1767225600
+        registration_end = 1767830400
+        season_end = 1768435200
+        claim_deadline = 1771027200
+        payout_bps = [5000, 3000, 2000]
+        min_participants = 3
+        challenge_window = 86400
+        dispute_bond = 50000000
+    "#;
+    
+    const COMMITMENT: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+    
+    fn spec_params(extra: &str) -> Result<InitializeSeasonParams, CliError> {
+        SeasonSpec::parse(&format!("{SPEC}\n{extra}"))?.params()
+    }
+    
+    fn spec_error(result: Result<InitializeSeasonParams, CliError>) -> String {
+        match result {
+            Err(CliError::Spec(message)) => message,
+            Err(err) => panic!("expected a spec error, got {err}"),
+            Ok(_) => panic!("expected a spec error"),
+        }
+    }
+    
+    #[test]
+    fn parses_a_minimal_spec_with_defaults() {
+        let spec = SeasonSpec::parse(SPEC).unwrap();
+        assert_eq!(spec.mint().unwrap(), None);
+        
+        let params = spec.params().unwrap();
+        assert_eq!(params.name, "Season 4");
+        assert_eq!(params.entry_fee, 100_000_000);
+        assert_eq!(params.payout_bps, vec![5000, 3000, 2000]);
+        assert_eq!((params.min_participants, params.max_participants), (3, 0));
+        assert_eq!(params.guaranteed_pool, 0);
+        assert_eq!((params.challenge_window, params.dispute_bond), (86_400, 50_000_000));
+        assert_eq!(params.unclaimed_policy, UnclaimedPrizePolicy::Treasury);
+        assert_eq!(params.dust_policy, DustPolicy::ToFirstPlace);
+        assert_eq!(params.mode, SeasonMode::Contest);
+        assert_eq!(params.raffle_commitment, [0; 32]);
+    }
+    
+    #[test]
+    fn parses_rollover_dust_and_raffle_settings() {
+        let params = spec_params(&format!(
+            "rollover_season = 5\ndust = \"next_season\"\nmode = \"raffle\"\n\
+             raffle_commitment = \"{COMMITMENT}\""
+        ))
+        .unwrap();
+        assert_eq!(
+            params.unclaimed_policy,
+            UnclaimedPrizePolicy::Rollover { season_number: 5 }
+        );
+        assert_eq!(params.dust_policy, DustPolicy::ToNextSeason { season_number: 5 });
+        assert_eq!(params.mode, SeasonMode::Raffle);
+        assert_eq!(params.raffle_commitment, [1; 32]);
+        
+        let params = spec_params("dust = \"treasury\"").unwrap();
+        assert_eq!(params.dust_policy, DustPolicy::ToTreasury);
+    }
+    
+    #[test]
+    fn reads_the_mint() {
+        let mint = Pubkey::new_unique();
+        let spec = SeasonSpec::parse(&format!("{SPEC}\nmint = \"{mint}\"")).unwrap();
+        assert_eq!(spec.mint().unwrap(), Some(mint));
+        
+        let spec = SeasonSpec::parse(&format!("{SPEC}\nmint = \"not-a-key\"")).unwrap();
+        assert!(matches!(spec.mint(), Err(CliError::InvalidPubkey(value)) if value == "not-a-key"));
+    }
+    
+    #[test]
+    fn rejects_malformed_specs() {
+        // Unknown and missing fields, wrong types
+        assert!(matches!(
+            SeasonSpec::parse(&format!("{SPEC}\nentry_feee = 1")),
+            Err(CliError::Spec(_))
+        ));
+        assert!(matches!(
+            SeasonSpec::parse("name = \"Season 4\""),
+            Err(CliError::Spec(_))
+        ));
+        assert!(matches!(
+            SeasonSpec::parse(&SPEC.replace("100000000", "\"100\"")),
+            Err(CliError::Spec(_))
+        ));
+        assert!(matches!(
+            SeasonSpec::parse(&format!("{SPEC}\nmode = \"lottery\"")),
+            Err(CliError::Spec(_))
+        ));
+        
+        assert_eq!(
+            spec_error(spec_params("mode = \"raffle\"")),
+            "raffles need a raffle_commitment"
+        );
+        assert_eq!(
+            spec_error(spec_params(&format!("raffle_commitment = \"{COMMITMENT}\""))),
+            "raffle_commitment is only for raffles"
+        );
+        assert_eq!(
+            spec_error(spec_params("mode = \"raffle\"\nraffle_commitment = \"0101\"")),
+            "raffle_commitment must be 32 bytes"
+        );
+        assert!(
+            spec_error(spec_params("mode = \"raffle\"\nraffle_commitment = \"zz\""))
+                .starts_with("raffle_commitment: ")
+        );
+        assert_eq!(
+            spec_error(spec_params("dust = \"next_season\"")),
+            "next_season dust needs a rollover_season"
+        );
+    }
+    
+    #[test]
+    fn parses_winners_in_placement_order() {
+        let winners = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let csv = format!("wallet\n{}\n  {}  \n", winners[0], winners[1]);
+        assert_eq!(parse_winners(&csv).unwrap(), winners);
+        assert!(parse_winners("wallet\n").unwrap().is_empty());
+    }
+    
+    #[test]
+    fn rejects_malformed_winners() {
+        assert!(matches!(
+            parse_winners("wallet\nnot-a-key\n"),
+            Err(CliError::InvalidPubkey(value)) if value == "not-a-key"
+        ));
+        let wallet = Pubkey::new_unique();
+        assert!(matches!(
+            parse_winners(&format!("winner\n{wallet}\n")),
+            Err(CliError::Csv(_))
+        ));
+        assert!(matches!(
+            parse_winners(&format!("wallet\n{wallet},extra\n")),
+            Err(CliError::Csv(_))
+        ));
+    }
+}
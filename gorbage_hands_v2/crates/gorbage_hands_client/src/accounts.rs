@@ -0,0 +1,38 @@
+//! Decoding of program accounts from raw account data
+
+use anchor_lang::AccountDeserialize;
+use gorbage_hands_v2::state::{
+    OracleRegistry, Participant, ParticipantPage, ProgramConfig, Season, SeasonRegistry,
+};
+
+use crate::error::ClientError;
+
+/// Decode an account, checking its discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    let mut data = data;
+    T::try_deserialize(&mut data).map_err(|err| ClientError::AccountDecode(err.to_string()))
+}
+
+pub fn program_config(data: &[u8]) -> Result<ProgramConfig, ClientError> {
+    decode(data)
+}
+
+pub fn season_registry(data: &[u8]) -> Result<SeasonRegistry, ClientError> {
+    decode(data)
+}
+
+pub fn oracle_registry(data: &[u8]) -> Result<OracleRegistry, ClientError> {
+    decode(data)
+}
+
+pub fn season(data: &[u8]) -> Result<Season, ClientError> {
+    decode(data)
+}
+
+pub fn participant(data: &[u8]) -> Result<Participant, ClientError> {
+    decode(data)
+}
+
+pub fn participant_page(data: &[u8]) -> Result<ParticipantPage, ClientError> {
+    decode(data)
+}
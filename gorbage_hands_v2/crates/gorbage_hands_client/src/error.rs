@@ -0,0 +1,166 @@
+//! Client errors and mapping of on-chain error codes back to `GorbageError`
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::solana_program::instruction::InstructionError;
+use gorbage_hands_v2::error::GorbageError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("failed to decode account: {0}")]
+    AccountDecode(String),
+    
+    #[error("season is priced in a mint but no token program was given")]
+    TokenProgramRequired,
+}
+
+/// Every `GorbageError` variant, in declaration order.
+/// Keep in sync with `gorbage_hands_v2::error::GorbageError`, the tests check it.
+const ALL_ERRORS: &[GorbageError] = &[
+    GorbageError::SeasonNotActive,
+    GorbageError::SeasonStillActive,
+    GorbageError::RegistrationClosed,
+    GorbageError::SeasonNotEnded,
+    GorbageError::WinnersAlreadySet,
+    GorbageError::WinnersNotSet,
+    GorbageError::InvalidWinnerCount,
+    GorbageError::AlreadyRegistered,
+    GorbageError::NotRegistered,
+    GorbageError::NotAWinner,
+    GorbageError::PrizeAlreadyClaimed,
+    GorbageError::Unauthorized,
+    GorbageError::InvalidEntryFee,
+    GorbageError::Overflow,
+    GorbageError::SeasonNameTooLong,
+    GorbageError::InsufficientVaultFunds,
+    GorbageError::InvalidPlacement,
+    GorbageError::FeeAlreadyCollected,
+    GorbageError::RegistrationNotEnded,
+    GorbageError::NoPrizePool,
+    GorbageError::InvalidPayoutSchedule,
+    GorbageError::PayoutScheduleSumMismatch,
+    GorbageError::RegistrationNotOpen,
+    GorbageError::FeeNotCollected,
+    GorbageError::PrizesNotAssigned,
+    GorbageError::SeasonClosed,
+    GorbageError::SeasonCancelled,
+    GorbageError::SeasonNotCancelled,
+    GorbageError::SeasonAlreadySettled,
+    GorbageError::TreasuryRequired,
+    GorbageError::TreasuryMismatch,
+    GorbageError::AlreadyRefunded,
+    GorbageError::MinParticipantsNotMet,
+    GorbageError::InvalidParticipantLimits,
+    GorbageError::SeasonFull,
+    GorbageError::SpotsAvailable,
+    GorbageError::WaitlistRequired,
+    GorbageError::WaitlistFull,
+    GorbageError::AlreadyOnWaitlist,
+    GorbageError::ParticipantInactive,
+    GorbageError::TokenAccountsRequired,
+    GorbageError::InvalidMint,
+    GorbageError::InvalidTokenAccount,
+    GorbageError::InvalidTokenProgram,
+    GorbageError::InvalidContribution,
+    GorbageError::ContributionsClosed,
+    GorbageError::InvalidClaimDeadline,
+    GorbageError::ClaimDeadlinePassed,
+    GorbageError::ClaimWindowOpen,
+    GorbageError::RolloverSeasonRequired,
+    GorbageError::InvalidRolloverSeason,
+    GorbageError::ParticipantNotClosable,
+    GorbageError::InvalidRemainingAccounts,
+    GorbageError::DuplicateWinner,
+    GorbageError::PayoutExceedsPool,
+    GorbageError::NotMerkleSettlement,
+    GorbageError::InvalidLeafCount,
+    GorbageError::InvalidClaimIndex,
+    GorbageError::InvalidMerkleProof,
+    GorbageError::GameServerNotSet,
+    GorbageError::ScoreMissing,
+    GorbageError::IncompleteScoreboard,
+    GorbageError::AlreadyRanked,
+    GorbageError::LeaderboardMismatch,
+    GorbageError::InvalidScoreAttestation,
+    GorbageError::StaleScoreNonce,
+    GorbageError::InvalidAttestorSet,
+    GorbageError::NotAnAttestor,
+    GorbageError::AlreadyAttested,
+    GorbageError::ResultHashMismatch,
+    GorbageError::InvalidChallengeWindow,
+    GorbageError::ChallengeWindowOpen,
+    GorbageError::ChallengeWindowClosed,
+    GorbageError::OpenDisputes,
+    GorbageError::NotEnoughAttestations,
+    GorbageError::InvalidDispute,
+    GorbageError::RaffleModeRequired,
+    GorbageError::ContestModeRequired,
+    GorbageError::InvalidRaffleCommitment,
+    GorbageError::InvalidRaffleSecret,
+    GorbageError::RaffleAlreadyRevealed,
+    GorbageError::RaffleNotRevealed,
+    GorbageError::InvalidDrawSequence,
+    GorbageError::PayoutBelowPool,
+    GorbageError::DustAboveLeafCount,
+    GorbageError::RaffleNotLocked,
+    GorbageError::RaffleAlreadyLocked,
+    GorbageError::RaffleSlotPending,
+    GorbageError::RaffleRevealExpired,
+    GorbageError::SeasonNotOver,
+    GorbageError::RaffleLocked,
+    GorbageError::InvalidParticipantPage,
+    GorbageError::SeasonEnded,
+    GorbageError::InvalidDisputeBond,
+    GorbageError::ClaimBitmapRequired,
+];
+
+/// Map a custom program error code to the `GorbageError` it stands for
+pub fn gorbage_error(code: u32) -> Option<GorbageError> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    let error = *ALL_ERRORS.get(index as usize)?;
+    
+    (u32::from(error) == code).then_some(error)
+}
+
+/// Map the error of a failed program instruction to a `GorbageError`, if it is one
+pub fn from_instruction_error(error: &InstructionError) -> Option<GorbageError> {
+    match error {
+        InstructionError::Custom(code) => gorbage_error(*code),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// `GorbageError` variant names in the order the program declares them
+    fn declared_errors() -> Vec<String> {
+        include_str!("../../../programs/gorbage_hands_v2/src/error.rs")
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.ends_with(',') && !line.starts_with("#["))
+            .map(|line| line.trim_end_matches(',').to_string())
+            .collect()
+    }
+    
+    #[test]
+    fn error_list_follows_the_program() {
+        let listed: Vec<String> = ALL_ERRORS.iter().map(GorbageError::name).collect();
+        assert_eq!(listed, declared_errors());
+    }
+    
+    #[test]
+    fn every_error_code_maps_back_to_its_error() {
+        for (index, error) in ALL_ERRORS.iter().enumerate() {
+            let code = u32::from(*error);
+            assert_eq!(code, ERROR_CODE_OFFSET + index as u32);
+            let mapped = from_instruction_error(&InstructionError::Custom(code));
+            assert_eq!(mapped.map(|error| error.name()), Some(error.name()));
+        }
+        
+        let past_the_end = ERROR_CODE_OFFSET + ALL_ERRORS.len() as u32;
+        assert!(gorbage_error(past_the_end).is_none());
+        assert!(gorbage_error(ERROR_CODE_OFFSET - 1).is_none());
+        assert!(from_instruction_error(&InstructionError::InvalidArgument).is_none());
+    }
+}
@@ -0,0 +1,884 @@
+//! Instruction builders for every program instruction
+//!
+//! Builders that act on an existing season take its decoded `Season` and fill
+//! in every PDA. Token-priced seasons also need the mint's token program
+//! (SPL Token or Token-2022), since the season only records the mint.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey, System};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{Id, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use gorbage_hands_v2::constants::PARTICIPANT_PAGE_SIZE;
+use gorbage_hands_v2::instructions::{DisputeResolution, InitializeSeasonParams};
+use gorbage_hands_v2::state::{DustPolicy, ParticipantPage, Season, UnclaimedPrizePolicy};
+use gorbage_hands_v2::{accounts, instruction, ID};
+
+use crate::error::ClientError;
+use crate::pda;
+
+/// Mint and token program of a token-priced season
+struct SeasonToken {
+    mint: Pubkey,
+    token_program: Pubkey,
+}
+
+impl SeasonToken {
+    /// `None` for native lamport seasons
+    fn resolve(
+        mint: Option<Pubkey>,
+        token_program: Option<Pubkey>,
+    ) -> Result<Option<Self>, ClientError> {
+        match mint {
+            Some(mint) => {
+                let token_program = token_program.ok_or(ClientError::TokenProgramRequired)?;
+                Ok(Some(Self { mint, token_program }))
+            }
+            None => Ok(None),
+        }
+    }
+    
+    fn account_of(&self, wallet: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(wallet, &self.mint, &self.token_program)
+    }
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn season_key(season: &Season) -> Pubkey {
+    pda::season(season.season_number).0
+}
+
+fn waitlist(season: &Season) -> Option<Pubkey> {
+    season
+        .is_capped()
+        .then(|| pda::waitlist(&season_key(season)).0)
+}
+
+/// Claim bitmap of the current results, if they are a Merkle settlement
+fn claim_bitmap(season_key: &Pubkey, season: &Season) -> Option<Pubkey> {
+    season
+        .merkle_root
+        .map(|_| pda::claim_bitmap(season_key, season.settlement_epoch).0)
+}
+
+/// Roster page number `page`, as opposed to the page holding a participant index
+fn page_key(season_key: &Pubkey, page: u64) -> Pubkey {
+    pda::participant_page(season_key, page * PARTICIPANT_PAGE_SIZE as u64).0
+}
+
+/// Participant accounts of `owners` as writable remaining accounts
+fn participant_metas(season_key: &Pubkey, owners: &[Pubkey]) -> Vec<AccountMeta> {
+    owners
+        .iter()
+        .map(|owner| AccountMeta::new(pda::participant(season_key, owner).0, false))
+        .collect()
+}
+
+/// One-time setup of the program config and season registry; `admin` becomes the global admin
+pub fn initialize_config(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            admin: *admin,
+            config: pda::config().0,
+            season_registry: pda::season_registry().0,
+            system_program: System::id(),
+        },
+        instruction::InitializeConfig {},
+    )
+}
+
+pub fn transfer_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::TransferAdmin {
+            admin: *admin,
+            config: pda::config().0,
+        },
+        instruction::TransferAdmin { new_admin: *new_admin },
+    )
+}
+
+pub fn set_game_server(admin: &Pubkey, game_server: &Pubkey) -> Instruction {
+    build(
+        accounts::SetGameServer {
+            admin: *admin,
+            config: pda::config().0,
+        },
+        instruction::SetGameServer {
+            game_server: *game_server,
+        },
+    )
+}
+
+pub fn initialize_oracle_registry(
+    admin: &Pubkey,
+    attestors: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        accounts::InitializeOracleRegistry {
+            admin: *admin,
+            config: pda::config().0,
+            oracle_registry: pda::oracle_registry().0,
+            system_program: System::id(),
+        },
+        instruction::InitializeOracleRegistry {
+            attestors,
+            threshold,
+        },
+    )
+}
+
+fn update_oracle_registry(admin: &Pubkey) -> accounts::UpdateOracleRegistry {
+    accounts::UpdateOracleRegistry {
+        admin: *admin,
+        config: pda::config().0,
+        oracle_registry: pda::oracle_registry().0,
+    }
+}
+
+pub fn add_attestor(admin: &Pubkey, attestor: &Pubkey) -> Instruction {
+    build(
+        update_oracle_registry(admin),
+        instruction::AddAttestor {
+            attestor: *attestor,
+        },
+    )
+}
+
+pub fn remove_attestor(admin: &Pubkey, attestor: &Pubkey) -> Instruction {
+    build(
+        update_oracle_registry(admin),
+        instruction::RemoveAttestor {
+            attestor: *attestor,
+        },
+    )
+}
+
+pub fn rotate_attestor(
+    admin: &Pubkey,
+    old_attestor: &Pubkey,
+    new_attestor: &Pubkey,
+) -> Instruction {
+    build(
+        update_oracle_registry(admin),
+        instruction::RotateAttestor {
+            old_attestor: *old_attestor,
+            new_attestor: *new_attestor,
+        },
+    )
+}
+
+pub fn set_attestation_threshold(admin: &Pubkey, threshold: u8) -> Instruction {
+    build(
+        update_oracle_registry(admin),
+        instruction::SetAttestationThreshold { threshold },
+    )
+}
+
+/// Create season `season_number`, which must be the config's `next_season_number`.
+/// `mint` with its token program prices the season in tokens; `authority`
+/// funds any guaranteed pool from its associated token account.
+pub fn initialize_season(
+    authority: &Pubkey,
+    season_number: u64,
+    params: InitializeSeasonParams,
+    mint: Option<Pubkey>,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(mint, token_program)?;
+    let season = pda::season(season_number).0;
+    let vault = pda::vault(&season).0;
+    let waitlist = (params.max_participants > 0).then(|| pda::waitlist(&season).0);
+    
+    Ok(build(
+        accounts::InitializeSeason {
+            authority: *authority,
+            config: pda::config().0,
+            season,
+            season_registry: pda::season_registry().0,
+            vault,
+            waitlist,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|token| token.account_of(&vault)),
+            authority_token_account: token.as_ref().map(|token| token.account_of(authority)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            associated_token_program: token.as_ref().map(|_| associated_token::ID),
+            system_program: System::id(),
+        },
+        instruction::InitializeSeason { params },
+    ))
+}
+
+/// Open roster page `page` of season `season_number`, paid by its authority.
+/// Send it with `initialize_season` for page 0, and before each page fills for
+/// the next one, otherwise the first registrant on the new page pays for it.
+pub fn open_participant_page(authority: &Pubkey, season_number: u64, page: u64) -> Instruction {
+    let season = pda::season(season_number).0;
+    build(
+        accounts::OpenParticipantPage {
+            authority: *authority,
+            season,
+            participant_page: page_key(&season, page),
+            system_program: System::id(),
+        },
+        instruction::OpenParticipantPage { page },
+    )
+}
+
+pub fn register_participant(
+    owner: &Pubkey,
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    let vault = pda::vault(&season_key).0;
+    
+    Ok(build(
+        accounts::RegisterParticipant {
+            participant_owner: *owner,
+            season: season_key,
+            participant: pda::participant(&season_key, owner).0,
+            participant_page: pda::participant_page(&season_key, season.next_participant_index).0,
+            vault,
+            waitlist: season.is_capped().then(|| pda::waitlist(&season_key).0),
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            participant_token_account: token.as_ref().map(|token| token.account_of(owner)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::RegisterParticipant {},
+    ))
+}
+
+/// Queue `wallet` for a spot in a full capped season
+pub fn join_waitlist(wallet: &Pubkey, season: &Season) -> Instruction {
+    let season_key = season_key(season);
+    build(
+        accounts::JoinWaitlist {
+            wallet: *wallet,
+            season: season_key,
+            participant: pda::participant(&season_key, wallet).0,
+            waitlist: pda::waitlist(&season_key).0,
+        },
+        instruction::JoinWaitlist {},
+    )
+}
+
+/// Leave during registration with a refund of the entry fee
+pub fn withdraw_participant(
+    owner: &Pubkey,
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::WithdrawParticipant {
+            participant_owner: *owner,
+            season: season_key,
+            participant: pda::participant(&season_key, owner).0,
+            vault: pda::vault(&season_key).0,
+            waitlist: waitlist(season),
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            participant_token_account: token.as_ref().map(|token| token.account_of(owner)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::WithdrawParticipant {},
+    ))
+}
+
+/// Remove `owner` from the season, refunding the entry fee during registration
+pub fn disqualify_participant(
+    authority: &Pubkey,
+    season: &Season,
+    owner: &Pubkey,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::DisqualifyParticipant {
+            authority: *authority,
+            season: season_key,
+            participant: pda::participant(&season_key, owner).0,
+            participant_owner: *owner,
+            vault: pda::vault(&season_key).0,
+            waitlist: waitlist(season),
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            participant_token_account: token.as_ref().map(|token| token.account_of(owner)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::DisqualifyParticipant {},
+    ))
+}
+
+pub fn contribute_to_pool(
+    sponsor: &Pubkey,
+    season: &Season,
+    amount: u64,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::ContributeToPool {
+            sponsor: *sponsor,
+            season: season_key,
+            sponsorship: pda::sponsorship(&season_key, sponsor).0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            sponsor_token_account: token.as_ref().map(|token| token.account_of(sponsor)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::ContributeToPool { amount },
+    ))
+}
+
+pub fn submit_score(
+    game_server: &Pubkey,
+    season: &Season,
+    owner: &Pubkey,
+    score: u64,
+    nonce: u64,
+) -> Instruction {
+    let season_key = season_key(season);
+    build(
+        accounts::SubmitScore {
+            config: pda::config().0,
+            game_server: *game_server,
+            season: season_key,
+            participant: pda::participant(&season_key, owner).0,
+        },
+        instruction::SubmitScore { score, nonce },
+    )
+}
+
+/// Submit a score signed by the game server. The ed25519 verify instruction
+/// carrying that signature must come right before this one.
+pub fn submit_attested_score(
+    owner: &Pubkey,
+    season: &Season,
+    score: u64,
+    nonce: u64,
+) -> Instruction {
+    let season_key = season_key(season);
+    build(
+        accounts::SubmitAttestedScore {
+            participant_owner: *owner,
+            config: pda::config().0,
+            season: season_key,
+            participant: pda::participant(&season_key, owner).0,
+            instructions_sysvar: sysvar::instructions::ID,
+        },
+        instruction::SubmitAttestedScore { score, nonce },
+    )
+}
+
+/// Take the platform fee to `treasury` once registration has closed
+pub fn collect_fee(
+    authority: &Pubkey,
+    treasury: &Pubkey,
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::CollectFee {
+            authority: *authority,
+            treasury: *treasury,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            treasury_token_account: token.as_ref().map(|token| token.account_of(treasury)),
+            authority_token_account: token.as_ref().map(|token| token.account_of(authority)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::CollectFee {},
+    ))
+}
+
+/// Commit a raffle season to a future slot once registration has closed
+/// (anyone can send it)
+pub fn lock_raffle(season: &Season) -> Instruction {
+    build(
+        accounts::LockRaffle {
+            season: season_key(season),
+        },
+        instruction::LockRaffle {},
+    )
+}
+
+pub fn reveal_raffle(authority: &Pubkey, season: &Season, secret: [u8; 32]) -> Instruction {
+    build(
+        accounts::RevealRaffle {
+            authority: *authority,
+            season: season_key(season),
+            slot_hashes: sysvar::slot_hashes::ID,
+        },
+        instruction::RevealRaffle { secret },
+    )
+}
+
+/// Draw raffle winners, passing the wallet hit by each draw in order
+pub fn draw_winners(authority: &Pubkey, season: &Season, drawn: &[Pubkey]) -> Instruction {
+    let season_key = season_key(season);
+    let mut ix = build(
+        accounts::DrawWinners {
+            authority: *authority,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+        },
+        instruction::DrawWinners {},
+    );
+    ix.accounts.extend(participant_metas(&season_key, drawn));
+    
+    ix
+}
+
+/// Rank a batch of scored wallets into the season leaderboard. Split the
+/// scoreboard into batches that fit a transaction; together they must cover
+/// every wallet with a score before `settle_season` with `rank_by_score`.
+pub fn rank_scores(authority: &Pubkey, season: &Season, participants: &[Pubkey]) -> Instruction {
+    let season_key = season_key(season);
+    let mut ix = build(
+        accounts::RankScores {
+            authority: *authority,
+            season: season_key,
+        },
+        instruction::RankScores {},
+    );
+    ix.accounts.extend(participant_metas(&season_key, participants));
+    
+    ix
+}
+
+/// Propose season results with `participants` as the winning wallets in
+/// placement order. With `rank_by_score` they must be the ranked leaderboard,
+/// see `settle_season_by_score`.
+pub fn settle_season(
+    authority: &Pubkey,
+    season: &Season,
+    participants: &[Pubkey],
+    rank_by_score: bool,
+) -> Instruction {
+    let season_key = season_key(season);
+    let mut ix = build(
+        accounts::SettleSeason {
+            authority: *authority,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+        },
+        instruction::SettleSeason { rank_by_score },
+    );
+    ix.accounts.extend(participant_metas(&season_key, participants));
+    
+    ix
+}
+
+/// Propose the ranked leaderboard of `season` as its results
+pub fn settle_season_by_score(authority: &Pubkey, season: &Season) -> Instruction {
+    let winners: Vec<Pubkey> = season.leaderboard.iter().map(|entry| entry.owner).collect();
+    settle_season(authority, season, &winners, true)
+}
+
+/// Propose a Merkle distribution of `total_payout` over `leaf_count` leaves,
+/// built with `gorbage_merkle`
+pub fn settle_season_with_root(
+    authority: &Pubkey,
+    season: &Season,
+    merkle_root: [u8; 32],
+    total_payout: u64,
+    leaf_count: u32,
+) -> Instruction {
+    let season_key = season_key(season);
+    build(
+        accounts::SettleSeasonWithRoot {
+            authority: *authority,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+            claim_bitmap: pda::claim_bitmap(&season_key, season.settlement_epoch).0,
+            system_program: System::id(),
+        },
+        instruction::SettleSeasonWithRoot {
+            merkle_root,
+            total_payout,
+            leaf_count,
+        },
+    )
+}
+
+/// Confirm the season's proposed results (registered attestors only)
+pub fn attest_results(attestor: &Pubkey, season: &Season) -> Instruction {
+    build(
+        accounts::AttestResults {
+            attestor: *attestor,
+            config: pda::config().0,
+            oracle_registry: pda::oracle_registry().0,
+            season: season_key(season),
+        },
+        instruction::AttestResults { result_hash: season.result_hash },
+    )
+}
+
+pub fn open_dispute(disputer: &Pubkey, season: &Season) -> Instruction {
+    let season_key = season_key(season);
+    build(
+        accounts::OpenDispute {
+            disputer: *disputer,
+            season: season_key,
+            participant: pda::participant(&season_key, disputer).0,
+            dispute: pda::dispute(&season_key, season.settlement_epoch, disputer).0,
+            system_program: System::id(),
+        },
+        instruction::OpenDispute {},
+    )
+}
+
+/// Resolve the dispute `disputer` opened against the results of `settlement_epoch`
+pub fn resolve_dispute(
+    admin: &Pubkey,
+    season: &Season,
+    settlement_epoch: u32,
+    disputer: &Pubkey,
+    resolution: DisputeResolution,
+) -> Instruction {
+    let season_key = season_key(season);
+    build(
+        accounts::ResolveDispute {
+            admin: *admin,
+            config: pda::config().0,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+            dispute: pda::dispute(&season_key, settlement_epoch, disputer).0,
+            disputer: *disputer,
+            treasury: season.treasury,
+            season_authority: season.authority,
+            claim_bitmap: claim_bitmap(&season_key, season),
+        },
+        instruction::ResolveDispute { resolution },
+    )
+}
+
+/// Finalize a settlement. The accounts for routing its rounding dust follow the
+/// season's dust policy; a next-season policy also passes the treasury, which
+/// takes the dust if that season can't.
+pub fn finalize_settlement(
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    let (treasury, rollover_season) = match season.dust_policy {
+        DustPolicy::ToFirstPlace => (None, None),
+        DustPolicy::ToTreasury => (Some(season.treasury), None),
+        DustPolicy::ToNextSeason { season_number } => {
+            (Some(season.treasury), Some(pda::season(season_number).0))
+        }
+    };
+    let rollover_vault = rollover_season.map(|rollover| pda::vault(&rollover).0);
+    
+    Ok(build(
+        accounts::FinalizeSettlement {
+            config: pda::config().0,
+            oracle_registry: pda::oracle_registry().0,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            treasury,
+            treasury_token_account: token
+                .as_ref()
+                .zip(treasury)
+                .map(|(token, treasury)| token.account_of(&treasury)),
+            rollover_season,
+            rollover_vault,
+            rollover_vault_token_account: token
+                .as_ref()
+                .zip(rollover_vault)
+                .map(|(token, vault)| token.account_of(&vault)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::FinalizeSettlement {},
+    ))
+}
+
+pub fn claim_prize(
+    winner: &Pubkey,
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::ClaimPrize {
+            winner: *winner,
+            season: season_key,
+            participant: pda::participant(&season_key, winner).0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            winner_token_account: token.as_ref().map(|token| token.account_of(winner)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::ClaimPrize {},
+    ))
+}
+
+/// Claim leaf `index` of a Merkle settlement with its sibling `proof`
+pub fn claim_prize_with_proof(
+    winner: &Pubkey,
+    season: &Season,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::ClaimPrizeWithProof {
+            winner: *winner,
+            season: season_key,
+            claim_bitmap: pda::claim_bitmap(&season_key, season.settlement_epoch).0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            winner_token_account: token.as_ref().map(|token| token.account_of(winner)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::ClaimPrizeWithProof {
+            index,
+            amount,
+            proof,
+        },
+    ))
+}
+
+/// Cancel the season (season authority or admin). Pass `treasury`, which must
+/// also sign, once the platform fee has been collected so it can be returned.
+pub fn cancel_season(
+    authority: &Pubkey,
+    season: &Season,
+    treasury: Option<Pubkey>,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::CancelSeason {
+            authority: *authority,
+            config: pda::config().0,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+            vault: pda::vault(&season_key).0,
+            treasury,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            treasury_token_account: token
+                .as_ref()
+                .zip(treasury)
+                .map(|(token, treasury)| token.account_of(&treasury)),
+            season_authority: season.authority,
+            season_authority_token_account: token
+                .as_ref()
+                .map(|token| token.account_of(&season.authority)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::CancelSeason {},
+    ))
+}
+
+/// Refund `owner`'s entry fee from a cancelled season
+pub fn claim_refund(
+    owner: &Pubkey,
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::ClaimRefund {
+            participant_owner: *owner,
+            season: season_key,
+            participant: pda::participant(&season_key, owner).0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            participant_token_account: token.as_ref().map(|token| token.account_of(owner)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::ClaimRefund {},
+    ))
+}
+
+/// Refund `sponsor`'s contribution to a cancelled season
+pub fn claim_sponsor_refund(
+    sponsor: &Pubkey,
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    Ok(build(
+        accounts::ClaimSponsorRefund {
+            sponsor: *sponsor,
+            season: season_key,
+            sponsorship: pda::sponsorship(&season_key, sponsor).0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            sponsor_token_account: token.as_ref().map(|token| token.account_of(sponsor)),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::ClaimSponsorRefund {},
+    ))
+}
+
+/// Close the season, sending any remaining balance back to `authority`.
+/// Treasury and rollover accounts are filled in from the season's unclaimed
+/// prize policy, whether or not anything is left unclaimed.
+pub fn close_season(
+    authority: &Pubkey,
+    season: &Season,
+    token_program: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let token = SeasonToken::resolve(season.mint, token_program)?;
+    let season_key = season_key(season);
+    
+    // The treasury also takes prizes a rollover season can no longer accept
+    let (treasury, rollover_season) = match season.unclaimed_policy {
+        UnclaimedPrizePolicy::Treasury => (Some(season.treasury), None),
+        UnclaimedPrizePolicy::Rollover { season_number } => {
+            (Some(season.treasury), Some(pda::season(season_number).0))
+        }
+    };
+    let rollover_vault = rollover_season.map(|rollover| pda::vault(&rollover).0);
+    
+    Ok(build(
+        accounts::CloseSeason {
+            authority: *authority,
+            season: season_key,
+            season_registry: pda::season_registry().0,
+            vault: pda::vault(&season_key).0,
+            mint: token.as_ref().map(|token| token.mint),
+            vault_token_account: token.as_ref().map(|_| season.vault_token_account),
+            authority_token_account: token.as_ref().map(|token| token.account_of(authority)),
+            treasury,
+            treasury_token_account: token
+                .as_ref()
+                .zip(treasury)
+                .map(|(token, treasury)| token.account_of(&treasury)),
+            rollover_season,
+            rollover_vault,
+            rollover_vault_token_account: token
+                .as_ref()
+                .zip(rollover_vault)
+                .map(|(token, vault)| token.account_of(&vault)),
+            claim_bitmap: claim_bitmap(&season_key, season),
+            token_program: token.as_ref().map(|token| token.token_program),
+            system_program: System::id(),
+        },
+        instruction::CloseSeason {},
+    ))
+}
+
+/// Close `owner`'s participant account once nothing is owed, returning its rent
+pub fn close_participant(owner: &Pubkey, season: &Season) -> Instruction {
+    let season_key = season_key(season);
+    build(
+        accounts::CloseParticipant {
+            participant_owner: *owner,
+            season: season_key,
+            participant: pda::participant(&season_key, owner).0,
+        },
+        instruction::CloseParticipant {},
+    )
+}
+
+/// Batch close of the participant accounts of `owners`, rent going back to each owner
+pub fn close_participants(payer: &Pubkey, season: &Season, owners: &[Pubkey]) -> Instruction {
+    let season_key = season_key(season);
+    let mut ix = build(
+        accounts::CloseParticipants {
+            payer: *payer,
+            season: season_key,
+        },
+        instruction::CloseParticipants {},
+    );
+    for owner in owners {
+        ix.accounts.push(AccountMeta::new(
+            pda::participant(&season_key, owner).0,
+            false,
+        ));
+        ix.accounts.push(AccountMeta::new(*owner, false));
+    }
+    
+    ix
+}
+
+/// Close a roster page of a closed or cancelled season, returning the rent to
+/// the wallet that opened it (anyone can send it)
+pub fn close_participant_page(payer: &Pubkey, participant_page: &ParticipantPage) -> Instruction {
+    build(
+        accounts::CloseParticipantPage {
+            payer: *payer,
+            season: participant_page.season,
+            rent_recipient: participant_page.payer,
+            participant_page: page_key(&participant_page.season, participant_page.page),
+        },
+        instruction::CloseParticipantPage {},
+    )
+}
+
+/// Refresh the registry entries of `season_numbers` (anyone can send it)
+pub fn refresh_season_registry(season_numbers: &[u64]) -> Instruction {
+    let mut ix = build(
+        accounts::RefreshSeasonRegistry {
+            season_registry: pda::season_registry().0,
+        },
+        instruction::RefreshSeasonRegistry {},
+    );
+    ix.accounts.extend(
+        season_numbers
+            .iter()
+            .map(|number| AccountMeta::new_readonly(pda::season(*number).0, false)),
+    );
+    
+    ix
+}
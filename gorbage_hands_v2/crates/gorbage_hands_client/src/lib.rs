@@ -0,0 +1,15 @@
+//! Client helpers for the gorbage_hands_v2 program: PDA derivation, instruction
+//! builders, account decoding and error mapping.
+//!
+//! Builds on the program crate with `no-entrypoint` only, so it works without
+//! the `cpi` feature.
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+
+pub use gorbage_hands_v2::ID as PROGRAM_ID;
+pub use gorbage_hands_v2::error::GorbageError;
+pub use gorbage_hands_v2::instructions::InitializeSeasonParams;
+pub use gorbage_hands_v2::state;
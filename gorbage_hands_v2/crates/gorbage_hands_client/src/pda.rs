@@ -0,0 +1,71 @@
+//! Program derived addresses, each returned with its bump
+
+use anchor_lang::prelude::Pubkey;
+use gorbage_hands_v2::constants::{
+    CLAIM_BITMAP_SEED, CONFIG_SEED, DISPUTE_SEED, ORACLE_REGISTRY_SEED, PARTICIPANT_PAGE_SEED,
+    PARTICIPANT_SEED, SEASON_REGISTRY_SEED, SEASON_SEED, SPONSORSHIP_SEED, VAULT_SEED,
+    WAITLIST_SEED,
+};
+use gorbage_hands_v2::state::ParticipantPage;
+use gorbage_hands_v2::ID;
+
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &ID)
+}
+
+pub fn season_registry() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEASON_REGISTRY_SEED], &ID)
+}
+
+pub fn oracle_registry() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORACLE_REGISTRY_SEED, config().0.as_ref()], &ID)
+}
+
+pub fn season(season_number: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEASON_SEED, &season_number.to_le_bytes()], &ID)
+}
+
+/// Vault holding the season's lamports, and the authority of its token account
+pub fn vault(season: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, season.as_ref()], &ID)
+}
+
+pub fn participant(season: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PARTICIPANT_SEED, season.as_ref(), owner.as_ref()], &ID)
+}
+
+/// Roster page holding participant `index`
+pub fn participant_page(season: &Pubkey, index: u64) -> (Pubkey, u8) {
+    let page = ParticipantPage::page_for(index);
+    Pubkey::find_program_address(
+        &[PARTICIPANT_PAGE_SEED, season.as_ref(), &page.to_le_bytes()],
+        &ID,
+    )
+}
+
+pub fn waitlist(season: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WAITLIST_SEED, season.as_ref()], &ID)
+}
+
+pub fn sponsorship(season: &Pubkey, sponsor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SPONSORSHIP_SEED, season.as_ref(), sponsor.as_ref()], &ID)
+}
+
+pub fn claim_bitmap(season: &Pubkey, settlement_epoch: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CLAIM_BITMAP_SEED, season.as_ref(), &settlement_epoch.to_le_bytes()],
+        &ID,
+    )
+}
+
+pub fn dispute(season: &Pubkey, settlement_epoch: u32, disputer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DISPUTE_SEED,
+            season.as_ref(),
+            &settlement_epoch.to_le_bytes(),
+            disputer.as_ref(),
+        ],
+        &ID,
+    )
+}
@@ -0,0 +1,453 @@
+//! In-process harness for the gorbage_hands_v2 program on LiteSVM.
+//!
+//! Loads the program built by `scripts/build-program.sh` or `anchor build` (or
+//! the file named by `GORBAGE_HANDS_SO`) and sets the clock directly, so tests
+//! can step across `registration_start`, `registration_end` and `season_end`. A
+//! dedicated fee payer signs every transaction, so wallets under test move by
+//! exactly the amounts the program transfers.
+//!
+//! The tests cover every `GorbageError` the program can return. Not covered:
+//! `SeasonNotActive` and `SeasonStillActive`, which no instruction raises;
+//! `Overflow`, `InsufficientVaultFunds` and `InvalidPlacement`, defensive checks
+//! the lamport and token amounts never reach; `NotMerkleSettlement`, since the
+//! claim bitmap only exists for Merkle settlements; and `InvalidDispute`, which
+//! the dispute PDA seeds rule out first.
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::{AccountDeserialize, Event};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use gorbage_hands_client::accounts::decode;
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_v2::constants::PARTICIPANT_PAGE_SIZE;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::state::{
+    DustPolicy, Participant, ParticipantPage, Season, SeasonMode, SeasonPhase, SeasonRegistry,
+    UnclaimedPrizePolicy,
+};
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use litesvm::LiteSVM;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+
+/// Clock at the start of every test
+pub const START: i64 = 1_700_000_000;
+pub const REGISTRATION_START: i64 = START + 100;
+pub const REGISTRATION_END: i64 = START + 1_000;
+pub const SEASON_END: i64 = START + 2_000;
+pub const CHALLENGE_WINDOW: i64 = 300;
+pub const CLAIM_DEADLINE: i64 = START + 10_000;
+pub const ENTRY_FEE: u64 = LAMPORTS_PER_SOL;
+pub const DISPUTE_BOND: u64 = LAMPORTS_PER_SOL / 2;
+
+/// Lamports every test wallet starts with
+pub const WALLET_FUNDING: u64 = 100 * LAMPORTS_PER_SOL;
+
+/// A contest paying 50/30/20 with at least two participants, on the standard timeline
+pub fn season_params() -> InitializeSeasonParams {
+    InitializeSeasonParams {
+        name: "Test Season".to_string(),
+        entry_fee: ENTRY_FEE,
+        registration_start: REGISTRATION_START,
+        registration_end: REGISTRATION_END,
+        season_end: SEASON_END,
+        payout_bps: vec![5000, 3000, 2000],
+        min_participants: 2,
+        max_participants: 0,
+        guaranteed_pool: 0,
+        claim_deadline: CLAIM_DEADLINE,
+        unclaimed_policy: UnclaimedPrizePolicy::Treasury,
+        dust_policy: DustPolicy::ToTreasury,
+        challenge_window: CHALLENGE_WINDOW,
+        dispute_bond: DISPUTE_BOND,
+        mode: SeasonMode::Contest,
+        raffle_commitment: [0; 32],
+    }
+}
+
+fn program_path() -> PathBuf {
+    match std::env::var_os("GORBAGE_HANDS_SO") {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../target/deploy/gorbage_hands_v2.so"),
+    }
+}
+
+pub struct TestEnv {
+    pub svm: LiteSVM,
+    /// Pays every transaction fee
+    pub payer: Keypair,
+    /// Global admin and authority of the seasons created through the harness
+    pub admin: Keypair,
+    /// Sole member of the oracle registry, threshold 1
+    pub attestor: Keypair,
+    /// Receives the platform fee
+    pub treasury: Keypair,
+}
+
+impl TestEnv {
+    /// Program loaded, clock at `START`, config and a 1-of-1 oracle registry created
+    pub fn new() -> Self {
+        let mut env = Self::without_config();
+        let admin = env.admin.pubkey();
+        let attestor = env.attestor.pubkey();
+        env.execute(
+            &[instructions::initialize_config(&admin)],
+            &[&env.admin.insecure_clone()],
+        );
+        env.execute(
+            &[instructions::initialize_oracle_registry(&admin, vec![attestor], 1)],
+            &[&env.admin.insecure_clone()],
+        );
+        
+        env
+    }
+    
+    /// Program loaded and clock at `START`, nothing initialized
+    pub fn without_config() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(gorbage_hands_v2::ID, program_path())
+            .expect("program binary missing, run scripts/build-program.sh or set GORBAGE_HANDS_SO");
+        
+        let mut env = Self {
+            svm,
+            payer: Keypair::new(),
+            admin: Keypair::new(),
+            attestor: Keypair::new(),
+            treasury: Keypair::new(),
+        };
+        for wallet in [&env.payer, &env.admin, &env.attestor, &env.treasury] {
+            env.svm.airdrop(&wallet.pubkey(), WALLET_FUNDING).unwrap();
+        }
+        env.warp_to(START);
+        
+        env
+    }
+    
+    /// A new wallet funded with `WALLET_FUNDING`
+    pub fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.svm.airdrop(&wallet.pubkey(), WALLET_FUNDING).unwrap();
+        wallet
+    }
+    
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+    
+    /// Move the cluster clock to `unix_timestamp`
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+    
+    /// Move the cluster clock to `slot`
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.slot = slot;
+        self.svm.set_sysvar(&clock);
+    }
+    
+    /// Send `instructions` signed by `signers`, with the harness payer paying the fee.
+    /// Returns LiteSVM's own result, large error variant included.
+    #[allow(clippy::result_large_err)]
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        // A fresh blockhash lets identical transactions be sent twice
+        self.svm.expire_blockhash();
+        let mut all_signers: Vec<&Keypair> = vec![&self.payer];
+        all_signers.extend(
+            signers
+                .iter()
+                .filter(|signer| signer.pubkey() != self.payer.pubkey()),
+        );
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+        
+        self.svm.send_transaction(transaction)
+    }
+    
+    /// Send a transaction that must succeed
+    pub fn execute(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionMetadata {
+        match self.send(instructions, signers) {
+            Ok(meta) => meta,
+            Err(failed) => panic!(
+                "transaction failed: {:?}\n{}",
+                failed.err,
+                failed.meta.logs.join("\n")
+            ),
+        }
+    }
+    
+    /// Send a transaction that must fail with `expected` in its last instruction
+    pub fn expect_error(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        expected: GorbageError,
+    ) {
+        let index = (instructions.len() - 1) as u8;
+        match self.send(instructions, signers) {
+            Ok(meta) => panic!(
+                "expected {} but the transaction succeeded\n{}",
+                expected.name(),
+                meta.logs.join("\n")
+            ),
+            Err(failed) => assert_eq!(
+                failed.err,
+                TransactionError::InstructionError(
+                    index,
+                    InstructionError::Custom(u32::from(expected))
+                ),
+                "expected {}\n{}",
+                expected.name(),
+                failed.meta.logs.join("\n")
+            ),
+        }
+    }
+    
+    /// Send a transaction that must be rejected, for failures outside `GorbageError`
+    /// such as account constraints enforced by Anchor or the runtime
+    pub fn expect_failure(&mut self, instructions: &[Instruction], signers: &[&Keypair]) {
+        if let Ok(meta) = self.send(instructions, signers) {
+            panic!("expected the transaction to fail\n{}", meta.logs.join("\n"));
+        }
+    }
+    
+    /// Lamports held by `address`, 0 if the account does not exist
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or(0)
+    }
+    
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.svm
+            .get_account(address)
+            .is_some_and(|account| account.lamports > 0)
+    }
+    
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self
+            .svm
+            .get_account(address)
+            .unwrap_or_else(|| panic!("account {address} not found"));
+        decode(&account.data).unwrap()
+    }
+    
+    /// Rent-exempt minimum of an account with `space` bytes of data
+    pub fn rent(&self, space: usize) -> u64 {
+        self.svm.minimum_balance_for_rent_exemption(space)
+    }
+    
+    pub fn season(&self, season_number: u64) -> Season {
+        self.fetch(&pda::season(season_number).0)
+    }
+    
+    /// Phase the season registry lists for the season, `None` once it was dropped
+    pub fn listed_phase(&self, season_number: u64) -> Option<SeasonPhase> {
+        let season_registry: SeasonRegistry = self.fetch(&pda::season_registry().0);
+        season_registry
+            .seasons
+            .iter()
+            .find(|entry| entry.season_number == season_number)
+            .map(|entry| entry.phase)
+    }
+    
+    pub fn participant(&self, season_number: u64, owner: &Pubkey) -> Participant {
+        self.fetch(&pda::participant(&pda::season(season_number).0, owner).0)
+    }
+    
+    pub fn vault(&self, season_number: u64) -> Pubkey {
+        pda::vault(&pda::season(season_number).0).0
+    }
+    
+    /// Create a season with the admin as authority and its first roster page
+    /// open, returning its number
+    pub fn create_season(&mut self, params: InitializeSeasonParams) -> u64 {
+        let season_number = self.next_season_number();
+        let admin = self.admin.insecure_clone();
+        let ix =
+            instructions::initialize_season(&admin.pubkey(), season_number, params, None, None)
+                .unwrap();
+        let first_page = instructions::open_participant_page(&admin.pubkey(), season_number, 0);
+        self.execute(&[ix, first_page], &[&admin]);
+        
+        season_number
+    }
+    
+    pub fn next_season_number(&self) -> u64 {
+        let config: gorbage_hands_v2::state::ProgramConfig = self.fetch(&pda::config().0);
+        config.next_season_number
+    }
+    
+    pub fn register(&mut self, season_number: u64, owner: &Keypair) {
+        let season = self.season(season_number);
+        let ix = instructions::register_participant(&owner.pubkey(), &season, None).unwrap();
+        self.execute(&[ix], &[owner]);
+    }
+    
+    /// Roster page number `page` of season `season_number`
+    pub fn participant_page(&self, season_number: u64, page: u64) -> ParticipantPage {
+        let index = page * PARTICIPANT_PAGE_SIZE as u64;
+        self.fetch(&pda::participant_page(&pda::season(season_number).0, index).0)
+    }
+    
+    /// Register `count` new wallets, returned in registration order
+    pub fn register_wallets(&mut self, season_number: u64, count: usize) -> Vec<Keypair> {
+        (0..count)
+            .map(|_| {
+                let wallet = self.wallet();
+                self.register(season_number, &wallet);
+                wallet
+            })
+            .collect()
+    }
+    
+    /// Standard season with `count` registered wallets, clock inside registration
+    pub fn season_with_wallets(&mut self, count: usize) -> (u64, Vec<Keypair>) {
+        let season_number = self.create_season(season_params());
+        self.warp_to(REGISTRATION_START);
+        let wallets = self.register_wallets(season_number, count);
+        (season_number, wallets)
+    }
+    
+    /// Collect the platform fee to the harness treasury
+    pub fn collect_fee(&mut self, season_number: u64) {
+        let season = self.season(season_number);
+        let admin = self.admin.insecure_clone();
+        let ix = instructions::collect_fee(&admin.pubkey(), &self.treasury.pubkey(), &season, None)
+            .unwrap();
+        self.execute(&[ix], &[&admin]);
+    }
+    
+    /// Standard season with `count` wallets and the fee collected, still in play
+    pub fn active_season(&mut self, count: usize) -> (u64, Vec<Keypair>) {
+        let (season_number, wallets) = self.season_with_wallets(count);
+        self.warp_to(REGISTRATION_END + 1);
+        self.collect_fee(season_number);
+        (season_number, wallets)
+    }
+    
+    /// Standard season with `count` wallets, fee collected and the season over
+    pub fn ended_season(&mut self, count: usize) -> (u64, Vec<Keypair>) {
+        let (season_number, wallets) = self.active_season(count);
+        self.warp_to(SEASON_END + 1);
+        (season_number, wallets)
+    }
+    
+    pub fn settle_ix(&self, season_number: u64, winners: &[&Keypair]) -> Instruction {
+        let winners: Vec<Pubkey> = winners.iter().map(|winner| winner.pubkey()).collect();
+        let season = self.season(season_number);
+        instructions::settle_season(&self.admin.pubkey(), &season, &winners, false)
+    }
+    
+    /// Propose `winners` in placement order
+    pub fn settle(&mut self, season_number: u64, winners: &[&Keypair]) {
+        let ix = self.settle_ix(season_number, winners);
+        let admin = self.admin.insecure_clone();
+        self.execute(&[ix], &[&admin]);
+    }
+    
+    pub fn attest(&mut self, season_number: u64) {
+        let ix = instructions::attest_results(&self.attestor.pubkey(), &self.season(season_number));
+        let attestor = self.attestor.insecure_clone();
+        self.execute(&[ix], &[&attestor]);
+    }
+    
+    /// Move past the challenge deadline and finalize
+    pub fn finalize(&mut self, season_number: u64) -> TransactionMetadata {
+        let season = self.season(season_number);
+        self.warp_to(season.challenge_deadline + 1);
+        let finalize = self.finalize_ix(season_number);
+        self.execute(&[finalize], &[])
+    }
+    
+    /// Finalize with the token program that owns the season's mint
+    pub fn finalize_ix(&self, season_number: u64) -> Instruction {
+        let season = self.season(season_number);
+        let token_program = season
+            .mint
+            .map(|mint| self.svm.get_account(&mint).unwrap().owner);
+        instructions::finalize_settlement(&season, token_program).unwrap()
+    }
+    
+    /// Attest the proposed results and finalize them
+    pub fn confirm_results(&mut self, season_number: u64) -> TransactionMetadata {
+        self.attest(season_number);
+        self.finalize(season_number)
+    }
+    
+    pub fn claim_ix(&self, season_number: u64, winner: &Pubkey) -> Instruction {
+        instructions::claim_prize(winner, &self.season(season_number), None).unwrap()
+    }
+    
+    pub fn claim(&mut self, season_number: u64, winner: &Keypair) {
+        let ix = self.claim_ix(season_number, &winner.pubkey());
+        self.execute(&[ix], &[winner]);
+    }
+    
+    pub fn close_ix(&self, season_number: u64) -> Instruction {
+        instructions::close_season(&self.admin.pubkey(), &self.season(season_number), None).unwrap()
+    }
+    
+    /// Wallets on the season roster, in participant index order
+    pub fn roster(&self, season_number: u64) -> Vec<Pubkey> {
+        let season_key = pda::season(season_number).0;
+        let season = self.season(season_number);
+        let mut roster = Vec::new();
+        let mut index = 0;
+        while index < season.next_participant_index {
+            let page: ParticipantPage = self.fetch(&pda::participant_page(&season_key, index).0);
+            index += page.entries.len() as u64;
+            roster.extend(page.entries);
+        }
+        
+        roster
+    }
+}
+
+impl Default for TestEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Events of type `T` a transaction emitted, decoded from its `Program data:` logs
+pub fn events<T: Event>(meta: &TransactionMetadata) -> Vec<T> {
+    meta.logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| BASE64_STANDARD.decode(data).ok())
+        .filter_map(|data| {
+            let mut body = data.strip_prefix(T::DISCRIMINATOR)?;
+            T::deserialize(&mut body).ok()
+        })
+        .collect()
+}
+
+/// The one event of type `T` a transaction emitted
+pub fn event<T: Event>(meta: &TransactionMetadata) -> T {
+    let mut events = events(meta);
+    assert_eq!(events.len(), 1, "expected exactly one event\n{}", meta.logs.join("\n"));
+    events.remove(0)
+}
+
+/// Account space of a program account type, including the discriminator
+pub fn space<T: anchor_lang::Space>() -> usize {
+    8 + T::INIT_SPACE
+}
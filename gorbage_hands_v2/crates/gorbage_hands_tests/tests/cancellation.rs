@@ -0,0 +1,422 @@
+//! Cancellation and refunds, sponsor contributions and guaranteed prize pools
+
+use std::slice;
+
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::events::{RefundClaimed, SeasonCancelled};
+use gorbage_hands_v2::state::{SeasonPhase, SeasonRegistry, Sponsorship};
+use litesvm::types::TransactionMetadata;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: u64 = LAMPORTS_PER_SOL;
+
+fn claim_refund(env: &mut TestEnv, season_number: u64, owner: &Keypair) -> TransactionMetadata {
+    let refund = instructions::claim_refund(&owner.pubkey(), &env.season(season_number), None)
+        .unwrap();
+    env.execute(&[refund], &[owner])
+}
+
+fn contribute(env: &mut TestEnv, season_number: u64, sponsor: &Keypair, amount: u64) {
+    let contribute = instructions::contribute_to_pool(
+        &sponsor.pubkey(),
+        &env.season(season_number),
+        amount,
+        None,
+    )
+    .unwrap();
+    env.execute(&[contribute], &[sponsor]);
+}
+
+/// Cancel an Active or Settling season, with the harness treasury returning the fee
+fn cancel_with_treasury(env: &mut TestEnv, season_number: u64) -> TransactionMetadata {
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.insecure_clone();
+    let cancel = instructions::cancel_season(
+        &admin.pubkey(),
+        &env.season(season_number),
+        Some(treasury.pubkey()),
+        None,
+    )
+    .unwrap();
+    env.execute(&[cancel], &[&admin, &treasury])
+}
+
+#[test]
+fn cancel_during_registration_refunds_everyone() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.season_with_wallets(3);
+    let vault = env.vault(season_number);
+    
+    let refund = instructions::claim_refund(&players[0].pubkey(), &env.season(season_number), None)
+        .unwrap();
+    env.expect_error(
+        slice::from_ref(&refund),
+        &[&players[0]],
+        GorbageError::SeasonNotCancelled,
+    );
+    
+    let outsider = env.wallet();
+    let cancel =
+        instructions::cancel_season(&outsider.pubkey(), &env.season(season_number), None, None)
+            .unwrap();
+    env.expect_error(&[cancel], &[&outsider], GorbageError::Unauthorized);
+    
+    let cancel =
+        instructions::cancel_season(&admin.pubkey(), &env.season(season_number), None, None)
+            .unwrap();
+    env.execute(slice::from_ref(&cancel), &[&admin]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Cancelled);
+    assert_eq!(env.balance(&vault), 3 * SOL);
+    let season_registry: SeasonRegistry = env.fetch(&pda::season_registry().0);
+    assert!(season_registry.seasons.is_empty());
+    
+    for (index, player) in players.iter().enumerate() {
+        let before = env.balance(&player.pubkey());
+        claim_refund(&mut env, season_number, player);
+        assert_eq!(env.balance(&player.pubkey()), before + ENTRY_FEE);
+        assert_eq!(env.balance(&vault), (2 - index as u64) * SOL);
+        assert!(env.participant(season_number, &player.pubkey()).refunded);
+    }
+    assert_eq!(env.season(season_number).prize_pool, 0);
+    env.expect_error(&[refund], &[&players[0]], GorbageError::AlreadyRefunded);
+    
+    env.expect_error(&[cancel], &[&admin], GorbageError::SeasonCancelled);
+    let late = env.wallet();
+    let register =
+        instructions::register_participant(&late.pubkey(), &env.season(season_number), None)
+            .unwrap();
+    env.expect_error(&[register], &[&late], GorbageError::SeasonCancelled);
+    env.warp_to(REGISTRATION_END + 1);
+    let collect = instructions::collect_fee(
+        &admin.pubkey(),
+        &env.treasury.pubkey(),
+        &env.season(season_number),
+        None,
+    )
+    .unwrap();
+    env.expect_error(&[collect], &[&admin], GorbageError::SeasonCancelled);
+}
+
+#[test]
+fn season_authority_and_config_admin_can_cancel() {
+    let mut env = TestEnv::new();
+    let authority = env.admin.insecure_clone();
+    let first = env.create_season(season_params());
+    let second = env.create_season(season_params());
+    
+    // After an admin transfer the old admin is still the authority of its seasons
+    let new_admin = env.wallet();
+    env.execute(
+        &[instructions::transfer_admin(
+            &authority.pubkey(),
+            &new_admin.pubkey(),
+        )],
+        &[&authority],
+    );
+    
+    let cancel = instructions::cancel_season(&new_admin.pubkey(), &env.season(first), None, None)
+        .unwrap();
+    env.execute(&[cancel], &[&new_admin]);
+    let cancel = instructions::cancel_season(&authority.pubkey(), &env.season(second), None, None)
+        .unwrap();
+    env.execute(&[cancel], &[&authority]);
+    assert_eq!(env.season(first).phase, SeasonPhase::Cancelled);
+    assert_eq!(env.season(second).phase, SeasonPhase::Cancelled);
+}
+
+#[test]
+fn cancel_after_fee_collection_returns_the_fee() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.pubkey();
+    let (season_number, players) = env.ended_season(4);
+    let vault = env.vault(season_number);
+    env.settle(season_number, &[&players[0]]);
+    assert_eq!(env.balance(&treasury), WALLET_FUNDING + 4 * SOL / 5);
+    assert_eq!(env.balance(&vault), 16 * SOL / 5);
+    
+    let cancel =
+        instructions::cancel_season(&admin.pubkey(), &env.season(season_number), None, None)
+            .unwrap();
+    env.expect_error(&[cancel], &[&admin], GorbageError::TreasuryRequired);
+    let impostor = env.wallet();
+    let cancel = instructions::cancel_season(
+        &admin.pubkey(),
+        &env.season(season_number),
+        Some(impostor.pubkey()),
+        None,
+    )
+    .unwrap();
+    env.expect_error(
+        &[cancel],
+        &[&admin, &impostor],
+        GorbageError::TreasuryMismatch,
+    );
+    
+    let cancelled = cancel_with_treasury(&mut env, season_number);
+    assert_eq!(env.balance(&treasury), WALLET_FUNDING);
+    assert_eq!(env.balance(&vault), 4 * SOL);
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Cancelled);
+    assert_eq!(season.prize_pool, 4 * SOL);
+    assert_eq!(season.fee_amount, 0);
+    let cancelled: SeasonCancelled = event(&cancelled);
+    assert_eq!(cancelled.cancelled_by, admin.pubkey());
+    assert_eq!(cancelled.participant_count, 4);
+    assert_eq!(cancelled.fee_returned, 4 * SOL / 5);
+    assert_eq!(cancelled.refundable_entry_fees, 4 * SOL);
+    
+    // The proposed winner gets a refund like everyone else
+    for (index, player) in players.iter().enumerate() {
+        let before = env.balance(&player.pubkey());
+        let refunded = claim_refund(&mut env, season_number, player);
+        assert_eq!(env.balance(&player.pubkey()), before + ENTRY_FEE);
+        let refunded: RefundClaimed = event(&refunded);
+        assert_eq!(refunded.owner, player.pubkey());
+        assert_eq!(refunded.amount, ENTRY_FEE);
+        assert_eq!(refunded.remaining_entry_fees, (3 - index as u64) * SOL);
+    }
+    assert_eq!(env.balance(&vault), 0);
+    let claim = env.claim_ix(season_number, &players[0].pubkey());
+    env.expect_error(&[claim], &[&players[0]], GorbageError::SeasonCancelled);
+}
+
+#[test]
+fn season_below_minimum_refunds_without_a_cancel() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.season_with_wallets(1);
+    env.warp_to(REGISTRATION_END + 1);
+    
+    let before = env.balance(&players[0].pubkey());
+    claim_refund(&mut env, season_number, &players[0]);
+    assert_eq!(env.balance(&players[0].pubkey()), before + ENTRY_FEE);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Cancelled);
+    
+    env.warp_to(SEASON_END + 1);
+    let settle = env.settle_ix(season_number, &[&players[0]]);
+    env.expect_error(&[settle], &[&admin], GorbageError::MinParticipantsNotMet);
+}
+
+#[test]
+fn sponsor_funds_skip_the_platform_fee() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.pubkey();
+    let (season_number, players) = env.season_with_wallets(2);
+    let vault = env.vault(season_number);
+    let sponsor = env.wallet();
+    
+    let contribute_nothing =
+        instructions::contribute_to_pool(&sponsor.pubkey(), &env.season(season_number), 0, None)
+            .unwrap();
+    env.expect_error(
+        &[contribute_nothing],
+        &[&sponsor],
+        GorbageError::InvalidContribution,
+    );
+    
+    contribute(&mut env, season_number, &sponsor, SOL);
+    let sponsorship_rent = env.rent(space::<Sponsorship>());
+    assert_eq!(
+        env.balance(&sponsor.pubkey()),
+        WALLET_FUNDING - SOL - sponsorship_rent
+    );
+    assert_eq!(env.balance(&vault), 3 * SOL);
+    let season = env.season(season_number);
+    assert_eq!(season.sponsor_pool, SOL);
+    assert_eq!(season.prize_pool, 2 * SOL);
+    
+    // 20% of the 2 SOL of entries only
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    assert_eq!(env.balance(&treasury), WALLET_FUNDING + 2 * SOL / 5);
+    assert_eq!(env.season(season_number).prize_pool, 13 * SOL / 5);
+    
+    // Contributions after fee collection go straight into the pool
+    contribute(&mut env, season_number, &sponsor, SOL / 2);
+    let sponsorship: Sponsorship =
+        env.fetch(&pda::sponsorship(&pda::season(season_number).0, &sponsor.pubkey()).0);
+    assert_eq!(sponsorship.amount, 3 * SOL / 2);
+    assert_eq!(env.season(season_number).prize_pool, 31 * SOL / 10);
+    assert_eq!(env.balance(&vault), 31 * SOL / 10);
+    
+    env.warp_to(SEASON_END + 1);
+    env.settle(season_number, &[&players[1]]);
+    let late =
+        instructions::contribute_to_pool(&sponsor.pubkey(), &env.season(season_number), SOL, None)
+            .unwrap();
+    env.expect_error(&[late], &[&sponsor], GorbageError::ContributionsClosed);
+    
+    env.confirm_results(season_number);
+    let before = env.balance(&players[1].pubkey());
+    env.claim(season_number, &players[1]);
+    assert_eq!(env.balance(&players[1].pubkey()), before + 31 * SOL / 10);
+    assert_eq!(env.balance(&vault), 0);
+    
+    let close = env.close_ix(season_number);
+    env.execute(&[close], &[&admin]);
+    let late =
+        instructions::contribute_to_pool(&sponsor.pubkey(), &env.season(season_number), SOL, None)
+            .unwrap();
+    env.expect_error(&[late], &[&sponsor], GorbageError::SeasonClosed);
+}
+
+#[test]
+fn sponsors_are_refunded_when_a_season_is_cancelled() {
+    let mut env = TestEnv::new();
+    let (season_number, players) = env.season_with_wallets(2);
+    let vault = env.vault(season_number);
+    let sponsor = env.wallet();
+    contribute(&mut env, season_number, &sponsor, SOL);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    
+    let refund =
+        instructions::claim_sponsor_refund(&sponsor.pubkey(), &env.season(season_number), None)
+            .unwrap();
+    env.expect_error(
+        slice::from_ref(&refund),
+        &[&sponsor],
+        GorbageError::SeasonNotCancelled,
+    );
+    
+    cancel_with_treasury(&mut env, season_number);
+    assert_eq!(env.balance(&vault), 3 * SOL);
+    let season = env.season(season_number);
+    assert_eq!(season.prize_pool, 2 * SOL);
+    assert_eq!(season.sponsor_pool, SOL);
+    
+    let before = env.balance(&sponsor.pubkey());
+    env.execute(slice::from_ref(&refund), &[&sponsor]);
+    assert_eq!(env.balance(&sponsor.pubkey()), before + SOL);
+    env.expect_error(&[refund], &[&sponsor], GorbageError::AlreadyRefunded);
+    
+    for player in &players {
+        claim_refund(&mut env, season_number, player);
+    }
+    assert_eq!(env.balance(&vault), 0);
+    
+    let late =
+        instructions::contribute_to_pool(&sponsor.pubkey(), &env.season(season_number), SOL, None)
+            .unwrap();
+    env.expect_error(&[late], &[&sponsor], GorbageError::SeasonCancelled);
+}
+
+#[test]
+fn guarantee_tops_up_the_pool_and_returns_the_rest() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.pubkey();
+    let treasury = env.treasury.pubkey();
+    let season_number = env.create_season(InitializeSeasonParams {
+        guaranteed_pool: 5 * SOL,
+        ..season_params()
+    });
+    let vault = env.vault(season_number);
+    assert_eq!(env.balance(&vault), 5 * SOL);
+    assert_eq!(env.season(season_number).guarantee_deposit, 5 * SOL);
+    
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 2);
+    assert_eq!(env.balance(&vault), 7 * SOL);
+    
+    // 1.6 SOL of entries after the fee, topped up by 3.4 SOL; 1.6 SOL goes back
+    let admin_before = env.balance(&admin);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    assert_eq!(env.balance(&treasury), WALLET_FUNDING + 2 * SOL / 5);
+    assert_eq!(env.balance(&admin), admin_before + 8 * SOL / 5);
+    assert_eq!(env.balance(&vault), 5 * SOL);
+    let season = env.season(season_number);
+    assert_eq!(season.prize_pool, 5 * SOL);
+    assert_eq!(season.guarantee_deposit, 17 * SOL / 5);
+    
+    // Two winners split the guaranteed 5 SOL 5/8 and 3/8
+    env.warp_to(SEASON_END + 1);
+    env.settle(season_number, &[&players[0], &players[1]]);
+    env.confirm_results(season_number);
+    for (player, prize) in players.iter().zip([25 * SOL / 8, 15 * SOL / 8]) {
+        let before = env.balance(&player.pubkey());
+        env.claim(season_number, player);
+        assert_eq!(env.balance(&player.pubkey()), before + prize);
+    }
+    assert_eq!(env.balance(&vault), 0);
+}
+
+#[test]
+fn guarantee_covered_by_entries_is_returned_in_full() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.pubkey();
+    let season_number = env.create_season(InitializeSeasonParams {
+        guaranteed_pool: SOL,
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_START);
+    env.register_wallets(season_number, 2);
+    
+    let admin_before = env.balance(&admin);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    assert_eq!(env.balance(&admin), admin_before + SOL);
+    assert_eq!(env.balance(&env.vault(season_number)), 8 * SOL / 5);
+    let season = env.season(season_number);
+    assert_eq!(season.prize_pool, 8 * SOL / 5);
+    assert_eq!(season.guarantee_deposit, 0);
+}
+
+#[test]
+fn cancel_returns_the_guarantee_to_the_authority() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.pubkey();
+    let season_number = env.create_season(InitializeSeasonParams {
+        guaranteed_pool: 5 * SOL,
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 2);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    
+    let admin_before = env.balance(&admin);
+    cancel_with_treasury(&mut env, season_number);
+    assert_eq!(env.balance(&admin), admin_before + 17 * SOL / 5);
+    assert_eq!(env.balance(&env.vault(season_number)), 2 * SOL);
+    
+    for player in &players {
+        claim_refund(&mut env, season_number, player);
+    }
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
+
+#[test]
+fn cancel_releases_the_guarantee_of_a_season_below_minimum() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let season_number = env.create_season(InitializeSeasonParams {
+        guaranteed_pool: 5 * SOL,
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 1);
+    env.warp_to(REGISTRATION_END + 1);
+    
+    // The refund cancels the season on the way
+    claim_refund(&mut env, season_number, &players[0]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Cancelled);
+    assert_eq!(env.balance(&env.vault(season_number)), 5 * SOL);
+    
+    let admin_before = env.balance(&admin.pubkey());
+    let cancel =
+        instructions::cancel_season(&admin.pubkey(), &env.season(season_number), None, None)
+            .unwrap();
+    env.execute(slice::from_ref(&cancel), &[&admin]);
+    assert_eq!(env.balance(&admin.pubkey()), admin_before + 5 * SOL);
+    assert_eq!(env.season(season_number).guarantee_deposit, 0);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+    
+    env.expect_error(&[cancel], &[&admin], GorbageError::SeasonCancelled);
+}
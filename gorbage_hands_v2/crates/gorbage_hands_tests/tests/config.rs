@@ -0,0 +1,247 @@
+//! Program config, admin transfer, game server and oracle registry
+
+use gorbage_hands_client::{instructions, pda};
+use gorbage_hands_tests::{season_params, TestEnv};
+use gorbage_hands_v2::constants::MAX_ATTESTORS;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::state::{OracleRegistry, ProgramConfig, SeasonRegistry};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[test]
+fn initialize_config_makes_the_caller_admin() {
+    let mut env = TestEnv::without_config();
+    let admin = env.admin.insecure_clone();
+    env.execute(
+        &[instructions::initialize_config(&admin.pubkey())],
+        &[&admin],
+    );
+    
+    let config: ProgramConfig = env.fetch(&pda::config().0);
+    assert_eq!(config.admin, admin.pubkey());
+    assert_eq!(config.game_server, Pubkey::default());
+    assert_eq!(config.next_season_number, 1);
+    let season_registry: SeasonRegistry = env.fetch(&pda::season_registry().0);
+    assert!(season_registry.seasons.is_empty());
+    
+    // The config PDA can only be created once
+    let other = env.wallet();
+    env.expect_failure(
+        &[instructions::initialize_config(&other.pubkey())],
+        &[&other],
+    );
+}
+
+#[test]
+fn transfer_admin_hands_over_admin_rights() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let new_admin = env.wallet();
+    
+    env.expect_error(
+        &[instructions::transfer_admin(
+            &new_admin.pubkey(),
+            &new_admin.pubkey(),
+        )],
+        &[&new_admin],
+        GorbageError::Unauthorized,
+    );
+    
+    env.execute(
+        &[instructions::transfer_admin(
+            &admin.pubkey(),
+            &new_admin.pubkey(),
+        )],
+        &[&admin],
+    );
+    let config: ProgramConfig = env.fetch(&pda::config().0);
+    assert_eq!(config.admin, new_admin.pubkey());
+    
+    // The old admin can no longer create seasons
+    let create =
+        instructions::initialize_season(&admin.pubkey(), 1, season_params(), None, None).unwrap();
+    env.expect_error(&[create], &[&admin], GorbageError::Unauthorized);
+    env.expect_error(
+        &[instructions::transfer_admin(
+            &admin.pubkey(),
+            &admin.pubkey(),
+        )],
+        &[&admin],
+        GorbageError::Unauthorized,
+    );
+}
+
+#[test]
+fn set_game_server_is_admin_only() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let game_server = Keypair::new();
+    let outsider = env.wallet();
+    
+    env.expect_error(
+        &[instructions::set_game_server(&outsider.pubkey(), &outsider.pubkey())],
+        &[&outsider],
+        GorbageError::Unauthorized,
+    );
+    
+    env.execute(
+        &[instructions::set_game_server(&admin.pubkey(), &game_server.pubkey())],
+        &[&admin],
+    );
+    let config: ProgramConfig = env.fetch(&pda::config().0);
+    assert_eq!(config.game_server, game_server.pubkey());
+    
+    env.execute(
+        &[instructions::set_game_server(&admin.pubkey(), &Pubkey::default())],
+        &[&admin],
+    );
+    let config: ProgramConfig = env.fetch(&pda::config().0);
+    assert_eq!(config.game_server, Pubkey::default());
+}
+
+#[test]
+fn initialize_oracle_registry_validates_the_attestor_set() {
+    let mut env = TestEnv::without_config();
+    let admin = env.admin.insecure_clone();
+    env.execute(
+        &[instructions::initialize_config(&admin.pubkey())],
+        &[&admin],
+    );
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+    
+    let invalid_sets = [
+        (vec![a, b], 0),
+        (vec![a, b], 3),
+        (vec![a, a], 1),
+        (Vec::new(), 0),
+        (
+            (0..=MAX_ATTESTORS).map(|_| Pubkey::new_unique()).collect(),
+            1,
+        ),
+    ];
+    for (attestors, threshold) in invalid_sets {
+        env.expect_error(
+            &[instructions::initialize_oracle_registry(
+                &admin.pubkey(),
+                attestors,
+                threshold,
+            )],
+            &[&admin],
+            GorbageError::InvalidAttestorSet,
+        );
+    }
+    
+    let outsider = env.wallet();
+    env.expect_error(
+        &[instructions::initialize_oracle_registry(
+            &outsider.pubkey(),
+            vec![a],
+            1,
+        )],
+        &[&outsider],
+        GorbageError::Unauthorized,
+    );
+    
+    env.execute(
+        &[instructions::initialize_oracle_registry(
+            &admin.pubkey(),
+            vec![a, b],
+            2,
+        )],
+        &[&admin],
+    );
+    let oracle_registry: OracleRegistry = env.fetch(&pda::oracle_registry().0);
+    assert_eq!(oracle_registry.attestors, vec![a, b]);
+    assert_eq!(oracle_registry.threshold, 2);
+}
+
+#[test]
+fn oracle_registry_updates() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let attestor = env.attestor.pubkey();
+    let second = Pubkey::new_unique();
+    let third = Pubkey::new_unique();
+    
+    env.execute(&[instructions::add_attestor(&admin.pubkey(), &second)], &[&admin]);
+    env.execute(
+        &[instructions::set_attestation_threshold(&admin.pubkey(), 2)],
+        &[&admin],
+    );
+    env.expect_error(
+        &[instructions::add_attestor(&admin.pubkey(), &second)],
+        &[&admin],
+        GorbageError::InvalidAttestorSet,
+    );
+    
+    // Removing would leave fewer attestors than the threshold
+    env.expect_error(
+        &[instructions::remove_attestor(&admin.pubkey(), &second)],
+        &[&admin],
+        GorbageError::InvalidAttestorSet,
+    );
+    env.expect_error(
+        &[instructions::remove_attestor(&admin.pubkey(), &third)],
+        &[&admin],
+        GorbageError::NotAnAttestor,
+    );
+    
+    env.expect_error(
+        &[instructions::rotate_attestor(&admin.pubkey(), &third, &second)],
+        &[&admin],
+        GorbageError::NotAnAttestor,
+    );
+    env.expect_error(
+        &[instructions::rotate_attestor(&admin.pubkey(), &second, &attestor)],
+        &[&admin],
+        GorbageError::InvalidAttestorSet,
+    );
+    env.execute(
+        &[instructions::rotate_attestor(&admin.pubkey(), &second, &third)],
+        &[&admin],
+    );
+    
+    for threshold in [0, 3] {
+        env.expect_error(
+            &[instructions::set_attestation_threshold(&admin.pubkey(), threshold)],
+            &[&admin],
+            GorbageError::InvalidAttestorSet,
+        );
+    }
+    env.execute(
+        &[instructions::set_attestation_threshold(&admin.pubkey(), 1)],
+        &[&admin],
+    );
+    env.execute(
+        &[instructions::remove_attestor(&admin.pubkey(), &attestor)],
+        &[&admin],
+    );
+    
+    let oracle_registry: OracleRegistry = env.fetch(&pda::oracle_registry().0);
+    assert_eq!(oracle_registry.attestors, vec![third]);
+    assert_eq!(oracle_registry.threshold, 1);
+    
+    // Fill the registry up to the maximum
+    for _ in 1..MAX_ATTESTORS {
+        env.execute(
+            &[instructions::add_attestor(&admin.pubkey(), &Pubkey::new_unique())],
+            &[&admin],
+        );
+    }
+    env.expect_error(
+        &[instructions::add_attestor(&admin.pubkey(), &Pubkey::new_unique())],
+        &[&admin],
+        GorbageError::InvalidAttestorSet,
+    );
+    
+    let outsider = env.wallet();
+    for update in [
+        instructions::add_attestor(&outsider.pubkey(), &Pubkey::new_unique()),
+        instructions::remove_attestor(&outsider.pubkey(), &third),
+        instructions::rotate_attestor(&outsider.pubkey(), &third, &Pubkey::new_unique()),
+        instructions::set_attestation_threshold(&outsider.pubkey(), 1),
+    ] {
+        env.expect_error(&[update], &[&outsider], GorbageError::Unauthorized);
+    }
+}
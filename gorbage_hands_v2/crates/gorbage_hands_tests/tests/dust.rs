@@ -0,0 +1,206 @@
+//! Rounding dust under each `DustPolicy`: three entries of 1 SOL + 1 lamport
+//! leave a 2_400_000_003 lamport pool, whose 50/30/20 prizes come to 2 lamports
+//! short of it, plus the bound on dust a Merkle root may leave
+
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::events::SettlementFinalized;
+use gorbage_hands_v2::state::{DustPolicy, SeasonPhase};
+use solana_sdk::signature::{Keypair, Signer};
+
+const PRIZE_POOL: u64 = 2_400_000_003;
+const PRIZES: [u64; 3] = [1_200_000_001, 720_000_000, 480_000_000];
+const DUST: u64 = 2;
+
+fn create_dusty_season(env: &mut TestEnv, dust_policy: DustPolicy) -> u64 {
+    env.create_season(InitializeSeasonParams {
+        entry_fee: ENTRY_FEE + 1,
+        dust_policy,
+        ..season_params()
+    })
+}
+
+/// Register three wallets, collect the fee and propose them as winners in order
+fn settle_dusty_season(env: &mut TestEnv, season_number: u64) -> Vec<Keypair> {
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 3);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    env.warp_to(SEASON_END + 1);
+    env.settle(season_number, &[&players[0], &players[1], &players[2]]);
+    
+    let season = env.season(season_number);
+    assert_eq!(season.prize_pool, PRIZE_POOL);
+    players
+}
+
+fn claim_all(env: &mut TestEnv, season_number: u64, players: &[Keypair]) -> Vec<u64> {
+    players
+        .iter()
+        .map(|player| {
+            let before = env.balance(&player.pubkey());
+            env.claim(season_number, player);
+            env.balance(&player.pubkey()) - before
+        })
+        .collect()
+}
+
+#[test]
+fn dust_goes_to_first_place() {
+    let mut env = TestEnv::new();
+    let season_number = create_dusty_season(&mut env, DustPolicy::ToFirstPlace);
+    let players = settle_dusty_season(&mut env, season_number);
+    
+    let season = env.season(season_number);
+    assert_eq!(season.dust_amount, 0);
+    assert_eq!(season.total_awarded, PRIZE_POOL);
+    assert_eq!(
+        env.participant(season_number, &players[0].pubkey()).prize_amount,
+        PRIZES[0] + DUST
+    );
+    
+    let finalized = env.confirm_results(season_number);
+    let event: SettlementFinalized = event(&finalized);
+    assert_eq!((event.dust, event.dust_recipient), (0, None));
+    
+    let claimed = claim_all(&mut env, season_number, &players);
+    assert_eq!(claimed, [PRIZES[0] + DUST, PRIZES[1], PRIZES[2]]);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
+
+#[test]
+fn dust_goes_to_the_treasury_on_finalize() {
+    let mut env = TestEnv::new();
+    let treasury = env.treasury.pubkey();
+    let season_number = create_dusty_season(&mut env, DustPolicy::ToTreasury);
+    let players = settle_dusty_season(&mut env, season_number);
+    
+    let season = env.season(season_number);
+    assert_eq!(season.dust_amount, DUST);
+    assert_eq!(season.total_awarded + season.dust_amount, PRIZE_POOL);
+    
+    let treasury_before = env.balance(&treasury);
+    let finalized = env.confirm_results(season_number);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Settled);
+    assert_eq!(env.balance(&treasury), treasury_before + DUST);
+    let event: SettlementFinalized = event(&finalized);
+    assert_eq!((event.dust, event.dust_recipient), (DUST, Some(treasury)));
+    assert_eq!(env.balance(&env.vault(season_number)), PRIZE_POOL - DUST);
+    
+    let claimed = claim_all(&mut env, season_number, &players);
+    assert_eq!(claimed, PRIZES);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
+
+#[test]
+fn dust_rolls_into_the_next_season() {
+    let mut env = TestEnv::new();
+    let next = env.next_season_number() + 1;
+    let season_number =
+        create_dusty_season(&mut env, DustPolicy::ToNextSeason { season_number: next });
+    assert_eq!(env.create_season(season_params()), next);
+    env.warp_to(REGISTRATION_START);
+    env.register_wallets(next, 2);
+    
+    let players = settle_dusty_season(&mut env, season_number);
+    let finalized = env.confirm_results(season_number);
+    let event: SettlementFinalized = event(&finalized);
+    assert_eq!(event.dust_recipient, Some(pda::season(next).0));
+    
+    let next_season = env.season(next);
+    assert_eq!(next_season.rollover_pool, DUST);
+    assert_eq!(env.balance(&env.vault(next)), 2 * ENTRY_FEE + DUST);
+    assert_eq!(env.balance(&env.vault(season_number)), PRIZE_POOL - DUST);
+    
+    let claimed = claim_all(&mut env, season_number, &players);
+    assert_eq!(claimed, PRIZES);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
+
+#[test]
+fn dust_the_next_season_cannot_take_goes_to_the_treasury() {
+    let mut env = TestEnv::new();
+    let treasury = env.treasury.pubkey();
+    let next = env.next_season_number() + 1;
+    let season_number =
+        create_dusty_season(&mut env, DustPolicy::ToNextSeason { season_number: next });
+    
+    // An empty native vault can't hold 2 lamports and stay rent exempt
+    env.create_season(season_params());
+    settle_dusty_season(&mut env, season_number);
+    
+    let treasury_before = env.balance(&treasury);
+    let finalized = env.confirm_results(season_number);
+    assert_eq!(env.balance(&treasury), treasury_before + DUST);
+    assert_eq!(env.season(next).rollover_pool, 0);
+    let event: SettlementFinalized = event(&finalized);
+    assert_eq!(event.dust_recipient, Some(treasury));
+    assert_eq!(env.balance(&env.vault(next)), 0);
+}
+
+#[test]
+fn merkle_roots_under_first_place_dust_pay_the_whole_pool() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let season_number = env.create_season(InitializeSeasonParams {
+        dust_policy: DustPolicy::ToFirstPlace,
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_START);
+    env.register_wallets(season_number, 2);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    env.warp_to(SEASON_END + 1);
+    
+    let prize_pool = env.season(season_number).prize_pool;
+    let settle = |env: &TestEnv, total: u64| {
+        instructions::settle_season_with_root(
+            &admin.pubkey(),
+            &env.season(season_number),
+            [7; 32],
+            total,
+            2,
+        )
+    };
+    env.expect_error(
+        &[settle(&env, prize_pool - 1)],
+        &[&admin],
+        GorbageError::PayoutBelowPool,
+    );
+    env.execute(&[settle(&env, prize_pool)], &[&admin]);
+    assert_eq!(env.season(season_number).dust_amount, 0);
+}
+
+#[test]
+fn merkle_root_dust_stays_below_one_unit_per_leaf() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let season_number = env.create_season(InitializeSeasonParams {
+        dust_policy: DustPolicy::ToTreasury,
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_START);
+    env.register_wallets(season_number, 2);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    env.warp_to(SEASON_END + 1);
+    
+    let prize_pool = env.season(season_number).prize_pool;
+    let settle = |env: &TestEnv, total: u64| {
+        instructions::settle_season_with_root(
+            &admin.pubkey(),
+            &env.season(season_number),
+            [7; 32],
+            total,
+            2,
+        )
+    };
+    env.expect_error(
+        &[settle(&env, prize_pool - 2)],
+        &[&admin],
+        GorbageError::DustAboveLeafCount,
+    );
+    env.execute(&[settle(&env, prize_pool - 1)], &[&admin]);
+    assert_eq!(env.season(season_number).dust_amount, 1);
+}
@@ -0,0 +1,125 @@
+//! Events emitted over a season's life, decoded from the transaction logs
+
+use gorbage_hands_client::{instructions, pda};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::events::{
+    FeeCollected, ParticipantRegistered, PrizeClaimed, PrizeSet, SeasonClosed, SeasonCreated,
+    WinnersSet,
+};
+use gorbage_hands_v2::state::{SeasonMode, UnclaimedPrizePolicy};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+
+const SOL: u64 = LAMPORTS_PER_SOL;
+
+#[test]
+fn season_creation_registration_and_fee_collection_emit_events() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let season_number = env.next_season_number();
+    let season_key = pda::season(season_number).0;
+    
+    let create = instructions::initialize_season(
+        &admin.pubkey(),
+        season_number,
+        season_params(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created: SeasonCreated = event(&env.execute(&[create], &[&admin]));
+    assert_eq!(created.season, season_key);
+    assert_eq!(created.season_number, season_number);
+    assert_eq!(created.authority, admin.pubkey());
+    assert_eq!(created.mode, SeasonMode::Contest);
+    assert_eq!(created.mint, None);
+    assert_eq!(created.entry_fee, ENTRY_FEE);
+    assert_eq!(created.payout_bps, vec![5000, 3000, 2000]);
+    assert_eq!(created.claim_deadline, CLAIM_DEADLINE);
+    assert_eq!(created.unclaimed_policy, UnclaimedPrizePolicy::Treasury);
+    
+    env.warp_to(REGISTRATION_START);
+    for index in 0..2 {
+        let player = env.wallet();
+        let register =
+            instructions::register_participant(&player.pubkey(), &env.season(season_number), None)
+                .unwrap();
+        let registered: ParticipantRegistered = event(&env.execute(&[register], &[&player]));
+        assert_eq!(registered.season, season_key);
+        assert_eq!(registered.owner, player.pubkey());
+        assert_eq!(
+            registered.participant,
+            pda::participant(&season_key, &player.pubkey()).0
+        );
+        assert_eq!(registered.index, index);
+        assert_eq!(registered.entry_fee_paid, ENTRY_FEE);
+        assert_eq!(registered.participant_count, index + 1);
+        assert_eq!(registered.prize_pool, (index + 1) * ENTRY_FEE);
+    }
+    
+    env.warp_to(REGISTRATION_END + 1);
+    let treasury = env.treasury.pubkey();
+    let collect =
+        instructions::collect_fee(&admin.pubkey(), &treasury, &env.season(season_number), None)
+            .unwrap();
+    let collected: FeeCollected = event(&env.execute(&[collect], &[&admin]));
+    assert_eq!(collected.treasury, treasury);
+    assert_eq!(collected.fee_amount, 2 * SOL / 5);
+    assert_eq!(collected.prize_pool_before, 2 * SOL);
+    assert_eq!(collected.prize_pool, 8 * SOL / 5);
+}
+
+#[test]
+fn settlement_claims_and_close_emit_events() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.ended_season(3);
+    let season_key = pda::season(season_number).0;
+    
+    let settle = env.settle_ix(season_number, &[&players[1], &players[0]]);
+    let settled = env.execute(&[settle], &[&admin]);
+    let winners_set: WinnersSet = event(&settled);
+    let season = env.season(season_number);
+    assert_eq!(winners_set.season, season_key);
+    assert_eq!(
+        winners_set.winners,
+        vec![players[1].pubkey(), players[0].pubkey()]
+    );
+    assert_eq!(winners_set.prize_amounts, vec![3 * SOL / 2, 9 * SOL / 10]);
+    assert_eq!(winners_set.merkle_root, None);
+    assert_eq!(winners_set.total_awarded, 12 * SOL / 5);
+    assert_eq!(winners_set.result_hash, season.result_hash);
+    assert_eq!(winners_set.challenge_deadline, season.challenge_deadline);
+    
+    let prizes: Vec<PrizeSet> = events(&settled);
+    let placed: Vec<_> = prizes
+        .iter()
+        .map(|prize| (prize.owner, prize.placement, prize.prize_amount))
+        .collect();
+    assert_eq!(
+        placed,
+        vec![
+            (players[1].pubkey(), 1, 3 * SOL / 2),
+            (players[0].pubkey(), 2, 9 * SOL / 10),
+        ]
+    );
+    
+    env.confirm_results(season_number);
+    let claim = env.claim_ix(season_number, &players[1].pubkey());
+    let claimed: PrizeClaimed = event(&env.execute(&[claim], &[&players[1]]));
+    assert_eq!(claimed.winner, players[1].pubkey());
+    assert_eq!(claimed.amount, 3 * SOL / 2);
+    assert_eq!(claimed.placement, 1);
+    assert_eq!(claimed.leaf_index, None);
+    assert_eq!(claimed.total_claimed, 3 * SOL / 2);
+    
+    // players[0] never claims, so the close releases their prize to the treasury
+    env.warp_to(CLAIM_DEADLINE + 1);
+    let close = env.close_ix(season_number);
+    let closed: SeasonClosed = event(&env.execute(&[close], &[&admin]));
+    assert_eq!(closed.season, season_key);
+    assert_eq!(closed.total_awarded, 12 * SOL / 5);
+    assert_eq!(closed.total_claimed, 3 * SOL / 2);
+    assert_eq!(closed.unclaimed, 9 * SOL / 10);
+    assert_eq!(closed.returned_to_authority, 0);
+}
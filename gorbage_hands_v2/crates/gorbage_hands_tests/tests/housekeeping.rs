@@ -0,0 +1,350 @@
+//! Reclaiming rent from participant accounts, keeping the season registry
+//! current and rolling unclaimed prizes into another season
+
+use std::slice;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::constants::PARTICIPANT_PAGE_SIZE;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::state::{
+    Participant, ParticipantPage, SeasonPhase, SeasonRegistry, UnclaimedPrizePolicy,
+};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+
+const SOL: u64 = LAMPORTS_PER_SOL;
+
+/// Season params on a timeline that starts after `CLAIM_DEADLINE`
+fn later_season_params() -> InitializeSeasonParams {
+    InitializeSeasonParams {
+        registration_start: CLAIM_DEADLINE + 100,
+        registration_end: CLAIM_DEADLINE + 1_000,
+        season_end: CLAIM_DEADLINE + 2_000,
+        claim_deadline: CLAIM_DEADLINE + 10_000,
+        ..season_params()
+    }
+}
+
+#[test]
+fn participants_close_once_nothing_is_owed() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(season_params());
+    let other_season = env.create_season(season_params());
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 3);
+    let others = env.register_wallets(other_season, 1);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    env.warp_to(SEASON_END + 1);
+    env.settle(season_number, &[&players[0], &players[1]]);
+    
+    let close = |env: &TestEnv, index: usize| {
+        instructions::close_participant(&players[index].pubkey(), &env.season(season_number))
+    };
+    env.expect_error(
+        &[close(&env, 2)],
+        &[&players[2]],
+        GorbageError::ParticipantNotClosable,
+    );
+    env.confirm_results(season_number);
+    
+    // Losers close right away, winners once they've claimed
+    let rent = env.rent(space::<Participant>());
+    let before = env.balance(&players[2].pubkey());
+    env.execute(&[close(&env, 2)], &[&players[2]]);
+    assert_eq!(env.balance(&players[2].pubkey()), before + rent);
+    
+    env.expect_error(
+        &[close(&env, 0)],
+        &[&players[0]],
+        GorbageError::ParticipantNotClosable,
+    );
+    env.claim(season_number, &players[0]);
+    env.execute(&[close(&env, 0)], &[&players[0]]);
+    assert!(!env.exists(&pda::participant(&pda::season(season_number).0, &players[0].pubkey()).0));
+    
+    // Anyone can batch close for the owners
+    let cranker = env.wallet();
+    let season = env.season(season_number);
+    let batch =
+        |owners: &[Pubkey]| instructions::close_participants(&cranker.pubkey(), &season, owners);
+    let owner = players[1].pubkey();
+    env.expect_error(
+        &[batch(&[owner])],
+        &[&cranker],
+        GorbageError::ParticipantNotClosable,
+    );
+    env.expect_error(
+        &[batch(&[])],
+        &[&cranker],
+        GorbageError::InvalidRemainingAccounts,
+    );
+    
+    let mut odd = batch(&[owner]);
+    odd.accounts.pop();
+    env.expect_error(&[odd], &[&cranker], GorbageError::InvalidRemainingAccounts);
+    let mut readonly = batch(&[owner]);
+    readonly.accounts[3].is_writable = false;
+    env.expect_error(
+        &[readonly],
+        &[&cranker],
+        GorbageError::InvalidRemainingAccounts,
+    );
+    let mut wrong_owner = batch(&[owner]);
+    wrong_owner.accounts[3] = AccountMeta::new(cranker.pubkey(), false);
+    env.expect_error(&[wrong_owner], &[&cranker], GorbageError::Unauthorized);
+    let mut other_participant = batch(&[owner]);
+    other_participant.accounts[2].pubkey =
+        pda::participant(&pda::season(other_season).0, &others[0].pubkey()).0;
+    other_participant.accounts[3].pubkey = others[0].pubkey();
+    env.expect_error(
+        &[other_participant],
+        &[&cranker],
+        GorbageError::NotRegistered,
+    );
+    
+    // An unclaimed prize is forfeited once the claim window closes
+    env.warp_to(CLAIM_DEADLINE + 1);
+    let before = env.balance(&owner);
+    env.execute(&[batch(&[owner])], &[&cranker]);
+    assert_eq!(env.balance(&owner), before + rent);
+}
+
+#[test]
+fn refunded_participants_close_after_a_cancellation() {
+    let mut env = TestEnv::new();
+    let (season_number, players) = env.season_with_wallets(2);
+    let admin = env.admin.insecure_clone();
+    let cancel =
+        instructions::cancel_season(&admin.pubkey(), &env.season(season_number), None, None)
+            .unwrap();
+    env.execute(&[cancel], &[&admin]);
+    
+    let season = env.season(season_number);
+    let close = instructions::close_participant(&players[0].pubkey(), &season);
+    env.expect_error(
+        slice::from_ref(&close),
+        &[&players[0]],
+        GorbageError::ParticipantNotClosable,
+    );
+    env.execute(
+        &[instructions::claim_refund(&players[0].pubkey(), &season, None).unwrap()],
+        &[&players[0]],
+    );
+    env.execute(&[close], &[&players[0]]);
+}
+
+#[test]
+fn roster_pages_close_to_whoever_paid_for_them_once_the_season_is_over() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.season_with_wallets(PARTICIPANT_PAGE_SIZE + 1);
+    let page_rent = env.rent(space::<ParticipantPage>());
+    
+    let cranker = env.wallet();
+    let close = |env: &TestEnv, page: u64| {
+        instructions::close_participant_page(
+            &cranker.pubkey(),
+            &env.participant_page(season_number, page),
+        )
+    };
+    env.expect_error(&[close(&env, 0)], &[&cranker], GorbageError::SeasonNotOver);
+    
+    let cancel =
+        instructions::cancel_season(&admin.pubkey(), &env.season(season_number), None, None)
+            .unwrap();
+    env.execute(&[cancel], &[&admin]);
+    
+    let mut wrong_recipient = close(&env, 0);
+    wrong_recipient.accounts[2].pubkey = cranker.pubkey();
+    env.expect_error(&[wrong_recipient], &[&cranker], GorbageError::Unauthorized);
+    
+    // Anyone can crank the rent back to the wallet that paid it: the authority
+    // for the first page, the registrant who crossed into the second
+    let first_page = pda::participant_page(&pda::season(season_number).0, 0).0;
+    let opener = players[PARTICIPANT_PAGE_SIZE].pubkey();
+    let (admin_before, opener_before) = (env.balance(&admin.pubkey()), env.balance(&opener));
+    env.execute(&[close(&env, 0), close(&env, 1)], &[&cranker]);
+    assert_eq!(env.balance(&admin.pubkey()), admin_before + page_rent);
+    assert_eq!(env.balance(&opener), opener_before + page_rent);
+    assert!(!env.exists(&first_page));
+    
+    // A season that missed its minimum counts as cancelled once registration ends
+    let undersubscribed = env.create_season(InitializeSeasonParams {
+        registration_start: SEASON_END + 100,
+        registration_end: SEASON_END + 200,
+        season_end: SEASON_END + 300,
+        ..season_params()
+    });
+    let open = instructions::open_participant_page(&admin.pubkey(), undersubscribed, 1);
+    env.execute(&[open], &[&admin]);
+    env.warp_to(SEASON_END + 201);
+    let open = instructions::open_participant_page(&admin.pubkey(), undersubscribed, 2);
+    env.expect_error(&[open], &[&admin], GorbageError::SeasonCancelled);
+    
+    let pages = [
+        env.participant_page(undersubscribed, 0),
+        env.participant_page(undersubscribed, 1),
+    ];
+    let before = env.balance(&admin.pubkey());
+    env.execute(
+        &[
+            instructions::close_participant_page(&cranker.pubkey(), &pages[0]),
+            instructions::close_participant_page(&cranker.pubkey(), &pages[1]),
+        ],
+        &[&cranker],
+    );
+    assert_eq!(env.balance(&admin.pubkey()), before + 2 * page_rent);
+}
+
+#[test]
+fn refresh_drops_finished_seasons_from_the_registry() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(season_params());
+    let undersubscribed = env.create_season(season_params());
+    env.warp_to(REGISTRATION_START);
+    env.register_wallets(season_number, 2);
+    env.register_wallets(undersubscribed, 1);
+    
+    let listed = |env: &TestEnv| -> Vec<(u64, SeasonPhase)> {
+        let season_registry: SeasonRegistry = env.fetch(&pda::season_registry().0);
+        season_registry
+            .seasons
+            .iter()
+            .map(|entry| (entry.season_number, entry.phase))
+            .collect()
+    };
+    env.warp_to(REGISTRATION_END + 1);
+    assert_eq!(
+        listed(&env),
+        vec![
+            (season_number, SeasonPhase::Scheduled),
+            (undersubscribed, SeasonPhase::Scheduled),
+        ]
+    );
+    
+    // The undersubscribed season was cancelled when registration closed, the
+    // registry only learns of it on refresh. The full season is unlisted
+    // until its fee is collected.
+    env.execute(
+        &[instructions::refresh_season_registry(&[
+            season_number,
+            undersubscribed,
+        ])],
+        &[],
+    );
+    assert_eq!(listed(&env), vec![]);
+    
+    let refresh = instructions::refresh_season_registry(&[undersubscribed]);
+    env.expect_error(&[refresh], &[], GorbageError::InvalidRemainingAccounts);
+    
+    env.collect_fee(season_number);
+    assert_eq!(listed(&env), vec![(season_number, SeasonPhase::Active)]);
+}
+
+#[test]
+fn unclaimed_prizes_roll_over_into_the_next_season() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let season_number = env.next_season_number();
+    let next_season = season_number + 1;
+    env.create_season(InitializeSeasonParams {
+        unclaimed_policy: UnclaimedPrizePolicy::Rollover {
+            season_number: next_season,
+        },
+        ..season_params()
+    });
+    env.create_season(later_season_params());
+    let unrelated_season = env.create_season(later_season_params());
+    
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 2);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    env.warp_to(SEASON_END + 1);
+    env.settle(season_number, &[&players[0], &players[1]]);
+    env.confirm_results(season_number);
+    env.claim(season_number, &players[0]);
+    
+    // 1.6 SOL pool paid 50/30 rescaled: 1 SOL claimed, 0.6 SOL left
+    let unclaimed = 3 * SOL / 5;
+    env.expect_error(
+        &[env.close_ix(season_number)],
+        &[&admin],
+        GorbageError::ClaimWindowOpen,
+    );
+    env.warp_to(CLAIM_DEADLINE + 1);
+    
+    let mut without_rollover = env.close_ix(season_number);
+    for index in [9, 10] {
+        without_rollover.accounts[index] = AccountMeta::new_readonly(gorbage_hands_v2::ID, false);
+    }
+    env.expect_error(
+        &[without_rollover],
+        &[&admin],
+        GorbageError::RolloverSeasonRequired,
+    );
+    let mut wrong_season = env.close_ix(season_number);
+    let unrelated_key = pda::season(unrelated_season).0;
+    wrong_season.accounts[9].pubkey = unrelated_key;
+    wrong_season.accounts[10].pubkey = pda::vault(&unrelated_key).0;
+    env.expect_error(
+        &[wrong_season],
+        &[&admin],
+        GorbageError::InvalidRolloverSeason,
+    );
+    let mut wrong_vault = env.close_ix(season_number);
+    wrong_vault.accounts[10].pubkey = pda::vault(&unrelated_key).0;
+    env.expect_error(
+        &[wrong_vault],
+        &[&admin],
+        GorbageError::InvalidRolloverSeason,
+    );
+    
+    env.execute(&[env.close_ix(season_number)], &[&admin]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Closed);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+    assert_eq!(env.balance(&env.vault(next_season)), unclaimed);
+    assert_eq!(env.season(next_season).rollover_pool, unclaimed);
+    
+    let first_page = env.participant_page(season_number, 0);
+    let close_page = instructions::close_participant_page(&admin.pubkey(), &first_page);
+    env.execute(&[close_page], &[&admin]);
+}
+
+#[test]
+fn unclaimed_prizes_a_cancelled_season_cannot_take_go_to_the_treasury() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.pubkey();
+    let season_number = env.next_season_number();
+    let next_season = season_number + 1;
+    env.create_season(InitializeSeasonParams {
+        unclaimed_policy: UnclaimedPrizePolicy::Rollover {
+            season_number: next_season,
+        },
+        ..season_params()
+    });
+    env.create_season(later_season_params());
+    let cancel = instructions::cancel_season(&admin.pubkey(), &env.season(next_season), None, None)
+        .unwrap();
+    env.execute(&[cancel], &[&admin]);
+    
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 2);
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    env.warp_to(SEASON_END + 1);
+    env.settle(season_number, &[&players[0], &players[1]]);
+    env.confirm_results(season_number);
+    env.claim(season_number, &players[0]);
+    env.warp_to(CLAIM_DEADLINE + 1);
+    
+    let treasury_before = env.balance(&treasury);
+    env.execute(&[env.close_ix(season_number)], &[&admin]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Closed);
+    assert_eq!(env.balance(&treasury), treasury_before + 3 * SOL / 5);
+    assert_eq!(env.season(next_season).rollover_pool, 0);
+}
@@ -0,0 +1,314 @@
+//! A contest season from creation to close, with exact lamport balances for
+//! the vault, the treasury and the winners at every step
+
+use std::slice;
+
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::state::SeasonPhase;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+
+const SOL: u64 = LAMPORTS_PER_SOL;
+
+#[test]
+fn season_lifecycle_balances() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.pubkey();
+    let season_number = env.create_season(season_params());
+    let vault = env.vault(season_number);
+    
+    // Registration: 4 entries of 1 SOL
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 4);
+    assert_eq!(env.balance(&vault), 4 * SOL);
+    
+    // Platform fee: 20% of the entries
+    let collect =
+        instructions::collect_fee(&admin.pubkey(), &treasury, &env.season(season_number), None)
+            .unwrap();
+    env.expect_error(
+        slice::from_ref(&collect),
+        &[&admin],
+        GorbageError::RegistrationNotEnded,
+    );
+    env.warp_to(REGISTRATION_END);
+    env.expect_error(
+        slice::from_ref(&collect),
+        &[&admin],
+        GorbageError::RegistrationNotEnded,
+    );
+    env.warp_to(REGISTRATION_END + 1);
+    env.execute(slice::from_ref(&collect), &[&admin]);
+    
+    assert_eq!(env.balance(&treasury), WALLET_FUNDING + 4 * SOL / 5);
+    assert_eq!(env.balance(&vault), 16 * SOL / 5);
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Active);
+    assert_eq!(env.listed_phase(season_number), Some(SeasonPhase::Active));
+    assert_eq!(season.fee_amount, 4 * SOL / 5);
+    assert_eq!(season.prize_pool, 16 * SOL / 5);
+    assert_eq!(season.treasury, treasury);
+    env.expect_error(&[collect], &[&admin], GorbageError::FeeAlreadyCollected);
+    
+    // Settlement: 50/30/20 of the 3.2 SOL pool
+    let settle = env.settle_ix(season_number, &[&players[2], &players[0], &players[3]]);
+    env.expect_error(
+        slice::from_ref(&settle),
+        &[&admin],
+        GorbageError::SeasonNotEnded,
+    );
+    env.warp_to(SEASON_END + 1);
+    env.execute(&[settle], &[&admin]);
+    
+    let prizes = [8 * SOL / 5, 24 * SOL / 25, 16 * SOL / 25];
+    let winners = [&players[2], &players[0], &players[3]];
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Settling);
+    assert_eq!(env.listed_phase(season_number), Some(SeasonPhase::Settling));
+    assert_eq!(
+        season.winners,
+        winners.map(|winner| winner.pubkey()).to_vec()
+    );
+    assert_eq!(season.total_awarded, 16 * SOL / 5);
+    assert_eq!(season.challenge_deadline, SEASON_END + 1 + CHALLENGE_WINDOW);
+    for (index, (winner, prize)) in winners.iter().zip(prizes).enumerate() {
+        let participant = env.participant(season_number, &winner.pubkey());
+        assert_eq!(participant.placement as usize, index + 1);
+        assert_eq!(participant.prize_amount, prize);
+    }
+    assert_eq!(
+        env.participant(season_number, &players[1].pubkey())
+            .placement,
+        0
+    );
+    
+    // Prizes stay locked until the results are attested and the challenge window closes
+    let claim = env.claim_ix(season_number, &winners[0].pubkey());
+    env.expect_error(
+        slice::from_ref(&claim),
+        &[winners[0]],
+        GorbageError::PrizesNotAssigned,
+    );
+    env.confirm_results(season_number);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Settled);
+    assert_eq!(env.listed_phase(season_number), None);
+    assert_eq!(env.balance(&vault), 16 * SOL / 5);
+    
+    // Claims pay exactly the assigned prizes
+    let mut vault_balance = env.balance(&vault);
+    for (winner, prize) in winners.iter().zip(prizes) {
+        let before = env.balance(&winner.pubkey());
+        env.claim(season_number, winner);
+        vault_balance -= prize;
+        assert_eq!(env.balance(&winner.pubkey()), before + prize);
+        assert_eq!(env.balance(&vault), vault_balance);
+    }
+    assert_eq!(env.balance(&vault), 0);
+    assert_eq!(env.season(season_number).total_claimed, 16 * SOL / 5);
+    env.expect_error(&[claim], &[winners[0]], GorbageError::PrizeAlreadyClaimed);
+    let claim = env.claim_ix(season_number, &players[1].pubkey());
+    env.expect_error(&[claim], &[&players[1]], GorbageError::NotAWinner);
+    
+    // Close: nothing is left, the treasury keeps exactly the fee
+    let admin_before = env.balance(&admin.pubkey());
+    let close = env.close_ix(season_number);
+    env.execute(slice::from_ref(&close), &[&admin]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Closed);
+    assert_eq!(env.balance(&admin.pubkey()), admin_before);
+    assert_eq!(env.balance(&treasury), WALLET_FUNDING + 4 * SOL / 5);
+    assert_eq!(env.listed_phase(season_number), None);
+    
+    env.expect_error(&[close], &[&admin], GorbageError::SeasonClosed);
+    let claim = env.claim_ix(season_number, &winners[0].pubkey());
+    env.expect_error(&[claim], &[winners[0]], GorbageError::SeasonClosed);
+}
+
+#[test]
+fn fewer_winners_than_placements_share_the_whole_pool() {
+    let mut env = TestEnv::new();
+    let (season_number, players) = env.ended_season(4);
+    
+    env.settle(season_number, &[&players[0], &players[1]]);
+    env.confirm_results(season_number);
+    
+    // 50/30 of the schedule, rescaled to 5/8 and 3/8 of the 3.2 SOL pool
+    let first = env.balance(&players[0].pubkey());
+    let second = env.balance(&players[1].pubkey());
+    env.claim(season_number, &players[0]);
+    env.claim(season_number, &players[1]);
+    assert_eq!(env.balance(&players[0].pubkey()), first + 2 * SOL);
+    assert_eq!(env.balance(&players[1].pubkey()), second + 6 * SOL / 5);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
+
+#[test]
+fn collect_fee_errors() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.pubkey();
+    
+    // Before registration has even opened
+    let scheduled = env.create_season(season_params());
+    let collect =
+        instructions::collect_fee(&admin.pubkey(), &treasury, &env.season(scheduled), None)
+            .unwrap();
+    env.expect_error(&[collect], &[&admin], GorbageError::RegistrationNotEnded);
+    
+    // Nobody paid in
+    let empty = env.create_season(InitializeSeasonParams {
+        min_participants: 0,
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_END + 1);
+    let collect =
+        instructions::collect_fee(&admin.pubkey(), &treasury, &env.season(empty), None).unwrap();
+    env.expect_error(&[collect], &[&admin], GorbageError::NoPrizePool);
+    
+    // Registration closed short of the minimum
+    let collect =
+        instructions::collect_fee(&admin.pubkey(), &treasury, &env.season(scheduled), None)
+            .unwrap();
+    env.expect_error(&[collect], &[&admin], GorbageError::MinParticipantsNotMet);
+    
+    let mut env = TestEnv::new();
+    let (season_number, _) = env.season_with_wallets(2);
+    env.warp_to(REGISTRATION_END + 1);
+    let outsider = env.wallet();
+    let collect = instructions::collect_fee(
+        &outsider.pubkey(),
+        &treasury,
+        &env.season(season_number),
+        None,
+    )
+    .unwrap();
+    env.expect_error(&[collect], &[&outsider], GorbageError::Unauthorized);
+}
+
+#[test]
+fn settle_season_validates_winners() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (other_season, others) = env.season_with_wallets(2);
+    let (season_number, players) = env.season_with_wallets(4);
+    
+    let settle = env.settle_ix(season_number, &[&players[0]]);
+    env.expect_error(&[settle], &[&admin], GorbageError::FeeNotCollected);
+    
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(other_season);
+    env.collect_fee(season_number);
+    env.warp_to(SEASON_END + 1);
+    
+    let outsider = env.wallet();
+    let season = env.season(season_number);
+    let settle =
+        instructions::settle_season(&outsider.pubkey(), &season, &[players[0].pubkey()], false);
+    env.expect_error(&[settle], &[&outsider], GorbageError::Unauthorized);
+    
+    let cases = [
+        (Vec::new(), GorbageError::InvalidWinnerCount),
+        (
+            players.iter().collect::<Vec<_>>(),
+            GorbageError::InvalidWinnerCount,
+        ),
+        (
+            vec![&players[0], &players[1], &players[0]],
+            GorbageError::DuplicateWinner,
+        ),
+    ];
+    for (winners, expected) in cases {
+        let settle = env.settle_ix(season_number, &winners);
+        env.expect_error(&[settle], &[&admin], expected);
+    }
+    
+    // A participant of another season
+    let mut settle = env.settle_ix(season_number, &[&players[0]]);
+    settle.accounts[3].pubkey =
+        pda::participant(&pda::season(other_season).0, &others[0].pubkey()).0;
+    env.expect_error(&[settle], &[&admin], GorbageError::NotRegistered);
+    
+    // Winners are written to, so they must be writable
+    let mut settle = env.settle_ix(season_number, &[&players[0]]);
+    settle.accounts[3].is_writable = false;
+    env.expect_error(&[settle], &[&admin], GorbageError::InvalidRemainingAccounts);
+    
+    env.settle(season_number, &[&players[0]]);
+    let settle = env.settle_ix(season_number, &[&players[1]]);
+    env.expect_error(&[settle], &[&admin], GorbageError::WinnersAlreadySet);
+}
+
+#[test]
+fn claims_and_close_follow_the_phase() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.ended_season(2);
+    
+    let claim = env.claim_ix(season_number, &players[0].pubkey());
+    env.expect_error(&[claim], &[&players[0]], GorbageError::WinnersNotSet);
+    let close = env.close_ix(season_number);
+    env.expect_error(&[close], &[&admin], GorbageError::WinnersNotSet);
+    
+    env.settle(season_number, &[&players[0]]);
+    let close = env.close_ix(season_number);
+    env.expect_error(&[close], &[&admin], GorbageError::PrizesNotAssigned);
+    
+    env.confirm_results(season_number);
+    let outsider = env.wallet();
+    let close =
+        instructions::close_season(&outsider.pubkey(), &env.season(season_number), None).unwrap();
+    env.expect_error(&[close], &[&outsider], GorbageError::Unauthorized);
+    
+    // Settled seasons can no longer be cancelled
+    let cancel =
+        instructions::cancel_season(&admin.pubkey(), &env.season(season_number), None, None)
+            .unwrap();
+    env.expect_error(&[cancel], &[&admin], GorbageError::SeasonAlreadySettled);
+}
+
+#[test]
+fn unclaimed_prizes_go_to_the_treasury_after_the_deadline() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.pubkey();
+    let (season_number, players) = env.ended_season(2);
+    env.settle(season_number, &[&players[0], &players[1]]);
+    env.confirm_results(season_number);
+    
+    // Only second place claims: 3/8 of the 1.6 SOL pool
+    env.claim(season_number, &players[1]);
+    let unclaimed = SOL;
+    assert_eq!(env.balance(&env.vault(season_number)), unclaimed);
+    
+    let close = env.close_ix(season_number);
+    env.expect_error(
+        slice::from_ref(&close),
+        &[&admin],
+        GorbageError::ClaimWindowOpen,
+    );
+    
+    env.warp_to(CLAIM_DEADLINE + 1);
+    let claim = env.claim_ix(season_number, &players[0].pubkey());
+    env.expect_error(&[claim], &[&players[0]], GorbageError::ClaimDeadlinePassed);
+    
+    let mut without_treasury = close.clone();
+    without_treasury.accounts[7].pubkey = gorbage_hands_v2::ID;
+    without_treasury.accounts[7].is_writable = false;
+    env.expect_error(
+        &[without_treasury],
+        &[&admin],
+        GorbageError::TreasuryRequired,
+    );
+    let mut wrong_treasury = close.clone();
+    wrong_treasury.accounts[7].pubkey = admin.pubkey();
+    env.expect_error(&[wrong_treasury], &[&admin], GorbageError::TreasuryMismatch);
+    
+    let treasury_before = env.balance(&treasury);
+    let admin_before = env.balance(&admin.pubkey());
+    env.execute(&[close], &[&admin]);
+    assert_eq!(env.balance(&treasury), treasury_before + unclaimed);
+    assert_eq!(env.balance(&admin.pubkey()), admin_before);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
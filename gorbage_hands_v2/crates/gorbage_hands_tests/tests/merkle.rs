@@ -0,0 +1,309 @@
+//! Merkle-root settlement: posting a root and claiming with proofs built by
+//! `gorbage_merkle`, and closing the claim bitmap
+
+use std::slice;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::constants::MAX_MERKLE_LEAVES;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::instructions::DisputeResolution;
+use gorbage_hands_v2::merkle;
+use gorbage_hands_v2::state::{ClaimBitmap, SeasonPhase};
+use gorbage_merkle::{Claim, Distribution, Entry};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: u64 = LAMPORTS_PER_SOL;
+
+fn hash(hex: &str) -> [u8; 32] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+fn settle_with_root(
+    env: &mut TestEnv,
+    season_number: u64,
+    root: [u8; 32],
+    total: u64,
+    leaf_count: u32,
+) {
+    let admin = env.admin.insecure_clone();
+    let settle = instructions::settle_season_with_root(
+        &admin.pubkey(),
+        &env.season(season_number),
+        root,
+        total,
+        leaf_count,
+    );
+    env.execute(&[settle], &[&admin]);
+}
+
+/// Pass the optional account `key` of `ix` as omitted
+fn without_account(ix: &mut Instruction, key: &Pubkey) {
+    let meta = ix.accounts.iter_mut().find(|meta| meta.pubkey == *key).unwrap();
+    *meta = AccountMeta::new_readonly(gorbage_hands_v2::ID, false);
+}
+
+fn claim_ix(
+    env: &TestEnv,
+    season_number: u64,
+    winner: &Pubkey,
+    claim: &Claim,
+) -> Instruction {
+    let proof = claim.proof.iter().map(|sibling| hash(sibling)).collect();
+    instructions::claim_prize_with_proof(
+        winner,
+        &env.season(season_number),
+        claim.index,
+        claim.amount,
+        proof,
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn winners_claim_with_proofs() {
+    let mut env = TestEnv::new();
+    let (season_number, players) = env.ended_season(4);
+    
+    // Winners need not be participants; leaf 2 pays a wallet outside the season
+    let outsider = env.wallet();
+    let winners: [&Keypair; 3] = [&players[2], &players[0], &outsider];
+    let amounts = [8 * SOL / 5, 24 * SOL / 25, 16 * SOL / 25];
+    let entries: Vec<Entry> = winners
+        .iter()
+        .zip(amounts)
+        .map(|(winner, amount)| Entry {
+            wallet: winner.pubkey(),
+            amount,
+        })
+        .collect();
+    let pool = env.season(season_number).prize_pool;
+    assert_eq!(pool, 16 * SOL / 5);
+    let distribution = Distribution::build(&entries, pool).unwrap();
+    
+    settle_with_root(
+        &mut env,
+        season_number,
+        hash(&distribution.root),
+        distribution.total,
+        distribution.leaf_count,
+    );
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Settling);
+    assert_eq!(env.listed_phase(season_number), Some(SeasonPhase::Settling));
+    assert_eq!(season.merkle_leaf_count, 3);
+    assert_eq!(season.total_awarded, pool);
+    
+    let claims = &distribution.claims;
+    let claim = claim_ix(&env, season_number, &winners[0].pubkey(), &claims[0]);
+    env.expect_error(&[claim], &[winners[0]], GorbageError::PrizesNotAssigned);
+    
+    env.confirm_results(season_number);
+    
+    // Someone else's leaf, a forged amount, an index past the tree and a zero amount
+    let claim = claim_ix(&env, season_number, &winners[1].pubkey(), &claims[0]);
+    env.expect_error(&[claim], &[winners[1]], GorbageError::InvalidMerkleProof);
+    let forged = Claim {
+        amount: claims[1].amount + 1,
+        ..claims[1].clone()
+    };
+    let claim = claim_ix(&env, season_number, &winners[1].pubkey(), &forged);
+    env.expect_error(&[claim], &[winners[1]], GorbageError::InvalidMerkleProof);
+    let past_end = Claim {
+        index: 3,
+        ..claims[1].clone()
+    };
+    let claim = claim_ix(&env, season_number, &winners[1].pubkey(), &past_end);
+    env.expect_error(&[claim], &[winners[1]], GorbageError::InvalidClaimIndex);
+    let zero = Claim {
+        amount: 0,
+        ..claims[1].clone()
+    };
+    let claim = claim_ix(&env, season_number, &winners[1].pubkey(), &zero);
+    env.expect_error(&[claim], &[winners[1]], GorbageError::NotAWinner);
+    
+    // Participant-based claims don't apply to a Merkle settlement
+    let claim = env.claim_ix(season_number, &players[2].pubkey());
+    env.expect_error(&[claim], &[&players[2]], GorbageError::NotAWinner);
+    
+    for (winner, claim) in winners.iter().zip(claims) {
+        let before = env.balance(&winner.pubkey());
+        let ix = claim_ix(&env, season_number, &winner.pubkey(), claim);
+        env.execute(slice::from_ref(&ix), &[*winner]);
+        assert_eq!(env.balance(&winner.pubkey()), before + claim.amount);
+        env.expect_error(&[ix], &[*winner], GorbageError::PrizeAlreadyClaimed);
+    }
+    assert_eq!(env.season(season_number).total_claimed, pool);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+    
+    // Closing the season returns the claim bitmap rent to the authority
+    let admin = env.admin.insecure_clone();
+    let bitmap_key = pda::claim_bitmap(&pda::season(season_number).0, 0).0;
+    let bitmap_rent = env.balance(&bitmap_key);
+    assert_eq!(bitmap_rent, env.rent(8 + ClaimBitmap::space(3)));
+    let mut close = env.close_ix(season_number);
+    without_account(&mut close, &bitmap_key);
+    env.expect_error(&[close], &[&admin], GorbageError::ClaimBitmapRequired);
+    let before = env.balance(&admin.pubkey());
+    env.execute(&[env.close_ix(season_number)], &[&admin]);
+    assert_eq!(env.balance(&admin.pubkey()), before + bitmap_rent);
+    assert!(!env.exists(&bitmap_key));
+}
+
+#[test]
+fn voided_merkle_results_close_their_claim_bitmap() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.ended_season(2);
+    let total = env.season(season_number).prize_pool;
+    let root = merkle::leaf_hash(0, &players[0].pubkey(), total);
+    settle_with_root(&mut env, season_number, root, total, 1);
+    
+    let disputer = &players[1];
+    let open = instructions::open_dispute(&disputer.pubkey(), &env.season(season_number));
+    env.execute(&[open], &[disputer]);
+    let void = |env: &TestEnv| {
+        instructions::resolve_dispute(
+            &admin.pubkey(),
+            &env.season(season_number),
+            0,
+            &disputer.pubkey(),
+            DisputeResolution::Void,
+        )
+    };
+    
+    let bitmap_key = pda::claim_bitmap(&pda::season(season_number).0, 0).0;
+    let bitmap_rent = env.balance(&bitmap_key);
+    let mut without_bitmap = void(&env);
+    without_account(&mut without_bitmap, &bitmap_key);
+    env.expect_error(&[without_bitmap], &[&admin], GorbageError::ClaimBitmapRequired);
+    
+    let before = env.balance(&admin.pubkey());
+    env.execute(&[void(&env)], &[&admin]);
+    assert_eq!(env.balance(&admin.pubkey()), before + bitmap_rent);
+    assert!(!env.exists(&bitmap_key));
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Active);
+    
+    // The next proposal gets a fresh bitmap
+    settle_with_root(&mut env, season_number, root, total, 1);
+    assert!(env.exists(&pda::claim_bitmap(&pda::season(season_number).0, 1).0));
+}
+
+#[test]
+fn claims_never_exceed_the_posted_total() {
+    let mut env = TestEnv::new();
+    let (season_number, players) = env.ended_season(2);
+    
+    // Two leaves each claiming the whole committed total
+    let total = env.season(season_number).prize_pool - 1;
+    let leaves = [
+        merkle::leaf_hash(0, &players[0].pubkey(), total),
+        merkle::leaf_hash(1, &players[1].pubkey(), total),
+    ];
+    let root = merkle::node_hash(&leaves[0], &leaves[1]);
+    settle_with_root(&mut env, season_number, root, total, 2);
+    env.confirm_results(season_number);
+    
+    let season = env.season(season_number);
+    let first = instructions::claim_prize_with_proof(
+        &players[0].pubkey(),
+        &season,
+        0,
+        total,
+        vec![leaves[1]],
+        None,
+    )
+    .unwrap();
+    env.execute(&[first], &[&players[0]]);
+    let second = instructions::claim_prize_with_proof(
+        &players[1].pubkey(),
+        &season,
+        1,
+        total,
+        vec![leaves[0]],
+        None,
+    )
+    .unwrap();
+    env.expect_error(&[second], &[&players[1]], GorbageError::PayoutExceedsPool);
+    assert_eq!(env.season(season_number).total_claimed, total);
+}
+
+#[test]
+fn settle_with_root_validates_the_proposal() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, _players) = env.season_with_wallets(2);
+    
+    let settle = |env: &TestEnv, total: u64, leaf_count: u32| {
+        instructions::settle_season_with_root(
+            &admin.pubkey(),
+            &env.season(season_number),
+            [7; 32],
+            total,
+            leaf_count,
+        )
+    };
+    env.expect_error(
+        &[settle(&env, SOL, 1)],
+        &[&admin],
+        GorbageError::FeeNotCollected,
+    );
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    env.expect_error(
+        &[settle(&env, SOL, 1)],
+        &[&admin],
+        GorbageError::SeasonNotEnded,
+    );
+    env.warp_to(SEASON_END + 1);
+    
+    let outsider = env.wallet();
+    let mut not_authority = settle(&env, SOL, 1);
+    not_authority.accounts[0].pubkey = outsider.pubkey();
+    env.expect_error(&[not_authority], &[&outsider], GorbageError::Unauthorized);
+    
+    env.expect_error(
+        &[settle(&env, SOL, 0)],
+        &[&admin],
+        GorbageError::InvalidLeafCount,
+    );
+    env.expect_error(
+        &[settle(&env, SOL, MAX_MERKLE_LEAVES + 1)],
+        &[&admin],
+        GorbageError::InvalidLeafCount,
+    );
+    let pool = env.season(season_number).prize_pool;
+    env.expect_error(
+        &[settle(&env, pool + 1, 1)],
+        &[&admin],
+        GorbageError::PayoutExceedsPool,
+    );
+    
+    env.execute(&[settle(&env, pool, 1)], &[&admin]);
+    // The epoch's claim bitmap already exists, so a second root fails to create it
+    env.expect_failure(&[settle(&env, pool, 1)], &[&admin]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Settling);
+    
+    // A season that missed its minimum is cancelled and can't be settled
+    let season_number = env.create_season(InitializeSeasonParams {
+        registration_start: SEASON_END + 100,
+        registration_end: SEASON_END + 200,
+        season_end: SEASON_END + 300,
+        ..season_params()
+    });
+    env.warp_to(SEASON_END + 100);
+    env.register_wallets(season_number, 1);
+    env.warp_to(SEASON_END + 301);
+    let settle = instructions::settle_season_with_root(
+        &admin.pubkey(),
+        &env.season(season_number),
+        [7; 32],
+        0,
+        1,
+    );
+    env.expect_error(&[settle], &[&admin], GorbageError::MinParticipantsNotMet);
+}
@@ -0,0 +1,287 @@
+//! Oracle attestation of proposed results and the dispute window
+
+use std::slice;
+
+use gorbage_hands_client::{instructions, pda};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::events::{DisputeOpened, DisputeResolved, ResultsVoided};
+use gorbage_hands_v2::instructions::DisputeResolution;
+use gorbage_hands_v2::state::{Dispute, SeasonPhase};
+use litesvm::types::TransactionMetadata;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+
+fn add_second_attestor(env: &mut TestEnv, threshold: u8) -> Keypair {
+    let admin = env.admin.insecure_clone();
+    let second = Keypair::new();
+    env.execute(
+        &[instructions::add_attestor(&admin.pubkey(), &second.pubkey())],
+        &[&admin],
+    );
+    env.execute(
+        &[instructions::set_attestation_threshold(&admin.pubkey(), threshold)],
+        &[&admin],
+    );
+    second
+}
+
+fn attest_as(env: &mut TestEnv, attestor: &Keypair, season_number: u64) {
+    let attest = instructions::attest_results(&attestor.pubkey(), &env.season(season_number));
+    env.execute(&[attest], &[attestor]);
+}
+
+fn open_dispute(env: &mut TestEnv, season_number: u64, disputer: &Keypair) -> TransactionMetadata {
+    let open = instructions::open_dispute(&disputer.pubkey(), &env.season(season_number));
+    env.execute(&[open], &[disputer])
+}
+
+fn resolve_dispute(
+    env: &mut TestEnv,
+    season_number: u64,
+    settlement_epoch: u32,
+    disputer: &Keypair,
+    resolution: DisputeResolution,
+) -> TransactionMetadata {
+    let admin = env.admin.insecure_clone();
+    let resolve = instructions::resolve_dispute(
+        &admin.pubkey(),
+        &env.season(season_number),
+        settlement_epoch,
+        &disputer.pubkey(),
+        resolution,
+    );
+    env.execute(&[resolve], &[&admin])
+}
+
+#[test]
+fn results_need_the_attestation_threshold() {
+    let mut env = TestEnv::new();
+    let attestor = env.attestor.insecure_clone();
+    let second = add_second_attestor(&mut env, 2);
+    let (season_number, players) = env.ended_season(2);
+    
+    let attest = instructions::attest_results(&attestor.pubkey(), &env.season(season_number));
+    env.expect_error(&[attest], &[&attestor], GorbageError::WinnersNotSet);
+    let finalize = env.finalize_ix(season_number);
+    env.expect_error(&[finalize], &[], GorbageError::WinnersNotSet);
+    
+    env.settle(season_number, &[&players[0]]);
+    let season = env.season(season_number);
+    
+    let outsider = env.wallet();
+    let attest = instructions::attest_results(&outsider.pubkey(), &season);
+    env.expect_error(&[attest], &[&outsider], GorbageError::NotAnAttestor);
+    
+    // The result hash is the last 32 bytes of the instruction data
+    let mut attest = instructions::attest_results(&attestor.pubkey(), &season);
+    *attest.data.last_mut().unwrap() ^= 1;
+    env.expect_error(&[attest], &[&attestor], GorbageError::ResultHashMismatch);
+    
+    attest_as(&mut env, &attestor, season_number);
+    let attest = instructions::attest_results(&attestor.pubkey(), &season);
+    env.expect_error(&[attest], &[&attestor], GorbageError::AlreadyAttested);
+    
+    let finalize = env.finalize_ix(season_number);
+    env.expect_error(
+        slice::from_ref(&finalize),
+        &[],
+        GorbageError::ChallengeWindowOpen,
+    );
+    env.warp_to(season.challenge_deadline + 1);
+    env.expect_error(
+        slice::from_ref(&finalize),
+        &[],
+        GorbageError::NotEnoughAttestations,
+    );
+    
+    attest_as(&mut env, &second, season_number);
+    env.execute(slice::from_ref(&finalize), &[]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Settled);
+    
+    env.expect_error(&[finalize], &[], GorbageError::SeasonAlreadySettled);
+    let attest = instructions::attest_results(&second.pubkey(), &season);
+    env.expect_error(&[attest], &[&second], GorbageError::SeasonAlreadySettled);
+}
+
+#[test]
+fn removed_attestors_no_longer_count() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let attestor = env.attestor.insecure_clone();
+    let second = add_second_attestor(&mut env, 1);
+    let (season_number, players) = env.ended_season(2);
+    env.settle(season_number, &[&players[0]]);
+    
+    attest_as(&mut env, &attestor, season_number);
+    env.execute(
+        &[instructions::remove_attestor(&admin.pubkey(), &attestor.pubkey())],
+        &[&admin],
+    );
+    
+    let season = env.season(season_number);
+    env.warp_to(season.challenge_deadline + 1);
+    let finalize = env.finalize_ix(season_number);
+    env.expect_error(
+        slice::from_ref(&finalize),
+        &[],
+        GorbageError::NotEnoughAttestations,
+    );
+    
+    attest_as(&mut env, &second, season_number);
+    env.execute(&[finalize], &[]);
+    assert_eq!(
+        env.season(season_number).attestations,
+        vec![second.pubkey()]
+    );
+}
+
+#[test]
+fn upheld_dispute_forfeits_the_bond() {
+    let mut env = TestEnv::new();
+    let treasury = env.treasury.pubkey();
+    let (season_number, players) = env.ended_season(4);
+    let disputer = &players[1];
+    
+    let open = instructions::open_dispute(&disputer.pubkey(), &env.season(season_number));
+    env.expect_error(&[open], &[disputer], GorbageError::WinnersNotSet);
+    
+    let admin = env.admin.insecure_clone();
+    let disqualify = instructions::disqualify_participant(
+        &admin.pubkey(),
+        &env.season(season_number),
+        &players[3].pubkey(),
+        None,
+    )
+    .unwrap();
+    env.execute(&[disqualify], &[&admin]);
+    env.settle(season_number, &[&players[0]]);
+    let before = env.balance(&disputer.pubkey());
+    let opened: DisputeOpened = event(&open_dispute(&mut env, season_number, disputer));
+    assert_eq!((opened.bond, opened.open_disputes), (DISPUTE_BOND, 1));
+    
+    let dispute_key = pda::dispute(&pda::season(season_number).0, 0, &disputer.pubkey()).0;
+    let dispute_rent = env.rent(space::<Dispute>());
+    assert_eq!(
+        env.balance(&disputer.pubkey()),
+        before - DISPUTE_BOND - dispute_rent
+    );
+    assert_eq!(env.balance(&dispute_key), DISPUTE_BOND + dispute_rent);
+    let dispute: Dispute = env.fetch(&dispute_key);
+    assert_eq!(dispute.disputer, disputer.pubkey());
+    assert_eq!(dispute.bond, DISPUTE_BOND);
+    assert_eq!(env.season(season_number).open_disputes, 1);
+    
+    // Only active participants can dispute
+    let outsider = env.wallet();
+    let open = instructions::open_dispute(&outsider.pubkey(), &env.season(season_number));
+    env.expect_failure(&[open], &[&outsider]);
+    let open = instructions::open_dispute(&players[3].pubkey(), &env.season(season_number));
+    env.expect_error(&[open], &[&players[3]], GorbageError::ParticipantInactive);
+    
+    env.attest(season_number);
+    let season = env.season(season_number);
+    env.warp_to(season.challenge_deadline + 1);
+    let finalize = env.finalize_ix(season_number);
+    env.expect_error(slice::from_ref(&finalize), &[], GorbageError::OpenDisputes);
+    let open = instructions::open_dispute(&players[2].pubkey(), &season);
+    env.expect_error(&[open], &[&players[2]], GorbageError::ChallengeWindowClosed);
+    
+    let resolve = instructions::resolve_dispute(
+        &outsider.pubkey(),
+        &season,
+        0,
+        &disputer.pubkey(),
+        DisputeResolution::Uphold,
+    );
+    env.expect_error(&[resolve], &[&outsider], GorbageError::Unauthorized);
+    let mut resolve = instructions::resolve_dispute(
+        &admin.pubkey(),
+        &season,
+        0,
+        &disputer.pubkey(),
+        DisputeResolution::Uphold,
+    );
+    resolve.accounts[6].pubkey = outsider.pubkey();
+    env.expect_error(&[resolve], &[&admin], GorbageError::TreasuryMismatch);
+    
+    let treasury_before = env.balance(&treasury);
+    let upheld = resolve_dispute(
+        &mut env,
+        season_number,
+        0,
+        disputer,
+        DisputeResolution::Uphold,
+    );
+    let resolved: DisputeResolved = event(&upheld);
+    assert_eq!((resolved.bond_forfeited, resolved.open_disputes), (DISPUTE_BOND, 0));
+    assert_eq!(env.balance(&treasury), treasury_before + DISPUTE_BOND);
+    assert_eq!(env.balance(&disputer.pubkey()), before - DISPUTE_BOND);
+    assert!(!env.exists(&dispute_key));
+    assert_eq!(env.season(season_number).open_disputes, 0);
+    
+    env.execute(&[finalize], &[]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Settled);
+}
+
+#[test]
+fn voided_results_are_proposed_again() {
+    let mut env = TestEnv::new();
+    let treasury = env.treasury.pubkey();
+    let (season_number, players) = env.ended_season(3);
+    env.settle(season_number, &[&players[0]]);
+    
+    let disputer = &players[1];
+    let late_disputer = &players[2];
+    let before = env.balance(&disputer.pubkey());
+    let late_before = env.balance(&late_disputer.pubkey());
+    open_dispute(&mut env, season_number, disputer);
+    open_dispute(&mut env, season_number, late_disputer);
+    env.attest(season_number);
+    
+    let treasury_before = env.balance(&treasury);
+    let voided = resolve_dispute(
+        &mut env,
+        season_number,
+        0,
+        disputer,
+        DisputeResolution::Void,
+    );
+    assert_eq!(env.balance(&disputer.pubkey()), before);
+    let event: ResultsVoided = event(&voided);
+    assert_eq!(event.settlement_epoch, 0);
+    assert_eq!(event.disputer, disputer.pubkey());
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Active);
+    assert_eq!(env.listed_phase(season_number), Some(SeasonPhase::Active));
+    assert_eq!(season.settlement_epoch, 1);
+    assert!(season.winners.is_empty());
+    assert!(season.attestations.is_empty());
+    assert_eq!(season.open_disputes, 0);
+    
+    // Disputes against the voided proposal are closed with their bond returned
+    resolve_dispute(
+        &mut env,
+        season_number,
+        0,
+        late_disputer,
+        DisputeResolution::Uphold,
+    );
+    assert_eq!(env.balance(&late_disputer.pubkey()), late_before);
+    assert_eq!(env.balance(&treasury), treasury_before);
+    
+    // The authority proposes new results, the old placement no longer counts
+    env.settle(season_number, &[&players[1]]);
+    env.confirm_results(season_number);
+    let claim = env.claim_ix(season_number, &players[0].pubkey());
+    env.expect_error(&[claim], &[&players[0]], GorbageError::NotAWinner);
+    
+    let winner_before = env.balance(&players[1].pubkey());
+    env.claim(season_number, &players[1]);
+    assert_eq!(
+        env.balance(&players[1].pubkey()),
+        winner_before + 12 * LAMPORTS_PER_SOL / 5
+    );
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
@@ -0,0 +1,292 @@
+//! Raffle seasons: commit-reveal of the draw seed and verification of the
+//! client-computed draw sequence
+
+use anchor_lang::prelude::Pubkey;
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::constants::{RAFFLE_REVEAL_WINDOW, RAFFLE_SLOT_DELAY};
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::events::{RaffleRevealed, RaffleSlotLocked};
+use gorbage_hands_v2::raffle;
+use gorbage_hands_v2::state::{ParticipantStatus, SeasonMode, SeasonPhase};
+use solana_sdk::hash::Hash;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::slot_hashes::SlotHashes;
+
+const SOL: u64 = LAMPORTS_PER_SOL;
+const SECRET: [u8; 32] = [42; 32];
+const SLOT_HASH: [u8; 32] = [9; 32];
+
+/// Raffle season with `count` wallets, locked to its draw slot once registration closed
+fn raffle_season(env: &mut TestEnv, count: usize) -> (u64, Vec<Keypair>) {
+    let season_number = env.create_season(InitializeSeasonParams {
+        mode: SeasonMode::Raffle,
+        raffle_commitment: raffle::commitment(&SECRET),
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, count);
+    env.warp_to(REGISTRATION_END + 1);
+    lock(env, season_number);
+    (season_number, players)
+}
+
+fn lock(env: &mut TestEnv, season_number: u64) {
+    let lock = instructions::lock_raffle(&env.season(season_number));
+    env.execute(&[lock], &[]);
+}
+
+/// Move past the locked slot and record `SLOT_HASH` as its hash
+fn reach_raffle_slot(env: &mut TestEnv, season_number: u64) {
+    let raffle_slot = env.season(season_number).raffle_slot;
+    env.warp_to_slot(raffle_slot + 1);
+    env.svm.set_sysvar(&SlotHashes::new(&[
+        (raffle_slot + 1, Hash::new_from_array([1; 32])),
+        (raffle_slot, Hash::new_from_array(SLOT_HASH)),
+    ]));
+}
+
+/// Reveal the secret and collect the fee, leaving the season in play
+fn reveal(env: &mut TestEnv, season_number: u64) {
+    reach_raffle_slot(env, season_number);
+    let admin = env.admin.insecure_clone();
+    let reveal = instructions::reveal_raffle(&admin.pubkey(), &env.season(season_number), SECRET);
+    env.execute(&[reveal], &[&admin]);
+    env.collect_fee(season_number);
+}
+
+/// Wallets hit by each draw until the season's winners are filled, skipping
+/// inactive and already drawn participants the way the program does
+fn draw_sequence(env: &TestEnv, season_number: u64) -> (Vec<Pubkey>, Vec<Pubkey>) {
+    let season = env.season(season_number);
+    let seed = season.raffle_seed.unwrap();
+    let roster = env.roster(season_number);
+    let eligible = season.participant_count - season.disqualified_count;
+    let target = (season.payout_bps.len() as u64).min(eligible) as usize;
+    
+    let mut drawn = Vec::new();
+    let mut winners = Vec::new();
+    let mut attempt = 0;
+    while winners.len() < target {
+        let index = raffle::draw_index(&seed, attempt, season.next_participant_index);
+        let wallet = roster[index as usize];
+        let participant = env.participant(season_number, &wallet);
+        if participant.status == ParticipantStatus::Active && !winners.contains(&wallet) {
+            winners.push(wallet);
+        }
+        drawn.push(wallet);
+        attempt += 1;
+    }
+    
+    (drawn, winners)
+}
+
+#[test]
+fn raffle_winners_follow_the_revealed_seed() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let season_number = env.create_season(InitializeSeasonParams {
+        mode: SeasonMode::Raffle,
+        raffle_commitment: raffle::commitment(&SECRET),
+        ..season_params()
+    });
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 7);
+    
+    // A disqualified participant stays on the roster but can't be drawn
+    let disqualify = instructions::disqualify_participant(
+        &admin.pubkey(),
+        &env.season(season_number),
+        &players[3].pubkey(),
+        None,
+    )
+    .unwrap();
+    env.execute(&[disqualify], &[&admin]);
+    
+    let reveal_ix = |env: &TestEnv, secret: [u8; 32]| {
+        instructions::reveal_raffle(&admin.pubkey(), &env.season(season_number), secret)
+    };
+    let lock_ix = |env: &TestEnv| instructions::lock_raffle(&env.season(season_number));
+    env.expect_error(&[lock_ix(&env)], &[], GorbageError::RegistrationNotEnded);
+    env.expect_error(
+        &[reveal_ix(&env, SECRET)],
+        &[&admin],
+        GorbageError::RaffleNotLocked,
+    );
+    env.warp_to(REGISTRATION_END + 1);
+    
+    // The fee waits for the draw seed, so an unrevealed raffle refunds in full
+    let season = env.season(season_number);
+    let collect = instructions::collect_fee(&admin.pubkey(), &env.treasury.pubkey(), &season, None);
+    env.expect_error(&[collect.unwrap()], &[&admin], GorbageError::RaffleNotRevealed);
+    
+    // Anyone locks the raffle to a slot that hasn't been produced yet
+    env.warp_to_slot(100);
+    let locked: RaffleSlotLocked = event(&env.execute(&[lock_ix(&env)], &[]));
+    assert_eq!(locked.raffle_slot, 100 + RAFFLE_SLOT_DELAY);
+    assert_eq!(env.season(season_number).raffle_slot, 100 + RAFFLE_SLOT_DELAY);
+    env.expect_error(&[lock_ix(&env)], &[], GorbageError::RaffleAlreadyLocked);
+    env.expect_error(
+        &[reveal_ix(&env, SECRET)],
+        &[&admin],
+        GorbageError::RaffleSlotPending,
+    );
+    
+    reach_raffle_slot(&mut env, season_number);
+    env.expect_error(
+        &[reveal_ix(&env, [0; 32])],
+        &[&admin],
+        GorbageError::InvalidRaffleSecret,
+    );
+    
+    let revealed: RaffleRevealed = event(&env.execute(&[reveal_ix(&env, SECRET)], &[&admin]));
+    env.collect_fee(season_number);
+    let draw = instructions::draw_winners(&admin.pubkey(), &env.season(season_number), &[]);
+    env.expect_error(&[draw], &[&admin], GorbageError::SeasonNotEnded);
+    let season = env.season(season_number);
+    let season_key = pda::season(season_number).0;
+    assert_eq!(Some(revealed.raffle_seed), season.raffle_seed);
+    assert_eq!(season.raffle_secret, SECRET);
+    assert_eq!(season.raffle_slot_hash, SLOT_HASH);
+    assert_eq!(
+        season.raffle_seed,
+        Some(raffle::raffle_seed(&SECRET, &SLOT_HASH, &season_key))
+    );
+    env.expect_error(
+        &[reveal_ix(&env, SECRET)],
+        &[&admin],
+        GorbageError::RaffleAlreadyRevealed,
+    );
+    
+    env.warp_to(SEASON_END + 1);
+    let settle = env.settle_ix(season_number, &[&players[0]]);
+    env.expect_error(&[settle], &[&admin], GorbageError::ContestModeRequired);
+    
+    let (drawn, winners) = draw_sequence(&env, season_number);
+    assert_eq!(winners.len(), 3);
+    assert!(!winners.contains(&players[3].pubkey()));
+    let season = env.season(season_number);
+    let draw = |drawn: &[Pubkey]| instructions::draw_winners(&admin.pubkey(), &season, drawn);
+    
+    let mut too_many = drawn.clone();
+    too_many.push(drawn[0]);
+    env.expect_error(
+        &[draw(&too_many)],
+        &[&admin],
+        GorbageError::InvalidDrawSequence,
+    );
+    env.expect_error(
+        &[draw(&drawn[..drawn.len() - 1])],
+        &[&admin],
+        GorbageError::InvalidDrawSequence,
+    );
+    let mut wrong_order = drawn.clone();
+    wrong_order[0] = players
+        .iter()
+        .map(|player| player.pubkey())
+        .find(|wallet| *wallet != drawn[0])
+        .unwrap();
+    env.expect_error(
+        &[draw(&wrong_order)],
+        &[&admin],
+        GorbageError::InvalidDrawSequence,
+    );
+    let mut outsider = drawn.clone();
+    outsider[0] = env.wallet().pubkey();
+    env.expect_failure(&[draw(&outsider)], &[&admin]);
+    
+    env.execute(&[draw(&drawn)], &[&admin]);
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Settling);
+    assert_eq!(env.listed_phase(season_number), Some(SeasonPhase::Settling));
+    assert_eq!(season.winners, winners);
+    env.expect_error(&[draw(&drawn)], &[&admin], GorbageError::WinnersAlreadySet);
+    
+    // 6 entries less the 20% fee, paid 50/30/20
+    env.confirm_results(season_number);
+    let prizes = [12 * SOL / 5, 36 * SOL / 25, 24 * SOL / 25];
+    for (wallet, prize) in winners.iter().zip(prizes) {
+        let winner = players
+            .iter()
+            .find(|player| player.pubkey() == *wallet)
+            .unwrap();
+        let before = env.balance(wallet);
+        env.claim(season_number, winner);
+        assert_eq!(env.balance(wallet), before + prize);
+    }
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
+
+#[test]
+fn raffle_instructions_need_a_raffle_season() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, _players) = env.ended_season(2);
+    let season = env.season(season_number);
+    
+    let reveal = instructions::reveal_raffle(&admin.pubkey(), &season, SECRET);
+    env.expect_error(&[reveal], &[&admin], GorbageError::RaffleModeRequired);
+    let lock = instructions::lock_raffle(&season);
+    env.expect_error(&[lock], &[], GorbageError::RaffleModeRequired);
+    let draw = instructions::draw_winners(&admin.pubkey(), &season, &[]);
+    env.expect_error(&[draw], &[&admin], GorbageError::RaffleModeRequired);
+}
+
+#[test]
+fn locked_raffles_need_a_seed_and_keep_their_roster() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = raffle_season(&mut env, 2);
+    let disqualify = |env: &TestEnv| {
+        let season = env.season(season_number);
+        instructions::disqualify_participant(&admin.pubkey(), &season, &players[0].pubkey(), None)
+            .unwrap()
+    };
+    env.expect_error(&[disqualify(&env)], &[&admin], GorbageError::RaffleLocked);
+    
+    // Without a seed the fee can't be collected, so nothing can be drawn
+    env.warp_to(SEASON_END + 1);
+    let draw = instructions::draw_winners(&admin.pubkey(), &env.season(season_number), &[]);
+    env.expect_error(&[draw], &[&admin], GorbageError::FeeNotCollected);
+    
+    // Knowing the seed, the authority could otherwise disqualify its way to
+    // the winners it wants
+    reveal(&mut env, season_number);
+    env.expect_error(&[disqualify(&env)], &[&admin], GorbageError::RaffleLocked);
+    assert_eq!(
+        env.participant(season_number, &players[0].pubkey()).status,
+        ParticipantStatus::Active
+    );
+}
+
+#[test]
+fn anyone_cancels_a_raffle_revealed_too_late() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = raffle_season(&mut env, 2);
+    let outsider = env.wallet();
+    
+    let cancel = |env: &TestEnv| {
+        instructions::cancel_season(&outsider.pubkey(), &env.season(season_number), None, None)
+            .unwrap()
+    };
+    env.expect_error(&[cancel(&env)], &[&outsider], GorbageError::Unauthorized);
+    
+    // Past the window the committed slot has left SlotHashes and can't be revealed
+    let raffle_slot = env.season(season_number).raffle_slot;
+    env.warp_to_slot(raffle_slot + RAFFLE_REVEAL_WINDOW + 1);
+    let reveal = instructions::reveal_raffle(&admin.pubkey(), &env.season(season_number), SECRET);
+    env.expect_error(&[reveal], &[&admin], GorbageError::RaffleRevealExpired);
+    
+    env.execute(&[cancel(&env)], &[&outsider]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Cancelled);
+    for player in &players {
+        let before = env.balance(&player.pubkey());
+        let refund = instructions::claim_refund(&player.pubkey(), &env.season(season_number), None)
+            .unwrap();
+        env.execute(&[refund], &[player]);
+        assert_eq!(env.balance(&player.pubkey()), before + ENTRY_FEE);
+    }
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+}
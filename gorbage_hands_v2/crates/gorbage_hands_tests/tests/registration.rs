@@ -0,0 +1,420 @@
+//! Registration, the participant roster, withdrawals, disqualification and the waitlist
+
+use std::slice;
+
+use anchor_lang::prelude::AccountMeta;
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::constants::{
+    MAX_WAITLIST_LEN, PARTICIPANT_PAGE_SIZE, WAITLIST_CONFIRMATION_WINDOW,
+};
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::state::{
+    Participant, ParticipantPage, ParticipantStatus, SeasonPhase, Waitlist,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn capped_params(max_participants: u64) -> InitializeSeasonParams {
+    InitializeSeasonParams {
+        max_participants,
+        ..season_params()
+    }
+}
+
+fn waitlist(env: &TestEnv, season_number: u64) -> Waitlist {
+    env.fetch(&pda::waitlist(&pda::season(season_number).0).0)
+}
+
+fn register_ix(
+    env: &TestEnv,
+    season_number: u64,
+    owner: &Keypair,
+) -> solana_sdk::instruction::Instruction {
+    instructions::register_participant(&owner.pubkey(), &env.season(season_number), None).unwrap()
+}
+
+#[test]
+fn register_moves_the_entry_fee_into_the_vault() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(season_params());
+    env.warp_to(REGISTRATION_START);
+    let player = env.wallet();
+    
+    env.register(season_number, &player);
+    
+    // The roster page was opened by the authority, the player only pays for
+    // their own participant account
+    let participant_rent = env.rent(space::<Participant>());
+    assert_eq!(env.balance(&env.vault(season_number)), ENTRY_FEE);
+    assert_eq!(
+        env.balance(&player.pubkey()),
+        WALLET_FUNDING - ENTRY_FEE - participant_rent
+    );
+    
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Registration);
+    assert_eq!(season.prize_pool, ENTRY_FEE);
+    assert_eq!(season.participant_count, 1);
+    assert_eq!(season.next_participant_index, 1);
+    
+    let participant = env.participant(season_number, &player.pubkey());
+    assert_eq!(participant.owner, player.pubkey());
+    assert_eq!(participant.season, pda::season(season_number).0);
+    assert_eq!(participant.index, 0);
+    assert_eq!(participant.entry_fee_paid, ENTRY_FEE);
+    assert_eq!(participant.status, ParticipantStatus::Active);
+    assert_eq!(participant.registered_at, REGISTRATION_START);
+    
+    let second = env.wallet();
+    env.register(season_number, &second);
+    assert_eq!(
+        env.balance(&second.pubkey()),
+        WALLET_FUNDING - ENTRY_FEE - participant_rent
+    );
+    assert_eq!(env.balance(&env.vault(season_number)), 2 * ENTRY_FEE);
+    
+    // A wallet can only register once
+    let again = register_ix(&env, season_number, &player);
+    env.expect_failure(&[again], &[&player]);
+}
+
+#[test]
+fn roster_pages_keep_registration_order() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, mut players) = env.season_with_wallets(PARTICIPANT_PAGE_SIZE);
+    
+    // Registering past a page boundary opens the next page at the registrant's cost
+    let last_player = env.wallet();
+    env.register(season_number, &last_player);
+    assert_eq!(
+        env.balance(&last_player.pubkey()),
+        WALLET_FUNDING
+            - ENTRY_FEE
+            - env.rent(space::<Participant>())
+            - env.rent(space::<ParticipantPage>())
+    );
+    assert_eq!(
+        env.participant_page(season_number, 1).payer,
+        last_player.pubkey()
+    );
+    players.push(last_player);
+    
+    // The authority can open the page after the current one, but no further
+    let outsider = env.wallet();
+    let open = instructions::open_participant_page(&outsider.pubkey(), season_number, 2);
+    env.expect_error(&[open], &[&outsider], GorbageError::Unauthorized);
+    let open = instructions::open_participant_page(&admin.pubkey(), season_number, 3);
+    env.expect_error(&[open], &[&admin], GorbageError::InvalidParticipantPage);
+    let admin_before = env.balance(&admin.pubkey());
+    let open = instructions::open_participant_page(&admin.pubkey(), season_number, 2);
+    env.execute(slice::from_ref(&open), &[&admin]);
+    assert_eq!(
+        env.balance(&admin.pubkey()),
+        admin_before - env.rent(space::<ParticipantPage>())
+    );
+    assert_eq!(env.participant_page(season_number, 2).payer, admin.pubkey());
+    env.expect_failure(&[open], &[&admin]);
+    
+    let first_page = env.participant_page(season_number, 0);
+    assert_eq!(first_page.page, 0);
+    assert_eq!(first_page.payer, admin.pubkey());
+    assert_eq!(first_page.entries.len(), PARTICIPANT_PAGE_SIZE);
+    let last = players.last().unwrap().pubkey();
+    let second_page = env.participant_page(season_number, 1);
+    assert_eq!(second_page.page, 1);
+    assert_eq!(second_page.entries, vec![last]);
+    assert_eq!(
+        second_page.participant_index(0),
+        PARTICIPANT_PAGE_SIZE as u64
+    );
+    assert_eq!(
+        env.participant(season_number, &last).index,
+        PARTICIPANT_PAGE_SIZE as u64
+    );
+    
+    let wallets: Vec<_> = players.iter().map(|player| player.pubkey()).collect();
+    assert_eq!(env.roster(season_number), wallets);
+}
+
+#[test]
+fn registration_follows_the_clock() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(season_params());
+    let early = env.wallet();
+    
+    let register = register_ix(&env, season_number, &early);
+    env.expect_error(&[register], &[&early], GorbageError::RegistrationNotOpen);
+    
+    env.warp_to(REGISTRATION_START);
+    env.register_wallets(season_number, 2);
+    env.warp_to(REGISTRATION_END);
+    env.register(season_number, &early);
+    
+    let late = env.wallet();
+    env.warp_to(REGISTRATION_END + 1);
+    let register = register_ix(&env, season_number, &late);
+    env.expect_error(&[register], &[&late], GorbageError::RegistrationClosed);
+    
+    env.collect_fee(season_number);
+    let register = register_ix(&env, season_number, &late);
+    env.expect_error(&[register], &[&late], GorbageError::RegistrationClosed);
+}
+
+#[test]
+fn registration_below_minimum_cancels_the_season() {
+    let mut env = TestEnv::new();
+    let (season_number, _) = env.season_with_wallets(1);
+    env.warp_to(REGISTRATION_END + 1);
+    
+    let late = env.wallet();
+    let register = register_ix(&env, season_number, &late);
+    env.expect_error(&[register], &[&late], GorbageError::SeasonCancelled);
+}
+
+#[test]
+fn withdraw_refunds_the_entry_fee() {
+    let mut env = TestEnv::new();
+    let (season_number, players) = env.season_with_wallets(3);
+    let leaving = &players[1];
+    let balance_before = env.balance(&leaving.pubkey());
+    
+    let withdraw =
+        instructions::withdraw_participant(&leaving.pubkey(), &env.season(season_number), None)
+            .unwrap();
+    env.execute(slice::from_ref(&withdraw), &[leaving]);
+    
+    assert_eq!(env.balance(&leaving.pubkey()), balance_before + ENTRY_FEE);
+    assert_eq!(env.balance(&env.vault(season_number)), 2 * ENTRY_FEE);
+    let season = env.season(season_number);
+    assert_eq!(season.participant_count, 2);
+    assert_eq!(season.prize_pool, 2 * ENTRY_FEE);
+    // Indices stay stable, the roster still lists the withdrawn wallet
+    assert_eq!(season.next_participant_index, 3);
+    assert_eq!(env.roster(season_number)[1], leaving.pubkey());
+    let participant = env.participant(season_number, &leaving.pubkey());
+    assert_eq!(participant.status, ParticipantStatus::Withdrawn);
+    assert!(participant.refunded);
+    
+    env.expect_error(&[withdraw], &[leaving], GorbageError::ParticipantInactive);
+    
+    env.warp_to(REGISTRATION_END + 1);
+    let withdraw =
+        instructions::withdraw_participant(&players[0].pubkey(), &env.season(season_number), None)
+            .unwrap();
+    env.expect_error(
+        &[withdraw],
+        &[&players[0]],
+        GorbageError::RegistrationClosed,
+    );
+}
+
+#[test]
+fn disqualify_during_registration_refunds() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.season_with_wallets(3);
+    let cheater = players[2].pubkey();
+    let balance_before = env.balance(&cheater);
+    
+    let outsider = env.wallet();
+    let disqualify = instructions::disqualify_participant(
+        &outsider.pubkey(),
+        &env.season(season_number),
+        &cheater,
+        None,
+    )
+    .unwrap();
+    env.expect_error(&[disqualify], &[&outsider], GorbageError::Unauthorized);
+    
+    let disqualify = instructions::disqualify_participant(
+        &admin.pubkey(),
+        &env.season(season_number),
+        &cheater,
+        None,
+    )
+    .unwrap();
+    env.execute(slice::from_ref(&disqualify), &[&admin]);
+    
+    assert_eq!(env.balance(&cheater), balance_before + ENTRY_FEE);
+    assert_eq!(env.balance(&env.vault(season_number)), 2 * ENTRY_FEE);
+    let season = env.season(season_number);
+    assert_eq!(season.participant_count, 2);
+    assert_eq!(season.disqualified_count, 0);
+    let participant = env.participant(season_number, &cheater);
+    assert_eq!(participant.status, ParticipantStatus::Disqualified);
+    assert!(participant.refunded);
+    
+    env.expect_error(&[disqualify], &[&admin], GorbageError::ParticipantInactive);
+}
+
+#[test]
+fn disqualify_after_registration_keeps_the_entry_fee() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.ended_season(3);
+    let cheater = players[0].pubkey();
+    let vault_before = env.balance(&env.vault(season_number));
+    let balance_before = env.balance(&cheater);
+    
+    let disqualify = instructions::disqualify_participant(
+        &admin.pubkey(),
+        &env.season(season_number),
+        &cheater,
+        None,
+    )
+    .unwrap();
+    env.execute(&[disqualify], &[&admin]);
+    
+    assert_eq!(env.balance(&cheater), balance_before);
+    assert_eq!(env.balance(&env.vault(season_number)), vault_before);
+    let season = env.season(season_number);
+    assert_eq!(season.participant_count, 3);
+    assert_eq!(season.disqualified_count, 1);
+    assert!(!env.participant(season_number, &cheater).refunded);
+    
+    // Disqualified participants cannot win
+    let settle = env.settle_ix(season_number, &[&players[0]]);
+    env.expect_error(&[settle], &[&admin], GorbageError::ParticipantInactive);
+    
+    env.settle(season_number, &[&players[1]]);
+    let disqualify = instructions::disqualify_participant(
+        &admin.pubkey(),
+        &env.season(season_number),
+        &players[2].pubkey(),
+        None,
+    )
+    .unwrap();
+    env.expect_error(&[disqualify], &[&admin], GorbageError::WinnersAlreadySet);
+}
+
+#[test]
+fn full_season_queues_sign_ups_on_the_waitlist() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(capped_params(2));
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 2);
+    let first_in_line = env.wallet();
+    let second_in_line = env.wallet();
+    
+    let register = register_ix(&env, season_number, &first_in_line);
+    env.expect_error(&[register], &[&first_in_line], GorbageError::SeasonFull);
+    
+    let join = instructions::join_waitlist(&players[0].pubkey(), &env.season(season_number));
+    env.expect_error(&[join], &[&players[0]], GorbageError::AlreadyRegistered);
+    
+    for wallet in [&first_in_line, &second_in_line] {
+        let join = instructions::join_waitlist(&wallet.pubkey(), &env.season(season_number));
+        env.execute(&[join], &[wallet]);
+    }
+    let join = instructions::join_waitlist(&first_in_line.pubkey(), &env.season(season_number));
+    env.expect_error(&[join], &[&first_in_line], GorbageError::AlreadyOnWaitlist);
+    assert_eq!(
+        waitlist(&env, season_number).queue,
+        vec![first_in_line.pubkey(), second_in_line.pubkey()]
+    );
+    
+    // A withdrawal offers the freed spot to the head of the queue
+    let withdraw =
+        instructions::withdraw_participant(&players[0].pubkey(), &env.season(season_number), None)
+            .unwrap();
+    env.execute(&[withdraw], &[&players[0]]);
+    let offers = waitlist(&env, season_number).offers;
+    assert_eq!(offers.len(), 1);
+    assert_eq!(offers[0].wallet, first_in_line.pubkey());
+    assert_eq!(
+        offers[0].expires_at,
+        REGISTRATION_START + WAITLIST_CONFIRMATION_WINDOW
+    );
+    
+    // The held spot is not open to anyone else
+    let register = register_ix(&env, season_number, &second_in_line);
+    env.expect_error(&[register], &[&second_in_line], GorbageError::SeasonFull);
+    env.register(season_number, &first_in_line);
+    
+    let waitlist = waitlist(&env, season_number);
+    assert!(waitlist.offers.is_empty());
+    assert_eq!(waitlist.queue, vec![second_in_line.pubkey()]);
+    assert_eq!(env.season(season_number).participant_count, 2);
+}
+
+#[test]
+fn lapsed_waitlist_offer_moves_on() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(InitializeSeasonParams {
+        registration_end: REGISTRATION_START + 3 * WAITLIST_CONFIRMATION_WINDOW,
+        season_end: REGISTRATION_START + 4 * WAITLIST_CONFIRMATION_WINDOW,
+        claim_deadline: REGISTRATION_START + 5 * WAITLIST_CONFIRMATION_WINDOW,
+        ..capped_params(2)
+    });
+    env.warp_to(REGISTRATION_START);
+    let players = env.register_wallets(season_number, 2);
+    let slow = env.wallet();
+    let quick = env.wallet();
+    for wallet in [&slow, &quick] {
+        let join = instructions::join_waitlist(&wallet.pubkey(), &env.season(season_number));
+        env.execute(&[join], &[wallet]);
+    }
+    
+    let disqualify = instructions::disqualify_participant(
+        &env.admin.pubkey(),
+        &env.season(season_number),
+        &players[1].pubkey(),
+        None,
+    )
+    .unwrap();
+    let admin = env.admin.insecure_clone();
+    env.execute(&[disqualify], &[&admin]);
+    assert_eq!(
+        waitlist(&env, season_number).offers[0].wallet,
+        slow.pubkey()
+    );
+    
+    // Once the offer lapses the next registration attempt passes it on
+    env.warp_to(REGISTRATION_START + WAITLIST_CONFIRMATION_WINDOW + 1);
+    env.register(season_number, &quick);
+    let register = register_ix(&env, season_number, &slow);
+    env.expect_error(&[register], &[&slow], GorbageError::SeasonFull);
+    
+    let waitlist = waitlist(&env, season_number);
+    assert!(waitlist.queue.is_empty());
+    assert!(waitlist.offers.is_empty());
+}
+
+#[test]
+fn waitlist_only_takes_sign_ups_for_full_seasons() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(capped_params(2));
+    env.warp_to(REGISTRATION_START);
+    env.register_wallets(season_number, 1);
+    
+    let early = env.wallet();
+    let join = instructions::join_waitlist(&early.pubkey(), &env.season(season_number));
+    env.expect_error(&[join], &[&early], GorbageError::SpotsAvailable);
+    
+    env.register_wallets(season_number, 1);
+    for _ in 0..MAX_WAITLIST_LEN {
+        let wallet = env.wallet();
+        let join = instructions::join_waitlist(&wallet.pubkey(), &env.season(season_number));
+        env.execute(&[join], &[&wallet]);
+    }
+    let join = instructions::join_waitlist(&early.pubkey(), &env.season(season_number));
+    env.expect_error(&[join], &[&early], GorbageError::WaitlistFull);
+    
+    env.warp_to(REGISTRATION_END + 1);
+    let join = instructions::join_waitlist(&early.pubkey(), &env.season(season_number));
+    env.expect_error(&[join], &[&early], GorbageError::RegistrationClosed);
+}
+
+#[test]
+fn capped_registration_requires_the_waitlist() {
+    let mut env = TestEnv::new();
+    let season_number = env.create_season(capped_params(2));
+    env.warp_to(REGISTRATION_START);
+    let player = env.wallet();
+    
+    let mut register = register_ix(&env, season_number, &player);
+    // Omitted optional accounts are passed as the program id
+    register.accounts[5] = AccountMeta::new_readonly(gorbage_hands_v2::ID, false);
+    env.expect_error(&[register], &[&player], GorbageError::WaitlistRequired);
+}
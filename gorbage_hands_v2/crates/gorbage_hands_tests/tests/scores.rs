@@ -0,0 +1,319 @@
+//! Game server scores, batched ranking and score-ranked settlement, and
+//! Ed25519-attested scores
+
+use std::slice;
+
+use anchor_lang::prelude::Pubkey;
+use gorbage_hands_client::{instructions, pda};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::events::ScoreSubmitted;
+use gorbage_hands_v2::instructions::score_attestation_message;
+use gorbage_hands_v2::state::SeasonPhase;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: u64 = LAMPORTS_PER_SOL;
+
+fn register_game_server(env: &mut TestEnv) -> Keypair {
+    let admin = env.admin.insecure_clone();
+    let game_server = Keypair::new();
+    env.execute(
+        &[instructions::set_game_server(&admin.pubkey(), &game_server.pubkey())],
+        &[&admin],
+    );
+    game_server
+}
+
+/// Submit `score` under the participant's next score nonce
+fn submit_score(
+    env: &mut TestEnv,
+    game_server: &Keypair,
+    season_number: u64,
+    owner: &Keypair,
+    score: u64,
+) {
+    let nonce = env.participant(season_number, &owner.pubkey()).score_nonce + 1;
+    let submit = instructions::submit_score(
+        &game_server.pubkey(),
+        &env.season(season_number),
+        &owner.pubkey(),
+        score,
+        nonce,
+    );
+    env.execute(&[submit], &[game_server]);
+}
+
+/// Ed25519 precompile instruction with `signer`'s signature over the attestation
+/// message, followed by the score submission
+fn attested_score(
+    env: &TestEnv,
+    signer: &Keypair,
+    season_number: u64,
+    owner: &Keypair,
+    score: u64,
+    nonce: u64,
+) -> [Instruction; 2] {
+    let season_key = pda::season(season_number).0;
+    let participant = pda::participant(&season_key, &owner.pubkey()).0;
+    let message = score_attestation_message(&season_key, &participant, score, nonce);
+    let signature = signer.sign_message(&message);
+    let verify = solana_ed25519_program::new_ed25519_instruction_with_signature(
+        &message,
+        signature.as_ref().try_into().unwrap(),
+        &signer.pubkey().to_bytes(),
+    );
+    let submit = instructions::submit_attested_score(
+        &owner.pubkey(),
+        &env.season(season_number),
+        score,
+        nonce,
+    );
+    
+    [verify, submit]
+}
+
+#[test]
+fn game_server_submits_scores_while_active() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let (season_number, players) = env.season_with_wallets(3);
+    let player = players[0].pubkey();
+    
+    let submit =
+        instructions::submit_score(&admin.pubkey(), &env.season(season_number), &player, 10, 1);
+    env.expect_error(&[submit], &[&admin], GorbageError::GameServerNotSet);
+    
+    let game_server = register_game_server(&mut env);
+    let submit = instructions::submit_score(
+        &game_server.pubkey(),
+        &env.season(season_number),
+        &player,
+        10,
+        1,
+    );
+    env.expect_error(&[submit], &[&game_server], GorbageError::FeeNotCollected);
+    
+    env.warp_to(REGISTRATION_END + 1);
+    env.collect_fee(season_number);
+    let impostor = Keypair::new();
+    let submit =
+        instructions::submit_score(&impostor.pubkey(), &env.season(season_number), &player, 10, 1);
+    env.expect_error(&[submit], &[&impostor], GorbageError::Unauthorized);
+    
+    submit_score(&mut env, &game_server, season_number, &players[0], 10);
+    let participant = env.participant(season_number, &player);
+    assert_eq!(participant.score, 10);
+    assert_eq!(participant.score_submitted_at, REGISTRATION_END + 1);
+    assert_eq!(env.season(season_number).scored_count, 1);
+    
+    // A later score replaces the earlier one
+    env.warp_to(REGISTRATION_END + 50);
+    submit_score(&mut env, &game_server, season_number, &players[0], 25);
+    let participant = env.participant(season_number, &player);
+    assert_eq!(participant.score, 25);
+    assert_eq!(participant.score_submitted_at, REGISTRATION_END + 50);
+    assert_eq!(env.season(season_number).scored_count, 1);
+    
+    // Disqualification takes the participant off the scoreboard
+    let disqualify = instructions::disqualify_participant(
+        &admin.pubkey(),
+        &env.season(season_number),
+        &player,
+        None,
+    )
+    .unwrap();
+    env.execute(&[disqualify], &[&admin]);
+    assert_eq!(env.season(season_number).scored_count, 0);
+    let submit = instructions::submit_score(
+        &game_server.pubkey(),
+        &env.season(season_number),
+        &player,
+        30,
+        3,
+    );
+    env.expect_error(
+        &[submit],
+        &[&game_server],
+        GorbageError::ParticipantInactive,
+    );
+    
+    // Scores stop once the season ends, even before it is settled
+    env.warp_to(SEASON_END + 1);
+    let submit = instructions::submit_score(
+        &game_server.pubkey(),
+        &env.season(season_number),
+        &players[2].pubkey(),
+        5,
+        1,
+    );
+    env.expect_error(
+        slice::from_ref(&submit),
+        &[&game_server],
+        GorbageError::SeasonEnded,
+    );
+    let attested = attested_score(&env, &game_server, season_number, &players[2], 5, 1);
+    env.expect_error(&attested, &[&players[2]], GorbageError::SeasonEnded);
+    
+    env.settle(season_number, &[&players[1]]);
+    env.expect_error(&[submit], &[&game_server], GorbageError::WinnersAlreadySet);
+}
+
+#[test]
+fn settle_ranks_by_stored_scores() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let game_server = register_game_server(&mut env);
+    let (season_number, players) = env.active_season(5);
+    
+    // players[1] and players[2] tie, players[1] scored first
+    env.warp_to(SEASON_END - 100);
+    for (index, score) in [(0, 10), (1, 40), (3, 20)] {
+        submit_score(
+            &mut env,
+            &game_server,
+            season_number,
+            &players[index],
+            score,
+        );
+    }
+    env.warp_to(SEASON_END - 50);
+    submit_score(&mut env, &game_server, season_number, &players[2], 40);
+    env.warp_to(SEASON_END + 1);
+    
+    let scored: Vec<_> = players[..4].iter().map(|player| player.pubkey()).collect();
+    let rank = |env: &TestEnv, owners: &[Pubkey]| {
+        instructions::rank_scores(&admin.pubkey(), &env.season(season_number), owners)
+    };
+    
+    let unscored = players[4].pubkey();
+    env.expect_error(&[rank(&env, &[unscored])], &[&admin], GorbageError::ScoreMissing);
+    
+    // The scoreboard is ranked over as many transactions as it takes
+    env.execute(&[rank(&env, &scored[..2])], &[&admin]);
+    env.expect_error(&[rank(&env, &scored[1..3])], &[&admin], GorbageError::AlreadyRanked);
+    let settle = instructions::settle_season_by_score(&admin.pubkey(), &env.season(season_number));
+    env.expect_error(&[settle], &[&admin], GorbageError::IncompleteScoreboard);
+    env.execute(&[rank(&env, &scored[2..])], &[&admin]);
+    
+    let season = env.season(season_number);
+    assert_eq!(season.ranked_count, 4);
+    let out_of_order = [scored[2], scored[1], scored[3]];
+    let settle = instructions::settle_season(&admin.pubkey(), &season, &out_of_order, true);
+    env.expect_error(&[settle], &[&admin], GorbageError::LeaderboardMismatch);
+    
+    // Late scores are refused, disqualifying a ranked player starts the ranking over
+    let late = instructions::submit_score(
+        &game_server.pubkey(),
+        &env.season(season_number),
+        &scored[0],
+        50,
+        2,
+    );
+    env.expect_error(&[late], &[&game_server], GorbageError::SeasonEnded);
+    let disqualify = instructions::disqualify_participant(
+        &admin.pubkey(),
+        &env.season(season_number),
+        &scored[0],
+        None,
+    )
+    .unwrap();
+    env.execute(&[disqualify], &[&admin]);
+    let season = env.season(season_number);
+    assert_eq!((season.ranking_round, season.ranked_count), (2, 0));
+    assert!(season.leaderboard.is_empty());
+    env.execute(&[rank(&env, &scored[1..])], &[&admin]);
+    
+    let settle = instructions::settle_season_by_score(&admin.pubkey(), &env.season(season_number));
+    env.execute(&[settle], &[&admin]);
+    
+    let season = env.season(season_number);
+    assert_eq!(season.phase, SeasonPhase::Settling);
+    assert_eq!(season.winners, vec![scored[1], scored[2], scored[3]]);
+    for (index, prize) in [(1, 2 * SOL), (2, 6 * SOL / 5), (3, 4 * SOL / 5)] {
+        let participant = env.participant(season_number, &scored[index]);
+        assert_eq!(participant.placement as usize, index);
+        assert_eq!(participant.prize_amount, prize);
+    }
+    assert_eq!(env.participant(season_number, &scored[0]).placement, 0);
+}
+
+#[test]
+fn players_submit_scores_attested_by_the_game_server() {
+    let mut env = TestEnv::new();
+    let (season_number, players) = env.active_season(2);
+    let player = &players[0];
+    let impostor = Keypair::new();
+    
+    let submit = attested_score(&env, &impostor, season_number, player, 10, 1);
+    env.expect_error(&submit, &[player], GorbageError::GameServerNotSet);
+    
+    let game_server = register_game_server(&mut env);
+    
+    // The precompile instruction must come right before the submission
+    let [_, submit] = attested_score(&env, &game_server, season_number, player, 10, 1);
+    env.expect_error(&[submit], &[player], GorbageError::InvalidScoreAttestation);
+    
+    // Signed by someone other than the registered game server
+    let submit = attested_score(&env, &impostor, season_number, player, 10, 1);
+    env.expect_error(&submit, &[player], GorbageError::InvalidScoreAttestation);
+    
+    // Signature over a different score
+    let [verify, _] = attested_score(&env, &game_server, season_number, player, 10, 1);
+    let [_, submit] = attested_score(&env, &game_server, season_number, player, 99, 1);
+    env.expect_error(
+        &[verify, submit],
+        &[player],
+        GorbageError::InvalidScoreAttestation,
+    );
+    
+    let submit = attested_score(&env, &game_server, season_number, player, 10, 1);
+    let submitted: ScoreSubmitted = event(&env.execute(&submit, &[player]));
+    assert_eq!((submitted.score, submitted.nonce), (10, 1));
+    assert!(submitted.attested);
+    let participant = env.participant(season_number, &player.pubkey());
+    assert_eq!(participant.score, 10);
+    assert_eq!(participant.score_nonce, 1);
+    assert_eq!(env.season(season_number).scored_count, 1);
+    
+    // Nonces only move forward
+    env.expect_error(&submit, &[player], GorbageError::StaleScoreNonce);
+    let submit = attested_score(&env, &game_server, season_number, player, 15, 5);
+    env.execute(&submit, &[player]);
+    let submit = attested_score(&env, &game_server, season_number, player, 20, 4);
+    env.expect_error(&submit, &[player], GorbageError::StaleScoreNonce);
+    
+    let participant = env.participant(season_number, &player.pubkey());
+    assert_eq!(participant.score, 15);
+    assert_eq!(participant.score_nonce, 5);
+}
+
+#[test]
+fn server_scores_retire_earlier_attestations() {
+    let mut env = TestEnv::new();
+    let game_server = register_game_server(&mut env);
+    let (season_number, players) = env.active_season(2);
+    let player = &players[0];
+    
+    // The player holds on to an attestation, then the server corrects the score
+    let attestation = attested_score(&env, &game_server, season_number, player, 100, 1);
+    submit_score(&mut env, &game_server, season_number, player, 10);
+    assert_eq!(env.participant(season_number, &player.pubkey()).score_nonce, 1);
+    env.expect_error(&attestation, &[player], GorbageError::StaleScoreNonce);
+    
+    // Server writes follow the same sequence
+    let stale = instructions::submit_score(
+        &game_server.pubkey(),
+        &env.season(season_number),
+        &player.pubkey(),
+        20,
+        1,
+    );
+    env.expect_error(&[stale], &[&game_server], GorbageError::StaleScoreNonce);
+    let attestation = attested_score(&env, &game_server, season_number, player, 30, 2);
+    env.execute(&attestation, &[player]);
+    
+    let participant = env.participant(season_number, &player.pubkey());
+    assert_eq!((participant.score, participant.score_nonce), (30, 2));
+}
@@ -0,0 +1,235 @@
+//! Season creation, numbering and the season registry
+
+use anchor_lang::prelude::AccountMeta;
+use gorbage_hands_client::{instructions, pda, InitializeSeasonParams};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::constants::{MAX_LISTED_SEASONS, MAX_WINNERS};
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::state::{
+    ProgramConfig, SeasonMode, SeasonPhase, SeasonRegistry, UnclaimedPrizePolicy, Waitlist,
+};
+use solana_sdk::signature::Signer;
+
+fn expect_invalid(env: &mut TestEnv, params: InitializeSeasonParams, expected: GorbageError) {
+    let admin = env.admin.insecure_clone();
+    let season_number = env.next_season_number();
+    let create =
+        instructions::initialize_season(&admin.pubkey(), season_number, params, None, None)
+            .unwrap();
+    env.expect_error(&[create], &[&admin], expected);
+}
+
+#[test]
+fn seasons_are_numbered_and_listed() {
+    let mut env = TestEnv::new();
+    
+    let first = env.create_season(season_params());
+    let second = env.create_season(InitializeSeasonParams {
+        max_participants: 10,
+        ..season_params()
+    });
+    assert_eq!((first, second), (1, 2));
+    let config: ProgramConfig = env.fetch(&pda::config().0);
+    assert_eq!(config.next_season_number, 3);
+    
+    let season = env.season(first);
+    assert_eq!(season.authority, env.admin.pubkey());
+    assert_eq!(season.phase, SeasonPhase::Scheduled);
+    assert_eq!(season.entry_fee, ENTRY_FEE);
+    assert_eq!(season.payout_bps, vec![5000, 3000, 2000]);
+    assert_eq!(season.prize_pool, 0);
+    assert_eq!(env.balance(&env.vault(first)), 0);
+    
+    // Only capped seasons get a waitlist
+    assert!(!env.exists(&pda::waitlist(&pda::season(first).0).0));
+    let waitlist: Waitlist = env.fetch(&pda::waitlist(&pda::season(second).0).0);
+    assert!(waitlist.queue.is_empty());
+    
+    let season_registry: SeasonRegistry = env.fetch(&pda::season_registry().0);
+    let listed: Vec<(u64, SeasonPhase)> = season_registry
+        .seasons
+        .iter()
+        .map(|entry| (entry.season_number, entry.phase))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![(1, SeasonPhase::Scheduled), (2, SeasonPhase::Scheduled)]
+    );
+    assert_eq!(season_registry.seasons[0].season, pda::season(1).0);
+}
+
+#[test]
+fn season_created_inside_registration_opens_immediately() {
+    let mut env = TestEnv::new();
+    env.warp_to(REGISTRATION_START + 10);
+    let season_number = env.create_season(season_params());
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Registration);
+}
+
+#[test]
+fn only_the_admin_creates_seasons() {
+    let mut env = TestEnv::new();
+    let outsider = env.wallet();
+    let create =
+        instructions::initialize_season(&outsider.pubkey(), 1, season_params(), None, None)
+            .unwrap();
+    env.expect_error(&[create], &[&outsider], GorbageError::Unauthorized);
+}
+
+#[test]
+fn initialize_season_validates_params() {
+    let mut env = TestEnv::new();
+    let cases = [
+        (
+            InitializeSeasonParams {
+                name: "x".repeat(33),
+                ..season_params()
+            },
+            GorbageError::SeasonNameTooLong,
+        ),
+        (
+            InitializeSeasonParams {
+                entry_fee: 0,
+                ..season_params()
+            },
+            GorbageError::InvalidEntryFee,
+        ),
+        (
+            InitializeSeasonParams {
+                registration_end: REGISTRATION_START,
+                ..season_params()
+            },
+            GorbageError::InvalidEntryFee,
+        ),
+        (
+            InitializeSeasonParams {
+                season_end: REGISTRATION_END,
+                ..season_params()
+            },
+            GorbageError::InvalidEntryFee,
+        ),
+        (
+            InitializeSeasonParams {
+                challenge_window: -1,
+                ..season_params()
+            },
+            GorbageError::InvalidChallengeWindow,
+        ),
+        (
+            InitializeSeasonParams {
+                dispute_bond: 0,
+                ..season_params()
+            },
+            GorbageError::InvalidDisputeBond,
+        ),
+        (
+            InitializeSeasonParams {
+                claim_deadline: SEASON_END + CHALLENGE_WINDOW,
+                ..season_params()
+            },
+            GorbageError::InvalidClaimDeadline,
+        ),
+        (
+            InitializeSeasonParams {
+                unclaimed_policy: UnclaimedPrizePolicy::Rollover { season_number: 1 },
+                ..season_params()
+            },
+            GorbageError::InvalidRolloverSeason,
+        ),
+        (
+            InitializeSeasonParams {
+                mode: SeasonMode::Raffle,
+                ..season_params()
+            },
+            GorbageError::InvalidRaffleCommitment,
+        ),
+        (
+            InitializeSeasonParams {
+                payout_bps: Vec::new(),
+                ..season_params()
+            },
+            GorbageError::InvalidPayoutSchedule,
+        ),
+        (
+            InitializeSeasonParams {
+                payout_bps: vec![1000; MAX_WINNERS + 1],
+                ..season_params()
+            },
+            GorbageError::InvalidPayoutSchedule,
+        ),
+        (
+            InitializeSeasonParams {
+                payout_bps: vec![10000, 0],
+                ..season_params()
+            },
+            GorbageError::InvalidPayoutSchedule,
+        ),
+        (
+            InitializeSeasonParams {
+                payout_bps: vec![5000, 3000],
+                ..season_params()
+            },
+            GorbageError::PayoutScheduleSumMismatch,
+        ),
+        (
+            InitializeSeasonParams {
+                min_participants: 5,
+                max_participants: 4,
+                ..season_params()
+            },
+            GorbageError::InvalidParticipantLimits,
+        ),
+    ];
+    for (params, expected) in cases {
+        expect_invalid(&mut env, params, expected);
+    }
+    
+    // A flat top-10 schedule is fine
+    env.create_season(InitializeSeasonParams {
+        payout_bps: vec![1000; MAX_WINNERS],
+        ..season_params()
+    });
+}
+
+#[test]
+fn capped_season_requires_a_waitlist() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let params = InitializeSeasonParams {
+        max_participants: 4,
+        ..season_params()
+    };
+    let mut create =
+        instructions::initialize_season(&admin.pubkey(), 1, params, None, None).unwrap();
+    // Omitted optional accounts are passed as the program id
+    create.accounts[5] = AccountMeta::new_readonly(gorbage_hands_v2::ID, false);
+    env.expect_error(&[create], &[&admin], GorbageError::WaitlistRequired);
+}
+
+#[test]
+fn full_registry_leaves_new_seasons_unlisted_until_stale_entries_drop() {
+    let mut env = TestEnv::new();
+    for _ in 0..MAX_LISTED_SEASONS {
+        env.create_season(season_params());
+    }
+    
+    // A full registry doesn't stop the season being created
+    let unlisted = env.create_season(season_params());
+    assert_eq!(env.season(unlisted).phase, SeasonPhase::Scheduled);
+    assert_eq!(env.listed_phase(unlisted), None);
+    
+    // Nobody registered, so every listed season was cancelled when registration
+    // closed and the next update prunes them
+    env.warp_to(REGISTRATION_END + 1);
+    let offset = REGISTRATION_END;
+    let later = env.create_season(InitializeSeasonParams {
+        registration_start: REGISTRATION_START + offset,
+        registration_end: REGISTRATION_END + offset,
+        season_end: SEASON_END + offset,
+        claim_deadline: CLAIM_DEADLINE + offset,
+        ..season_params()
+    });
+    let season_registry: SeasonRegistry = env.fetch(&pda::season_registry().0);
+    assert_eq!(season_registry.seasons.len(), 1);
+    assert_eq!(env.listed_phase(later), Some(SeasonPhase::Scheduled));
+}
@@ -0,0 +1,250 @@
+//! Token-priced seasons on SPL Token and Token-2022, with exact token balances
+//! and the checks on the token accounts passed in
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id, spl_associated_token_account,
+};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use gorbage_hands_client::{instructions, pda};
+use gorbage_hands_tests::*;
+use gorbage_hands_v2::error::GorbageError;
+use gorbage_hands_v2::state::{SeasonPhase, SeasonRegistry};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::instruction as system_instruction;
+
+/// Entry fee of one token at 9 decimals
+const TOKEN: u64 = LAMPORTS_PER_SOL;
+const DECIMALS: u8 = 9;
+
+/// Byte offset of `amount` in SPL Token and Token-2022 accounts
+const AMOUNT_OFFSET: usize = 64;
+
+struct TokenSetup {
+    mint: Pubkey,
+    token_program: Pubkey,
+}
+
+impl TokenSetup {
+    /// A new mint with the admin as mint authority
+    fn new(env: &mut TestEnv, token_program: Pubkey) -> Self {
+        let mint = Keypair::new();
+        let space = spl_token::state::Mint::LEN;
+        let create = system_instruction::create_account(
+            &env.payer.pubkey(),
+            &mint.pubkey(),
+            env.rent(space),
+            space as u64,
+            &token_program,
+        );
+        let initialize = spl_token_2022::instruction::initialize_mint2(
+            &token_program,
+            &mint.pubkey(),
+            &env.admin.pubkey(),
+            None,
+            DECIMALS,
+        )
+        .unwrap();
+        env.execute(&[create, initialize], &[&mint]);
+        
+        Self {
+            mint: mint.pubkey(),
+            token_program,
+        }
+    }
+    
+    fn account_of(&self, wallet: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(wallet, &self.mint, &self.token_program)
+    }
+    
+    /// Create the associated token account of `wallet` holding `amount`
+    fn fund(&self, env: &mut TestEnv, wallet: &Pubkey, amount: u64) -> Pubkey {
+        let create = spl_associated_token_account::instruction::create_associated_token_account(
+            &env.payer.pubkey(),
+            wallet,
+            &self.mint,
+            &self.token_program,
+        );
+        let admin = env.admin.insecure_clone();
+        let mut ixs = vec![create];
+        let mut signers = Vec::new();
+        if amount > 0 {
+            let mint_to = spl_token_2022::instruction::mint_to(
+                &self.token_program,
+                &self.mint,
+                &self.account_of(wallet),
+                &env.admin.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap();
+            ixs.push(mint_to);
+            signers.push(&admin);
+        }
+        env.execute(&ixs, &signers);
+        
+        self.account_of(wallet)
+    }
+}
+
+fn token_balance(env: &TestEnv, token_account: &Pubkey) -> u64 {
+    let account = env.svm.get_account(token_account).unwrap();
+    u64::from_le_bytes(
+        account.data[AMOUNT_OFFSET..AMOUNT_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+fn token_season_lifecycle(token_program: Pubkey) {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let treasury = env.treasury.pubkey();
+    let token = TokenSetup::new(&mut env, token_program);
+    let other_mint = TokenSetup::new(&mut env, token_program);
+    token.fund(&mut env, &admin.pubkey(), 0);
+    let treasury_tokens = token.fund(&mut env, &treasury, 0);
+    
+    // The mint needs the vault token account and vice versa
+    let season_number = env.next_season_number();
+    let create = |mint: Option<Pubkey>| {
+        instructions::initialize_season(
+            &admin.pubkey(),
+            season_number,
+            season_params(),
+            mint,
+            Some(token_program),
+        )
+        .unwrap()
+    };
+    let mut without_vault_tokens = create(Some(token.mint));
+    without_vault_tokens.accounts[7] = AccountMeta::new_readonly(gorbage_hands_v2::ID, false);
+    env.expect_error(
+        &[without_vault_tokens],
+        &[&admin],
+        GorbageError::TokenAccountsRequired,
+    );
+    let first_page = instructions::open_participant_page(&admin.pubkey(), season_number, 0);
+    env.execute(&[create(Some(token.mint)), first_page], &[&admin]);
+    
+    let season = env.season(season_number);
+    let vault_tokens = token.account_of(&env.vault(season_number));
+    assert_eq!(season.mint, Some(token.mint));
+    assert_eq!(season.vault_token_account, vault_tokens);
+    
+    // Entry fees are paid in tokens, lamports only cover rent
+    env.warp_to(REGISTRATION_START);
+    let players: Vec<Keypair> = (0..4).map(|_| env.wallet()).collect();
+    for player in &players {
+        token.fund(&mut env, &player.pubkey(), 10 * TOKEN);
+    }
+    let register = |env: &TestEnv, player: &Keypair| {
+        let season = env.season(season_number);
+        instructions::register_participant(&player.pubkey(), &season, Some(token_program)).unwrap()
+    };
+    
+    let mut without_mint = register(&env, &players[0]);
+    without_mint.accounts[6] = AccountMeta::new_readonly(gorbage_hands_v2::ID, false);
+    env.expect_error(
+        &[without_mint],
+        &[&players[0]],
+        GorbageError::TokenAccountsRequired,
+    );
+    let mut wrong_mint = register(&env, &players[0]);
+    wrong_mint.accounts[6].pubkey = other_mint.mint;
+    env.expect_error(&[wrong_mint], &[&players[0]], GorbageError::InvalidMint);
+    let mut wrong_vault = register(&env, &players[0]);
+    wrong_vault.accounts[7].pubkey = token.account_of(&players[1].pubkey());
+    env.expect_error(
+        &[wrong_vault],
+        &[&players[0]],
+        GorbageError::InvalidTokenAccount,
+    );
+    let mut wrong_program = register(&env, &players[0]);
+    wrong_program.accounts[9].pubkey = if token_program == spl_token::ID {
+        spl_token_2022::ID
+    } else {
+        spl_token::ID
+    };
+    env.expect_error(
+        &[wrong_program],
+        &[&players[0]],
+        GorbageError::InvalidTokenProgram,
+    );
+    
+    for player in &players {
+        let register = register(&env, player);
+        env.execute(&[register], &[player]);
+        assert_eq!(
+            token_balance(&env, &token.account_of(&player.pubkey())),
+            9 * TOKEN
+        );
+    }
+    assert_eq!(token_balance(&env, &vault_tokens), 4 * TOKEN);
+    assert_eq!(env.balance(&env.vault(season_number)), 0);
+    
+    // 20% fee to the treasury token account
+    env.warp_to(REGISTRATION_END + 1);
+    let collect = instructions::collect_fee(
+        &admin.pubkey(),
+        &treasury,
+        &env.season(season_number),
+        Some(token_program),
+    )
+    .unwrap();
+    env.execute(&[collect], &[&admin]);
+    assert_eq!(token_balance(&env, &treasury_tokens), 4 * TOKEN / 5);
+    assert_eq!(token_balance(&env, &vault_tokens), 16 * TOKEN / 5);
+    
+    env.warp_to(SEASON_END + 1);
+    env.settle(season_number, &[&players[2], &players[0], &players[3]]);
+    env.confirm_results(season_number);
+    
+    let claim = |env: &TestEnv, winner: &Pubkey| {
+        instructions::claim_prize(winner, &env.season(season_number), Some(token_program)).unwrap()
+    };
+    let mut wrong_destination = claim(&env, &players[2].pubkey());
+    wrong_destination.accounts[6].pubkey = token.account_of(&players[1].pubkey());
+    env.expect_error(
+        &[wrong_destination],
+        &[&players[2]],
+        GorbageError::InvalidTokenAccount,
+    );
+    
+    let prizes = [8 * TOKEN / 5, 24 * TOKEN / 25, 16 * TOKEN / 25];
+    for (winner, prize) in [&players[2], &players[0], &players[3]]
+        .into_iter()
+        .zip(prizes)
+    {
+        let winner_tokens = token.account_of(&winner.pubkey());
+        let before = token_balance(&env, &winner_tokens);
+        let claim = claim(&env, &winner.pubkey());
+        env.execute(&[claim], &[winner]);
+        assert_eq!(token_balance(&env, &winner_tokens), before + prize);
+    }
+    assert_eq!(token_balance(&env, &vault_tokens), 0);
+    
+    let close = instructions::close_season(
+        &admin.pubkey(),
+        &env.season(season_number),
+        Some(token_program),
+    )
+    .unwrap();
+    env.execute(&[close], &[&admin]);
+    assert_eq!(env.season(season_number).phase, SeasonPhase::Closed);
+    let season_registry: SeasonRegistry = env.fetch(&pda::season_registry().0);
+    assert!(season_registry.seasons.is_empty());
+}
+
+#[test]
+fn spl_token_season_lifecycle() {
+    token_season_lifecycle(spl_token::ID);
+}
+
+#[test]
+fn token_2022_season_lifecycle() {
+    token_season_lifecycle(spl_token_2022::ID);
+}
@@ -0,0 +1,340 @@
+//! Off-chain builder for Merkle prize distributions.
+//!
+//! Takes the wallet -> amount results of a season and produces the root,
+//! total and per-wallet proofs passed to `settle_season_with_root` and
+//! `claim_prize_with_proof`. Leaves and nodes are hashed with the program's
+//! own `merkle` module, so proofs verify on-chain bit for bit.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use gorbage_hands_v2::constants::MAX_MERKLE_LEAVES;
+use gorbage_hands_v2::merkle::{leaf_hash, node_hash, verify_proof};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    
+    #[error("invalid CSV input: {0}")]
+    Csv(#[from] csv::Error),
+    
+    #[error("invalid JSON input: {0}")]
+    Json(#[from] serde_json::Error),
+    
+    #[error("unsupported input format {0:?}, expected .csv or .json")]
+    UnsupportedFormat(String),
+    
+    #[error("invalid wallet {0:?}")]
+    InvalidWallet(String),
+    
+    #[error("no winners in input")]
+    Empty,
+    
+    #[error("{count} winners exceed the limit of {max}")]
+    TooManyLeaves { count: usize, max: u32 },
+    
+    #[error("duplicate wallet {0}")]
+    DuplicateWallet(Pubkey),
+    
+    #[error("wallet {0} has a zero amount")]
+    ZeroAmount(Pubkey),
+    
+    #[error("wallet {wallet} amount {amount} exceeds the prize pool of {prize_pool}")]
+    AmountAbovePool {
+        wallet: Pubkey,
+        amount: u64,
+        prize_pool: u64,
+    },
+    
+    #[error("total payout {total} does not match the prize pool of {prize_pool}")]
+    TotalMismatch { total: u128, prize_pool: u64 },
+}
+
+/// One row of the results input
+#[derive(Debug, Clone, Deserialize)]
+struct Row {
+    wallet: String,
+    amount: u64,
+}
+
+/// A winner and the amount they are owed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+/// Everything a winner needs to call `claim_prize_with_proof`
+#[derive(Debug, Clone, Serialize)]
+pub struct Claim {
+    pub index: u32,
+    pub wallet: String,
+    pub amount: u64,
+    /// Hex-encoded sibling hashes from the leaf up to the root
+    pub proof: Vec<String>,
+}
+
+/// A complete distribution, ready for `settle_season_with_root`
+#[derive(Debug, Clone, Serialize)]
+pub struct Distribution {
+    /// Hex-encoded Merkle root
+    pub root: String,
+    pub total: u64,
+    pub leaf_count: u32,
+    pub claims: Vec<Claim>,
+}
+
+impl Distribution {
+    /// Validate `entries` against the season's post-fee `prize_pool` and build the tree.
+    /// Leaf indices follow the input order.
+    pub fn build(entries: &[Entry], prize_pool: u64) -> Result<Self, BuildError> {
+        validate(entries, prize_pool)?;
+        
+        let leaves: Vec<[u8; 32]> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| leaf_hash(index as u32, &entry.wallet, entry.amount))
+            .collect();
+        let levels = build_levels(leaves);
+        let root = levels.last().expect("at least one level")[0];
+        
+        let claims = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let proof = proof_for(&levels, index);
+                debug_assert!(verify_proof(&proof, &root, levels[0][index]));
+                
+                Claim {
+                    index: index as u32,
+                    wallet: entry.wallet.to_string(),
+                    amount: entry.amount,
+                    proof: proof.iter().map(hex::encode).collect(),
+                }
+            })
+            .collect();
+        
+        Ok(Self {
+            root: hex::encode(root),
+            total: prize_pool,
+            leaf_count: entries.len() as u32,
+            claims,
+        })
+    }
+}
+
+/// Read results from a `.csv` file with a `wallet,amount` header, or a `.json`
+/// file holding an array of `{ "wallet": ..., "amount": ... }` objects
+pub fn read_entries(path: &Path) -> Result<Vec<Entry>, BuildError> {
+    let io_error = |source| BuildError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    
+    let rows: Vec<Row> = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(path)?
+            .deserialize()
+            .collect::<Result<_, _>>()?,
+        Some("json") => {
+            let data = std::fs::read_to_string(path).map_err(io_error)?;
+            serde_json::from_str(&data)?
+        }
+        other => return Err(BuildError::UnsupportedFormat(other.unwrap_or_default().to_string())),
+    };
+    
+    rows.into_iter()
+        .map(|row| {
+            let wallet = Pubkey::from_str(&row.wallet)
+                .map_err(|_| BuildError::InvalidWallet(row.wallet.clone()))?;
+            
+            Ok(Entry {
+                wallet,
+                amount: row.amount,
+            })
+        })
+        .collect()
+}
+
+/// Reject inputs the program would not pay out exactly
+fn validate(entries: &[Entry], prize_pool: u64) -> Result<(), BuildError> {
+    if entries.is_empty() {
+        return Err(BuildError::Empty);
+    }
+    if entries.len() > MAX_MERKLE_LEAVES as usize {
+        return Err(BuildError::TooManyLeaves {
+            count: entries.len(),
+            max: MAX_MERKLE_LEAVES,
+        });
+    }
+    
+    let mut seen = HashSet::with_capacity(entries.len());
+    let mut total: u128 = 0;
+    for entry in entries {
+        if !seen.insert(entry.wallet) {
+            return Err(BuildError::DuplicateWallet(entry.wallet));
+        }
+        if entry.amount == 0 {
+            return Err(BuildError::ZeroAmount(entry.wallet));
+        }
+        if entry.amount > prize_pool {
+            return Err(BuildError::AmountAbovePool {
+                wallet: entry.wallet,
+                amount: entry.amount,
+                prize_pool,
+            });
+        }
+        total += entry.amount as u128;
+    }
+    
+    if total != prize_pool as u128 {
+        return Err(BuildError::TotalMismatch { total, prize_pool });
+    }
+    
+    Ok(())
+}
+
+/// All tree levels from the leaves up to the root. A node without a sibling
+/// is carried up unchanged, matching a proof that simply has no entry for it.
+fn build_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    
+    while levels.last().expect("at least one level").len() > 1 {
+        let next = levels
+            .last()
+            .expect("at least one level")
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => node_hash(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    
+    levels
+}
+
+fn proof_for(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    
+    proof
+}
+
+#[cfg(test)]
+mod tests {
+    use gorbage_hands_v2::merkle;
+    
+    use super::*;
+    
+    fn entries(amounts: &[u64]) -> Vec<Entry> {
+        amounts
+            .iter()
+            .map(|&amount| Entry {
+                wallet: Pubkey::new_unique(),
+                amount,
+            })
+            .collect()
+    }
+    
+    fn decode(hash: &str) -> [u8; 32] {
+        hex::decode(hash).unwrap().try_into().unwrap()
+    }
+    
+    #[test]
+    fn rejects_an_empty_input() {
+        assert!(matches!(Distribution::build(&[], 100), Err(BuildError::Empty)));
+    }
+    
+    #[test]
+    fn rejects_more_leaves_than_the_program_accepts() {
+        let too_many = vec![
+            Entry {
+                wallet: Pubkey::default(),
+                amount: 1,
+            };
+            MAX_MERKLE_LEAVES as usize + 1
+        ];
+        assert!(matches!(
+            Distribution::build(&too_many, too_many.len() as u64),
+            Err(BuildError::TooManyLeaves { count, max })
+                if count == too_many.len() && max == MAX_MERKLE_LEAVES
+        ));
+    }
+    
+    #[test]
+    fn rejects_a_duplicate_wallet() {
+        let mut input = entries(&[60, 40]);
+        input[1].wallet = input[0].wallet;
+        assert!(matches!(
+            Distribution::build(&input, 100),
+            Err(BuildError::DuplicateWallet(wallet)) if wallet == input[0].wallet
+        ));
+    }
+    
+    #[test]
+    fn rejects_a_zero_amount() {
+        let input = entries(&[100, 0]);
+        assert!(matches!(
+            Distribution::build(&input, 100),
+            Err(BuildError::ZeroAmount(wallet)) if wallet == input[1].wallet
+        ));
+    }
+    
+    #[test]
+    fn rejects_an_amount_above_the_pool() {
+        let input = entries(&[101]);
+        assert!(matches!(
+            Distribution::build(&input, 100),
+            Err(BuildError::AmountAbovePool { amount: 101, prize_pool: 100, .. })
+        ));
+    }
+    
+    #[test]
+    fn rejects_a_total_that_misses_the_pool() {
+        assert!(matches!(
+            Distribution::build(&entries(&[60, 30]), 100),
+            Err(BuildError::TotalMismatch { total: 90, prize_pool: 100 })
+        ));
+        // Summed without overflow
+        assert!(matches!(
+            Distribution::build(&entries(&[u64::MAX, u64::MAX]), u64::MAX),
+            Err(BuildError::TotalMismatch { .. })
+        ));
+    }
+    
+    #[test]
+    fn proofs_verify_against_the_program() {
+        // An odd leaf count carries a node up without a sibling
+        let input = entries(&[50, 20, 15, 10, 5]);
+        let distribution = Distribution::build(&input, 100).unwrap();
+        assert_eq!(distribution.leaf_count, 5);
+        assert_eq!(distribution.total, 100);
+        
+        let root = decode(&distribution.root);
+        for (entry, claim) in input.iter().zip(&distribution.claims) {
+            let proof: Vec<[u8; 32]> = claim.proof.iter().map(|hash| decode(hash)).collect();
+            let leaf = merkle::leaf_hash(claim.index, &entry.wallet, claim.amount);
+            assert!(merkle::verify_proof(&proof, &root, leaf));
+            
+            let forged = merkle::leaf_hash(claim.index, &entry.wallet, claim.amount + 1);
+            assert!(!merkle::verify_proof(&proof, &root, forged));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use gorbage_merkle::{read_entries, Distribution};
+
+/// Build the Merkle root and per-wallet proofs for a Merkle-settled season
+#[derive(Parser)]
+#[command(name = "gorbage-merkle", version)]
+struct Args {
+    /// Results file (.csv with a wallet,amount header, or .json array)
+    input: PathBuf,
+    
+    /// The season's post-fee prize pool; the amounts must add up to exactly this
+    #[arg(long)]
+    prize_pool: u64,
+    
+    /// Write the distribution here instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    
+    let result = read_entries(&args.input)
+        .and_then(|entries| Distribution::build(&entries, args.prize_pool));
+    let distribution = match result {
+        Ok(distribution) => distribution,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    
+    let json = serde_json::to_string_pretty(&distribution).expect("distribution serializes");
+    match args.output {
+        Some(path) => {
+            if let Err(err) = std::fs::write(&path, json) {
+                eprintln!("error: failed to write {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+            eprintln!(
+                "Root {} for {} winners, total {}",
+                distribution.root, distribution.leaf_count, distribution.total
+            );
+        }
+        None => println!("{json}"),
+    }
+    
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,71 @@
+//! Fee collection and prize assignment on arbitrary seasons: the treasury, the
+//! winners and the rounding dust together account for exactly what the vault holds
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use gorbage_hands_v2::constants::MAX_WINNERS;
+use gorbage_hands_v2::payout;
+use gorbage_hands_v2::state::DustPolicy;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Season {
+    entry_fee: u64,
+    participant_count: u64,
+    guaranteed_pool: u64,
+    guarantee_deposit: u64,
+    sponsor_pool: u64,
+    rollover_pool: u64,
+    payout_bps: Vec<u16>,
+    winner_count: u8,
+    dust_to_first_place: bool,
+}
+
+fuzz_target!(|season: Season| {
+    // Registration rejects entry totals past u64::MAX
+    let Some(entry_total) = season.entry_fee.checked_mul(season.participant_count) else {
+        return;
+    };
+    let vault = entry_total as u128
+        + season.guarantee_deposit as u128
+        + season.sponsor_pool as u128
+        + season.rollover_pool as u128;
+    
+    let split = payout::split_entries(
+        entry_total,
+        season.guaranteed_pool,
+        season.guarantee_deposit,
+    );
+    assert_eq!(split.fee + split.post_fee_entries, entry_total);
+    assert_eq!(split.overlay + split.unused_guarantee, season.guarantee_deposit);
+    let Some(prize_pool) = payout::prize_pool(&split, season.sponsor_pool, season.rollover_pool)
+    else {
+        return;
+    };
+    
+    // Winners fill the first placements of the schedule, settling needs at least one
+    let placements = season.payout_bps.len().min(MAX_WINNERS);
+    let winners = (season.winner_count as usize).min(placements);
+    if winners == 0 {
+        return;
+    }
+    let payout_bps = &season.payout_bps[..winners];
+    let dust_policy = if season.dust_to_first_place {
+        DustPolicy::ToFirstPlace
+    } else {
+        DustPolicy::ToTreasury
+    };
+    let Some((prizes, dust)) = payout::settle_prizes(prize_pool, payout_bps, dust_policy) else {
+        assert!(payout_bps.iter().all(|bps| *bps == 0));
+        return;
+    };
+    
+    let awarded: u128 = prizes.iter().map(|prize| *prize as u128).sum();
+    assert_eq!(awarded + dust as u128, prize_pool as u128);
+    assert!((dust as u128) < winners as u128);
+    assert_eq!(
+        split.fee as u128 + split.unused_guarantee as u128 + awarded + dust as u128,
+        vault
+    );
+});
@@ -1,22 +1,42 @@
-use anchor_lang::prelude::*;
-
 // PDA Seeds
 pub const SEASON_SEED: &[u8] = b"season";
 pub const PARTICIPANT_SEED: &[u8] = b"participant";
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const CONFIG_SEED: &[u8] = b"config";
+pub const WAITLIST_SEED: &[u8] = b"waitlist";
+pub const SPONSORSHIP_SEED: &[u8] = b"sponsorship";
+pub const CLAIM_BITMAP_SEED: &[u8] = b"claim_bitmap";
+pub const ORACLE_REGISTRY_SEED: &[u8] = b"oracle_registry";
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+pub const PARTICIPANT_PAGE_SEED: &[u8] = b"participant_page";
+pub const SEASON_REGISTRY_SEED: &[u8] = b"season_registry";
 
 // Program limits
-pub const MAX_WINNERS: usize = 3;
+pub const MAX_WINNERS: usize = 10;
 pub const MAX_SEASON_NAME_LEN: usize = 32;
+pub const MAX_WAITLIST_LEN: usize = 64;
+pub const MAX_ATTESTORS: usize = 10;
+pub const PARTICIPANT_PAGE_SIZE: usize = 64;
+pub const MAX_LISTED_SEASONS: usize = 32;
+
+// Largest Merkle distribution, keeps the claim bitmap within the 10KB CPI allocation limit
+pub const MAX_MERKLE_LEAVES: u32 = 65_536;
+
+// Slots between locking a raffle and the slot whose hash seeds its draw
+pub const RAFFLE_SLOT_DELAY: u64 = 16;
+
+// Slots after the committed raffle slot in which the secret can be revealed: the
+// history the SlotHashes sysvar keeps. Afterwards anyone can cancel the season
+pub const RAFFLE_REVEAL_WINDOW: u64 = 512;
+
+// Time a waitlisted wallet has to register once a spot is offered (24 hours)
+pub const WAITLIST_CONFIRMATION_WINDOW: i64 = 24 * 60 * 60;
 
 // Entry fee in lamports (0.1 SOL default, configurable per season)
 pub const DEFAULT_ENTRY_FEE: u64 = 100_000_000;
 
-// Prize distribution percentages (basis points, 10000 = 100%)
-pub const FIRST_PLACE_BPS: u64 = 5000;  // 50%
-pub const SECOND_PLACE_BPS: u64 = 3000; // 30%
-pub const THIRD_PLACE_BPS: u64 = 2000;  // 20%
+// Basis point denominator (10000 = 100%)
+pub const BPS_DENOMINATOR: u64 = 10000;
 
 // Platform fee (basis points, 2000 = 20%)
 pub const PLATFORM_FEE_BPS: u64 = 2000;  // 20% goes to treasury
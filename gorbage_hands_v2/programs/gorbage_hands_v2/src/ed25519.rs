@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::error::GorbageError;
+
+/// Size of the Ed25519SignatureOffsets struct following the 2-byte header
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+
+/// Instruction index meaning "data lives in the Ed25519 instruction itself"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+const PUBKEY_LEN: usize = 32;
+
+/// Check that the instruction right before the current one is an Ed25519
+/// precompile call verifying exactly one signature by `signer` over `message`.
+/// The precompile has already checked the signature itself; this makes sure it
+/// covered the key and bytes we expect.
+pub fn verify_signed_message(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let ed25519_index = current_index
+        .checked_sub(1)
+        .ok_or(GorbageError::InvalidScoreAttestation)?;
+    let ed25519_ix = load_instruction_at_checked(ed25519_index as usize, instructions_sysvar)?;
+    
+    require_keys_eq!(
+        ed25519_ix.program_id,
+        ed25519_program::ID,
+        GorbageError::InvalidScoreAttestation
+    );
+    
+    let data = ed25519_ix.data.as_slice();
+    require!(
+        data.len() >= 2 + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        GorbageError::InvalidScoreAttestation
+    );
+    
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let signature_instruction_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_instruction_index = read_u16(8);
+    let message_data_offset = read_u16(10) as usize;
+    let message_data_size = read_u16(12) as usize;
+    let message_instruction_index = read_u16(14);
+    
+    // Key, signature and message must all come from the precompile's own data,
+    // otherwise they could point at bytes we never look at
+    require!(
+        signature_instruction_index == CURRENT_INSTRUCTION
+            && public_key_instruction_index == CURRENT_INSTRUCTION
+            && message_instruction_index == CURRENT_INSTRUCTION,
+        GorbageError::InvalidScoreAttestation
+    );
+    
+    let signed_pubkey = data
+        .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+        .ok_or(GorbageError::InvalidScoreAttestation)?;
+    let signed_message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(GorbageError::InvalidScoreAttestation)?;
+    
+    require!(
+        signed_pubkey == signer.as_ref() && signed_message == message,
+        GorbageError::InvalidScoreAttestation
+    );
+    
+    Ok(())
+}
@@ -61,4 +61,229 @@ pub enum GorbageError {
     
     #[msg("No prize pool to collect fee from")]
     NoPrizePool,
+    
+    #[msg("Payout schedule must have between 1 and MAX_WINNERS non-zero entries")]
+    InvalidPayoutSchedule,
+    
+    #[msg("Payout schedule must sum to 10000 basis points")]
+    PayoutScheduleSumMismatch,
+    
+    #[msg("Registration has not opened yet")]
+    RegistrationNotOpen,
+    
+    #[msg("Platform fee has not been collected yet")]
+    FeeNotCollected,
+    
+    #[msg("Results have not been confirmed yet")]
+    PrizesNotAssigned,
+    
+    #[msg("Season has been closed")]
+    SeasonClosed,
+    
+    #[msg("Season has been cancelled")]
+    SeasonCancelled,
+    
+    #[msg("Season has not been cancelled")]
+    SeasonNotCancelled,
+    
+    #[msg("Season is already settled and can no longer be cancelled")]
+    SeasonAlreadySettled,
+    
+    #[msg("Treasury must sign to return the collected fee")]
+    TreasuryRequired,
+    
+    #[msg("Treasury does not match the one the fee was collected to")]
+    TreasuryMismatch,
+    
+    #[msg("Entry fee already refunded")]
+    AlreadyRefunded,
+    
+    #[msg("Minimum participant count was not reached, season is refunding")]
+    MinParticipantsNotMet,
+    
+    #[msg("Maximum participants must be zero or at least the minimum")]
+    InvalidParticipantLimits,
+    
+    #[msg("Season is full, join the waitlist instead")]
+    SeasonFull,
+    
+    #[msg("Season has open spots, register directly instead")]
+    SpotsAvailable,
+    
+    #[msg("Waitlist account is required for seasons with a participant cap")]
+    WaitlistRequired,
+    
+    #[msg("Waitlist is full")]
+    WaitlistFull,
+    
+    #[msg("Wallet is already on the waitlist")]
+    AlreadyOnWaitlist,
+    
+    #[msg("Participant has withdrawn or been disqualified")]
+    ParticipantInactive,
+    
+    #[msg("Token accounts are required for token-priced seasons")]
+    TokenAccountsRequired,
+    
+    #[msg("Mint does not match the season mint")]
+    InvalidMint,
+    
+    #[msg("Token account does not match the expected owner or vault")]
+    InvalidTokenAccount,
+    
+    #[msg("Token program does not own the season mint")]
+    InvalidTokenProgram,
+    
+    #[msg("Contribution amount must be greater than zero")]
+    InvalidContribution,
+    
+    #[msg("Season no longer accepts contributions")]
+    ContributionsClosed,
+    
+    #[msg("Claim deadline must be after the season end")]
+    InvalidClaimDeadline,
+    
+    #[msg("Claim deadline has passed")]
+    ClaimDeadlinePassed,
+    
+    #[msg("Unclaimed prizes are still inside the claim window")]
+    ClaimWindowOpen,
+    
+    #[msg("Rollover season accounts are required")]
+    RolloverSeasonRequired,
+    
+    #[msg("Rollover season does not match the policy or can no longer take funds")]
+    InvalidRolloverSeason,
+    
+    #[msg("Participant account cannot be closed yet")]
+    ParticipantNotClosable,
+    
+    #[msg("Remaining accounts must be (participant, owner) pairs")]
+    InvalidRemainingAccounts,
+    
+    #[msg("Participant appears more than once in the winner list")]
+    DuplicateWinner,
+    
+    #[msg("Total payout exceeds the prize pool")]
+    PayoutExceedsPool,
+    
+    #[msg("Season was not settled with a Merkle root")]
+    NotMerkleSettlement,
+    
+    #[msg("Invalid Merkle leaf count")]
+    InvalidLeafCount,
+    
+    #[msg("Claim index outside the Merkle distribution")]
+    InvalidClaimIndex,
+    
+    #[msg("Merkle proof does not match the season root")]
+    InvalidMerkleProof,
+    
+    #[msg("No game server registered")]
+    GameServerNotSet,
+    
+    #[msg("Participant has no submitted score")]
+    ScoreMissing,
+    
+    #[msg("Every scored participant must be passed to rank by score")]
+    IncompleteScoreboard,
+    
+    #[msg("Participant already ranked in this ranking round")]
+    AlreadyRanked,
+    
+    #[msg("Participant accounts do not follow the leaderboard")]
+    LeaderboardMismatch,
+    
+    #[msg("Missing or invalid Ed25519 score attestation")]
+    InvalidScoreAttestation,
+    
+    #[msg("Score nonce already used")]
+    StaleScoreNonce,
+    
+    #[msg("Attestor set must be unique, at most the maximum size, and meet the threshold")]
+    InvalidAttestorSet,
+    
+    #[msg("Signer is not a registered attestor")]
+    NotAnAttestor,
+    
+    #[msg("Attestor already confirmed these results")]
+    AlreadyAttested,
+    
+    #[msg("Result hash does not match the proposed results")]
+    ResultHashMismatch,
+    
+    #[msg("Challenge window cannot be negative")]
+    InvalidChallengeWindow,
+    
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    
+    #[msg("Proposed results have open disputes")]
+    OpenDisputes,
+    
+    #[msg("Not enough attestors have confirmed the results")]
+    NotEnoughAttestations,
+    
+    #[msg("Dispute does not belong to this season")]
+    InvalidDispute,
+    
+    #[msg("Only raffle seasons can draw winners")]
+    RaffleModeRequired,
+    
+    #[msg("Raffle seasons pick winners with draw_winners")]
+    ContestModeRequired,
+    
+    #[msg("Raffle seasons need a commitment")]
+    InvalidRaffleCommitment,
+    
+    #[msg("Raffle secret does not match the commitment")]
+    InvalidRaffleSecret,
+    
+    #[msg("Raffle secret already revealed")]
+    RaffleAlreadyRevealed,
+    
+    #[msg("Raffle secret not revealed yet")]
+    RaffleNotRevealed,
+    
+    #[msg("Participant accounts do not follow the raffle draw")]
+    InvalidDrawSequence,
+    
+    #[msg("Payout must cover the whole prize pool when dust goes to first place")]
+    PayoutBelowPool,
+    
+    #[msg("Unassigned dust must be less than one unit per leaf")]
+    DustAboveLeafCount,
+    
+    #[msg("Raffle draw slot not committed yet")]
+    RaffleNotLocked,
+    
+    #[msg("Raffle draw slot already committed")]
+    RaffleAlreadyLocked,
+    
+    #[msg("Committed raffle slot not reached yet")]
+    RaffleSlotPending,
+    
+    #[msg("Raffle reveal deadline has passed")]
+    RaffleRevealExpired,
+    
+    #[msg("Participant pages can only be closed once the season is closed or cancelled")]
+    SeasonNotOver,
+    
+    #[msg("Raffle draw is locked, participants can no longer be disqualified")]
+    RaffleLocked,
+    
+    #[msg("Only the participant page being filled or the one after it can be opened")]
+    InvalidParticipantPage,
+    
+    #[msg("Season has ended, scores can no longer be submitted")]
+    SeasonEnded,
+    
+    #[msg("Seasons with a challenge window need a dispute bond")]
+    InvalidDisputeBond,
+    
+    #[msg("Merkle-settled seasons need their claim bitmap")]
+    ClaimBitmapRequired,
 }
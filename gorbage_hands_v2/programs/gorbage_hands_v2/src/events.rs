@@ -0,0 +1,358 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{DustPolicy, SeasonMode, UnclaimedPrizePolicy};
+
+// Every event is bounded (winner lists are capped at MAX_WINNERS), so all of them
+// fit in program logs and are emitted with `emit!`.
+
+#[event]
+pub struct ConfigInitialized {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub config: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct GameServerSet {
+    pub config: Pubkey,
+    pub old_game_server: Pubkey,
+    pub game_server: Pubkey,
+}
+
+/// Emitted whenever the attestor set or threshold changes, with the new state
+#[event]
+pub struct OracleRegistryUpdated {
+    pub oracle_registry: Pubkey,
+    pub attestors: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct SeasonCreated {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub authority: Pubkey,
+    pub name: String,
+    pub mode: SeasonMode,
+    /// None for native lamport seasons
+    pub mint: Option<Pubkey>,
+    pub entry_fee: u64,
+    pub payout_bps: Vec<u16>,
+    pub min_participants: u64,
+    pub max_participants: u64,
+    pub guaranteed_pool: u64,
+    pub guarantee_deposit: u64,
+    pub registration_start: i64,
+    pub registration_end: i64,
+    pub season_end: i64,
+    pub claim_deadline: i64,
+    pub unclaimed_policy: UnclaimedPrizePolicy,
+    pub dust_policy: DustPolicy,
+}
+
+#[event]
+pub struct ParticipantRegistered {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub participant: Pubkey,
+    pub owner: Pubkey,
+    pub index: u64,
+    /// Entry fee the vault actually received
+    pub entry_fee_paid: u64,
+    pub participant_count: u64,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct ParticipantPageOpened {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub participant_page: Pubkey,
+    pub page: u64,
+    /// Wallet that paid the page rent
+    pub payer: Pubkey,
+}
+
+#[event]
+pub struct ParticipantPageClosed {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub participant_page: Pubkey,
+    pub page: u64,
+    /// Wallet the page rent went back to
+    pub rent_recipient: Pubkey,
+}
+
+#[event]
+pub struct WaitlistJoined {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub wallet: Pubkey,
+    /// 1-based position in the queue
+    pub position: u32,
+}
+
+#[event]
+pub struct ParticipantWithdrawn {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub owner: Pubkey,
+    pub refund: u64,
+    pub participant_count: u64,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct ParticipantDisqualified {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub owner: Pubkey,
+    /// Entry fee refunded, 0 once the season is active
+    pub refund: u64,
+    pub participant_count: u64,
+    pub disqualified_count: u64,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct ParticipantClosed {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub participant: Pubkey,
+    /// Wallet the rent went back to
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct SponsorContributed {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub sponsor: Pubkey,
+    /// Amount the vault actually received
+    pub amount: u64,
+    /// Sponsor's running total for the season
+    pub sponsor_total: u64,
+    pub sponsor_pool: u64,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct SponsorRefunded {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    /// Sponsor contributions still held for refunds
+    pub remaining_sponsor_pool: u64,
+}
+
+#[event]
+pub struct ScoreSubmitted {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub participant: Pubkey,
+    pub owner: Pubkey,
+    pub score: u64,
+    pub nonce: u64,
+    pub submitted_at: i64,
+    /// Submitted by the participant with a game server attestation
+    pub attested: bool,
+    pub scored_count: u64,
+}
+
+#[event]
+pub struct ScoresRanked {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub ranking_round: u32,
+    /// Participants ranked by this batch
+    pub batch_size: u32,
+    pub ranked_count: u64,
+    pub scored_count: u64,
+}
+
+#[event]
+pub struct RaffleSlotLocked {
+    pub season: Pubkey,
+    pub season_number: u64,
+    /// Slot whose hash is mixed into the draw seed
+    pub raffle_slot: u64,
+}
+
+#[event]
+pub struct RaffleRevealed {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub raffle_slot: u64,
+    pub raffle_slot_hash: [u8; 32],
+    pub raffle_seed: [u8; 32],
+}
+
+#[event]
+pub struct FeeCollected {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub treasury: Pubkey,
+    pub fee_amount: u64,
+    /// Guarantee deposit paid into the prize pool
+    pub guarantee_overlay: u64,
+    /// Guarantee deposit returned to the authority
+    pub guarantee_returned: u64,
+    pub prize_pool_before: u64,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct WinnersSet {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub settlement_epoch: u32,
+    /// Winners in placement order, empty for Merkle settlements
+    pub winners: Vec<Pubkey>,
+    /// Prize per winner, same order as `winners`
+    pub prize_amounts: Vec<u64>,
+    /// Root of the distribution for Merkle settlements
+    pub merkle_root: Option<[u8; 32]>,
+    pub leaf_count: u32,
+    pub total_awarded: u64,
+    pub prize_pool: u64,
+    /// Part of the prize pool left unassigned by rounding, 0 when it went to first place
+    pub dust: u64,
+    pub dust_policy: DustPolicy,
+    pub result_hash: [u8; 32],
+    pub challenge_deadline: i64,
+}
+
+#[event]
+pub struct SettlementFinalized {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub settlement_epoch: u32,
+    pub winner_count: u8,
+    pub total_awarded: u64,
+    /// Rounding dust that left the vault, 0 when it went to first place
+    pub dust: u64,
+    pub dust_policy: DustPolicy,
+    /// Treasury or rollover season that received the dust
+    pub dust_recipient: Option<Pubkey>,
+}
+
+#[event]
+pub struct ResultsAttested {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub settlement_epoch: u32,
+    pub attestor: Pubkey,
+    pub result_hash: [u8; 32],
+    /// Attestations from current registry members, this one included
+    pub attestation_count: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub settlement_epoch: u32,
+    pub disputer: Pubkey,
+    pub bond: u64,
+    pub open_disputes: u32,
+}
+
+/// Emitted when a dispute is closed without voiding the results: upheld
+/// results forfeit the bond, disputes on already voided results get it back
+#[event]
+pub struct DisputeResolved {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub settlement_epoch: u32,
+    pub disputer: Pubkey,
+    pub bond: u64,
+    /// Bond sent to the treasury, 0 when it was returned
+    pub bond_forfeited: u64,
+    pub open_disputes: u32,
+}
+
+#[event]
+pub struct ResultsVoided {
+    pub season: Pubkey,
+    pub season_number: u64,
+    /// Settlement epoch whose results were thrown out
+    pub settlement_epoch: u32,
+    pub disputer: Pubkey,
+    /// Bond returned to the disputer
+    pub bond: u64,
+}
+
+#[event]
+pub struct PrizeSet {
+    pub season: Pubkey,
+    pub participant: Pubkey,
+    pub owner: Pubkey,
+    pub placement: u8,
+    pub prize_amount: u64,
+    pub settlement_epoch: u32,
+}
+
+#[event]
+pub struct PrizeClaimed {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub winner: Pubkey,
+    pub amount: u64,
+    /// Placement for account settlements, 0 for Merkle claims
+    pub placement: u8,
+    /// Leaf index for Merkle claims
+    pub leaf_index: Option<u32>,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct SeasonCancelled {
+    pub season: Pubkey,
+    pub season_number: u64,
+    /// Signer that cancelled the season
+    pub cancelled_by: Pubkey,
+    pub participant_count: u64,
+    /// Platform fee the treasury returned to the vault
+    pub fee_returned: u64,
+    /// Guarantee deposit returned to the season authority
+    pub guarantee_returned: u64,
+    /// Rollover pool returned to the season authority
+    pub rollover_returned: u64,
+    /// Entry fees left to refund
+    pub refundable_entry_fees: u64,
+    /// Sponsor contributions left to refund
+    pub refundable_sponsor_pool: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub owner: Pubkey,
+    pub amount: u64,
+    /// Entry fees still held for refunds
+    pub remaining_entry_fees: u64,
+}
+
+#[event]
+pub struct SeasonClosed {
+    pub season: Pubkey,
+    pub season_number: u64,
+    pub total_awarded: u64,
+    pub total_claimed: u64,
+    /// Unclaimed prizes released at close
+    pub unclaimed: u64,
+    pub unclaimed_policy: UnclaimedPrizePolicy,
+    /// Treasury or rollover season that received the unclaimed prizes
+    pub unclaimed_recipient: Option<Pubkey>,
+    /// Remaining vault balance sent to the authority
+    pub returned_to_authority: u64,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SEASON_SEED, CONFIG_SEED, ORACLE_REGISTRY_SEED};
+use crate::error::GorbageError;
+use crate::events::ResultsAttested;
+use crate::state::{Season, SeasonPhase, ProgramConfig, OracleRegistry};
+
+#[derive(Accounts)]
+pub struct AttestResults<'info> {
+    pub attestor: Signer<'info>,
+    
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [ORACLE_REGISTRY_SEED, config.key().as_ref()],
+        bump = oracle_registry.bump,
+        constraint = oracle_registry.contains(&attestor.key()) @ GorbageError::NotAnAttestor
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+}
+
+/// Confirm the proposed results of a season by submitting their hash. The
+/// results can only be finalized once `threshold` distinct registered attestors
+/// have confirmed the same hash. Attestors can co-sign by bundling their
+/// `attest_results` instructions into one transaction.
+pub fn handler(ctx: Context<AttestResults>, result_hash: [u8; 32]) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let oracle_registry = &ctx.accounts.oracle_registry;
+    let attestor = ctx.accounts.attestor.key();
+    
+    // Validations
+    match season.phase {
+        SeasonPhase::Settling => {}
+        SeasonPhase::Settled => return err!(GorbageError::SeasonAlreadySettled),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersNotSet),
+    }
+    require!(result_hash == season.result_hash, GorbageError::ResultHashMismatch);
+    require!(
+        !season.attestations.contains(&attestor),
+        GorbageError::AlreadyAttested
+    );
+    
+    // Attestors removed from the registry since attesting no longer count
+    season.attestations.retain(|existing| oracle_registry.contains(existing));
+    season.attestations.push(attestor);
+    
+    let attestation_count = season.attestations.len();
+    
+    msg!(
+        "Results of season {} attested by {} ({} of {})",
+        season.season_number,
+        attestor,
+        attestation_count,
+        oracle_registry.threshold
+    );
+    emit!(ResultsAttested {
+        season: season.key(),
+        season_number: season.season_number,
+        settlement_epoch: season.settlement_epoch,
+        attestor,
+        result_hash,
+        attestation_count: attestation_count as u8,
+        threshold: oracle_registry.threshold,
+    });
+    
+    Ok(())
+}
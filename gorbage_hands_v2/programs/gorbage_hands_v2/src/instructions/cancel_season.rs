@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, VAULT_SEED, CONFIG_SEED, SEASON_REGISTRY_SEED};
+use crate::error::GorbageError;
+use crate::events::SeasonCancelled;
+use crate::state::{Season, SeasonPhase, SeasonRegistry, ProgramConfig};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct CancelSeason<'info> {
+    /// Season authority or global admin, or anyone once a raffle's reveal deadline passed
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
+    /// CHECK: Vault PDA that holds the prize pool
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Treasury that received the platform fee, only needed once the fee was collected
+    #[account(mut)]
+    pub treasury: Option<Signer<'info>>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury token account returning the fee, only for token-priced seasons
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Season authority, receives back the guarantee deposit and rollover pool
+    #[account(
+        mut,
+        address = season.authority @ GorbageError::Unauthorized
+    )]
+    pub season_authority: AccountInfo<'info>,
+    
+    /// Season authority token account for the guarantee deposit, only for token-priced seasons
+    #[account(mut)]
+    pub season_authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Cancel a season so every participant can reclaim their entry fee and every
+/// sponsor their contribution. If the platform fee already went to the treasury,
+/// the treasury returns it to the vault in the same transaction so refunds are
+/// paid in full. The guarantee deposit goes straight back to the season authority.
+///
+/// A season cancelled automatically for missing its minimum can still be
+/// cancelled explicitly, to release its guarantee deposit and rollover pool.
+/// A raffle whose secret wasn't revealed in time can be cancelled by anyone.
+pub fn handler(ctx: Context<CancelSeason>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == season.authority
+            || authority == ctx.accounts.config.admin
+            || season.is_raffle_reveal_expired(clock.slot),
+        GorbageError::Unauthorized
+    );
+    
+    season.sync_phase(clock.unix_timestamp);
+    let fee_collected = match season.phase {
+        SeasonPhase::Scheduled | SeasonPhase::Registration => false,
+        SeasonPhase::Active | SeasonPhase::Settling => true,
+        SeasonPhase::Settled => return err!(GorbageError::SeasonAlreadySettled),
+        // Fees are never collected below the minimum, so nothing goes back to the treasury
+        SeasonPhase::Cancelled if season.guarantee_deposit > 0 || season.rollover_pool > 0 => false,
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+    };
+    
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    
+    let mut fee_returned = 0;
+    if fee_collected && season.fee_amount > 0 {
+        let treasury = ctx.accounts.treasury
+            .as_ref()
+            .ok_or(GorbageError::TreasuryRequired)?;
+        require!(treasury.key() == season.treasury, GorbageError::TreasuryMismatch);
+        
+        // Return the fee so the vault again holds every entry fee. Transfer fees
+        // on the way back are paid by the treasury on top of the fee amount.
+        let returned = vault.deposit(
+            &treasury.to_account_info(),
+            ctx.accounts.treasury_token_account.as_ref(),
+            vault.gross_up(season.fee_amount)?,
+        )?;
+        
+        season.prize_pool = season.prize_pool
+            .checked_add(returned)
+            .ok_or(GorbageError::Overflow)?;
+        
+        msg!("Platform fee of {} returned by treasury", returned);
+        season.fee_amount = 0;
+        fee_returned = returned;
+    }
+    
+    // Rolled-over prizes have no individual owner and go back to the season authority
+    let rollover_returned = season.rollover_pool;
+    if season.rollover_pool > 0 {
+        vault.withdraw(
+            &ctx.accounts.season_authority,
+            ctx.accounts.season_authority_token_account.as_ref(),
+            season.rollover_pool,
+        )?;
+        
+        msg!("Rollover pool of {} returned to authority", season.rollover_pool);
+        
+        if fee_collected {
+            season.prize_pool = season.prize_pool
+                .checked_sub(season.rollover_pool)
+                .ok_or(GorbageError::Overflow)?;
+        }
+        season.rollover_pool = 0;
+    }
+    
+    let guarantee_returned = season.guarantee_deposit;
+    if season.guarantee_deposit > 0 {
+        vault.withdraw(
+            &ctx.accounts.season_authority,
+            ctx.accounts.season_authority_token_account.as_ref(),
+            season.guarantee_deposit,
+        )?;
+        
+        msg!("Guarantee deposit of {} returned to authority", season.guarantee_deposit);
+        
+        // Once the fee is collected the overlay is counted in the prize pool
+        if fee_collected {
+            season.prize_pool = season.prize_pool
+                .checked_sub(season.guarantee_deposit)
+                .ok_or(GorbageError::Overflow)?;
+        }
+        season.guarantee_deposit = 0;
+    }
+    
+    // From here on prize_pool holds refundable entry fees and sponsor_pool
+    // holds refundable contributions
+    if fee_collected {
+        season.prize_pool = season.prize_pool
+            .checked_sub(season.sponsor_pool)
+            .ok_or(GorbageError::Overflow)?;
+    }
+    
+    season.phase = SeasonPhase::Cancelled;
+    ctx.accounts.season_registry.update(season, clock.unix_timestamp);
+    
+    msg!(
+        "Season {} cancelled, {} participants can claim refunds",
+        season.season_number,
+        season.participant_count
+    );
+    emit!(SeasonCancelled {
+        season: season.key(),
+        season_number: season.season_number,
+        cancelled_by: authority,
+        participant_count: season.participant_count,
+        fee_returned,
+        guarantee_returned,
+        rollover_returned,
+        refundable_entry_fees: season.prize_pool,
+        refundable_sponsor_pool: season.sponsor_pool,
+    });
+    
+    Ok(())
+}
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, VAULT_SEED};
 use crate::error::GorbageError;
-use crate::state::{Season, Participant};
+use crate::events::PrizeClaimed;
+use crate::state::{Season, SeasonPhase, Participant};
+use crate::vault::SeasonVault;
 
 #[derive(Accounts)]
 pub struct ClaimPrize<'info> {
@@ -11,6 +13,7 @@ pub struct ClaimPrize<'info> {
     pub winner: Signer<'info>,
     
     #[account(
+        mut,
         seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
         bump = season.bump
     )]
@@ -33,61 +36,82 @@ pub struct ClaimPrize<'info> {
     )]
     pub vault: AccountInfo<'info>,
     
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Winner token account receiving the prize, only for token-priced seasons
+    #[account(mut)]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<ClaimPrize>) -> Result<()> {
-    let season = &ctx.accounts.season;
+    let season = &mut ctx.accounts.season;
     let participant = &mut ctx.accounts.participant;
-    let vault = &ctx.accounts.vault;
     let winner = &ctx.accounts.winner;
+    let clock = Clock::get()?;
     
     // Validations
-    require!(season.winners_set, GorbageError::WinnersNotSet);
-    require!(participant.placement > 0, GorbageError::NotAWinner);
+    match season.phase {
+        SeasonPhase::Settled => {}
+        SeasonPhase::Settling => return err!(GorbageError::PrizesNotAssigned),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersNotSet),
+    }
+    require!(participant.is_winner(season), GorbageError::NotAWinner);
     require!(!participant.prize_claimed, GorbageError::PrizeAlreadyClaimed);
     require!(participant.prize_amount > 0, GorbageError::InvalidPlacement);
-    
-    let prize_amount = participant.prize_amount;
-    
-    // Check vault has enough balance
     require!(
-        vault.lamports() >= prize_amount,
-        GorbageError::InsufficientVaultFunds
+        season.is_claim_window_open(clock.unix_timestamp),
+        GorbageError::ClaimDeadlinePassed
     );
     
-    // Transfer prize from vault PDA to winner using invoke_signed
-    // The vault is a system-owned PDA, so we need to sign with the PDA seeds
-    let season_key = season.key();
-    let vault_seeds: &[&[u8]] = &[
-        VAULT_SEED,
-        season_key.as_ref(),
-        &[season.vault_bump],
-    ];
+    let prize_amount = participant.prize_amount;
     
-    invoke_signed(
-        &system_instruction::transfer(
-            vault.key,
-            winner.key,
-            prize_amount,
-        ),
-        &[
-            vault.to_account_info(),
-            winner.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[vault_seeds],
+    // Transfer prize from vault PDA to winner
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    vault.withdraw(
+        &winner.to_account_info(),
+        ctx.accounts.winner_token_account.as_ref(),
+        prize_amount,
     )?;
     
     // Mark prize as claimed
     participant.prize_claimed = true;
+    season.total_claimed = season.total_claimed
+        .checked_add(prize_amount)
+        .ok_or(GorbageError::Overflow)?;
     
     msg!(
-        "Prize claimed: {} lamports to {} for placement {}",
+        "Prize claimed: {} to {} for placement {}",
         prize_amount,
         winner.key(),
         participant.placement
     );
+    emit!(PrizeClaimed {
+        season: season.key(),
+        season_number: season.season_number,
+        winner: winner.key(),
+        amount: prize_amount,
+        placement: participant.placement,
+        leaf_index: None,
+        total_claimed: season.total_claimed,
+    });
     
     Ok(())
 }
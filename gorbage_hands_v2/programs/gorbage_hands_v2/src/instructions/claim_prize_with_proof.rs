@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, VAULT_SEED, CLAIM_BITMAP_SEED};
+use crate::error::GorbageError;
+use crate::events::PrizeClaimed;
+use crate::merkle;
+use crate::state::{Season, SeasonPhase, ClaimBitmap};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct ClaimPrizeWithProof<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [
+            CLAIM_BITMAP_SEED,
+            season.key().as_ref(),
+            season.settlement_epoch.to_le_bytes().as_ref()
+        ],
+        bump = claim_bitmap.bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+    
+    /// CHECK: Vault PDA that holds the prize pool
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Winner token account receiving the prize, only for token-priced seasons
+    #[account(mut)]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim a prize from a Merkle-settled season by proving `(index, winner, amount)`
+/// is a leaf of the season's root
+pub fn handler(
+    ctx: Context<ClaimPrizeWithProof>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+    let winner = &ctx.accounts.winner;
+    let clock = Clock::get()?;
+    
+    // Validations
+    match season.phase {
+        SeasonPhase::Settled => {}
+        SeasonPhase::Settling => return err!(GorbageError::PrizesNotAssigned),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersNotSet),
+    }
+    let merkle_root = season.merkle_root.ok_or(GorbageError::NotMerkleSettlement)?;
+    require!(index < claim_bitmap.leaf_count, GorbageError::InvalidClaimIndex);
+    require!(!claim_bitmap.is_claimed(index), GorbageError::PrizeAlreadyClaimed);
+    require!(amount > 0, GorbageError::NotAWinner);
+    require!(
+        season.is_claim_window_open(clock.unix_timestamp),
+        GorbageError::ClaimDeadlinePassed
+    );
+    
+    let leaf = merkle::leaf_hash(index, &winner.key(), amount);
+    require!(
+        merkle::verify_proof(&proof, &merkle_root, leaf),
+        GorbageError::InvalidMerkleProof
+    );
+    
+    // The root commits to at most total_awarded, but never pay past it
+    let total_claimed = season.total_claimed
+        .checked_add(amount)
+        .ok_or(GorbageError::Overflow)?;
+    require!(total_claimed <= season.total_awarded, GorbageError::PayoutExceedsPool);
+    
+    // Transfer prize from vault PDA to winner
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    vault.withdraw(
+        &winner.to_account_info(),
+        ctx.accounts.winner_token_account.as_ref(),
+        amount,
+    )?;
+    
+    // Mark leaf as claimed
+    claim_bitmap.set_claimed(index);
+    season.total_claimed = total_claimed;
+    
+    msg!(
+        "Prize claimed: {} to {} for leaf {}",
+        amount,
+        winner.key(),
+        index
+    );
+    emit!(PrizeClaimed {
+        season: season.key(),
+        season_number: season.season_number,
+        winner: winner.key(),
+        amount,
+        placement: 0,
+        leaf_index: Some(index),
+        total_claimed: season.total_claimed,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, VAULT_SEED};
+use crate::error::GorbageError;
+use crate::events::RefundClaimed;
+use crate::state::{Season, SeasonPhase, Participant};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub participant_owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), participant_owner.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.owner == participant_owner.key() @ GorbageError::Unauthorized,
+        constraint = participant.season == season.key() @ GorbageError::NotRegistered
+    )]
+    pub participant: Account<'info, Participant>,
+    
+    /// CHECK: Vault PDA that holds the entry fees
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Participant token account receiving the refund, only for token-priced seasons
+    #[account(mut)]
+    pub participant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let participant = &mut ctx.accounts.participant;
+    let owner = &ctx.accounts.participant_owner;
+    
+    // Validations
+    season.sync_phase(Clock::get()?.unix_timestamp);
+    require!(season.phase == SeasonPhase::Cancelled, GorbageError::SeasonNotCancelled);
+    require!(!participant.refunded, GorbageError::AlreadyRefunded);
+    
+    let refund_amount = participant.entry_fee_paid;
+    
+    // Transfer entry fee back from the vault PDA
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    vault.withdraw(
+        &owner.to_account_info(),
+        ctx.accounts.participant_token_account.as_ref(),
+        refund_amount,
+    )?;
+    
+    season.prize_pool = season.prize_pool
+        .checked_sub(refund_amount)
+        .ok_or(GorbageError::InsufficientVaultFunds)?;
+    participant.refunded = true;
+    
+    msg!(
+        "Refunded {} to {} for cancelled season {}",
+        refund_amount,
+        owner.key(),
+        season.season_number
+    );
+    emit!(RefundClaimed {
+        season: season.key(),
+        season_number: season.season_number,
+        owner: owner.key(),
+        amount: refund_amount,
+        remaining_entry_fees: season.prize_pool,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, VAULT_SEED, SPONSORSHIP_SEED};
+use crate::error::GorbageError;
+use crate::events::SponsorRefunded;
+use crate::state::{Season, SeasonPhase, Sponsorship};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct ClaimSponsorRefund<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [SPONSORSHIP_SEED, season.key().as_ref(), sponsor.key().as_ref()],
+        bump = sponsorship.bump,
+        constraint = sponsorship.sponsor == sponsor.key() @ GorbageError::Unauthorized
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    
+    /// CHECK: Vault PDA that holds the prize pool
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Sponsor token account receiving the refund, only for token-priced seasons
+    #[account(mut)]
+    pub sponsor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimSponsorRefund>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let sponsorship = &mut ctx.accounts.sponsorship;
+    let sponsor = &ctx.accounts.sponsor;
+    
+    // Validations
+    season.sync_phase(Clock::get()?.unix_timestamp);
+    require!(season.phase == SeasonPhase::Cancelled, GorbageError::SeasonNotCancelled);
+    require!(!sponsorship.refunded, GorbageError::AlreadyRefunded);
+    
+    let refund_amount = sponsorship.amount;
+    
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    vault.withdraw(
+        &sponsor.to_account_info(),
+        ctx.accounts.sponsor_token_account.as_ref(),
+        refund_amount,
+    )?;
+    
+    season.sponsor_pool = season.sponsor_pool
+        .checked_sub(refund_amount)
+        .ok_or(GorbageError::InsufficientVaultFunds)?;
+    sponsorship.refunded = true;
+    
+    msg!(
+        "Refunded {} to sponsor {} for cancelled season {}",
+        refund_amount,
+        sponsor.key(),
+        season.season_number
+    );
+    emit!(SponsorRefunded {
+        season: season.key(),
+        season_number: season.season_number,
+        sponsor: sponsor.key(),
+        amount: refund_amount,
+        remaining_sponsor_pool: season.sponsor_pool,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED};
+use crate::error::GorbageError;
+use crate::events::ParticipantClosed;
+use crate::state::{Season, Participant};
+
+#[derive(Accounts)]
+pub struct CloseParticipant<'info> {
+    #[account(mut)]
+    pub participant_owner: Signer<'info>,
+    
+    #[account(
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), participant_owner.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.owner == participant_owner.key() @ GorbageError::Unauthorized,
+        constraint = participant.season == season.key() @ GorbageError::NotRegistered,
+        close = participant_owner
+    )]
+    pub participant: Account<'info, Participant>,
+}
+
+#[derive(Accounts)]
+pub struct CloseParticipants<'info> {
+    /// Anyone can crank rent back to participant owners
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+}
+
+/// Close a participant account once the season is settled and nothing is owed,
+/// returning the rent to the owner
+pub fn handler(ctx: Context<CloseParticipant>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        ctx.accounts.participant.is_closable(&ctx.accounts.season, clock.unix_timestamp),
+        GorbageError::ParticipantNotClosable
+    );
+    
+    msg!(
+        "Participant {} closed for season {}",
+        ctx.accounts.participant.owner,
+        ctx.accounts.season.season_number
+    );
+    emit!(ParticipantClosed {
+        season: ctx.accounts.season.key(),
+        season_number: ctx.accounts.season.season_number,
+        participant: ctx.accounts.participant.key(),
+        owner: ctx.accounts.participant.owner,
+    });
+    
+    Ok(())
+}
+
+/// Permissionless batch close. `remaining_accounts` holds (participant, owner)
+/// pairs; each participant must belong to the season and be closable, and its
+/// rent goes to the matching owner.
+pub fn close_participants<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseParticipants<'info>>,
+) -> Result<()> {
+    let season = &ctx.accounts.season;
+    let clock = Clock::get()?;
+    let remaining = ctx.remaining_accounts;
+    
+    let pairs = remaining.chunks_exact(2);
+    require!(
+        !remaining.is_empty() && pairs.remainder().is_empty(),
+        GorbageError::InvalidRemainingAccounts
+    );
+    
+    for pair in pairs {
+        let participant_info = &pair[0];
+        let owner_info = &pair[1];
+        
+        require!(
+            participant_info.is_writable && owner_info.is_writable,
+            GorbageError::InvalidRemainingAccounts
+        );
+        
+        let participant: Account<'info, Participant> = Account::try_from(participant_info)?;
+        require!(participant.season == season.key(), GorbageError::NotRegistered);
+        require_keys_eq!(owner_info.key(), participant.owner, GorbageError::Unauthorized);
+        require!(
+            participant.is_closable(season, clock.unix_timestamp),
+            GorbageError::ParticipantNotClosable
+        );
+        
+        participant.close(owner_info.clone())?;
+        emit!(ParticipantClosed {
+            season: season.key(),
+            season_number: season.season_number,
+            participant: participant.key(),
+            owner: participant.owner,
+        });
+    }
+    
+    msg!(
+        "Closed {} participant accounts for season {}",
+        remaining.len() / 2,
+        season.season_number
+    );
+    
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::constants::{SEASON_SEED, VAULT_SEED};
+use crate::constants::{SEASON_SEED, VAULT_SEED, SEASON_REGISTRY_SEED, CLAIM_BITMAP_SEED};
 use crate::error::GorbageError;
-use crate::state::Season;
+use crate::events::SeasonClosed;
+use crate::state::{Season, SeasonPhase, SeasonRegistry, UnclaimedPrizePolicy, ClaimBitmap};
+use crate::vault::SeasonVault;
 
 #[derive(Accounts)]
 pub struct CloseSeason<'info> {
@@ -15,11 +18,17 @@ pub struct CloseSeason<'info> {
     #[account(
         mut,
         seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
-        bump = season.bump,
-        close = authority
+        bump = season.bump
     )]
     pub season: Account<'info, Season>,
     
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
     /// CHECK: Vault PDA that holds remaining funds
     #[account(
         mut,
@@ -28,31 +37,192 @@ pub struct CloseSeason<'info> {
     )]
     pub vault: AccountInfo<'info>,
     
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Authority token account receiving the remaining balance, only for token-priced seasons
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Treasury receiving unclaimed prizes under the Treasury policy, and
+    /// under Rollover when that season can't take them
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury token account, only for token-priced seasons under the Treasury policy
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Season receiving unclaimed prizes under the Rollover policy
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, rollover_season.season_number.to_le_bytes().as_ref()],
+        bump = rollover_season.bump
+    )]
+    pub rollover_season: Option<Account<'info, Season>>,
+    
+    /// CHECK: Vault PDA of the rollover season, verified in the handler
+    #[account(mut)]
+    pub rollover_vault: Option<UncheckedAccount<'info>>,
+    
+    /// Rollover season vault token account, only for token-priced seasons
+    #[account(mut)]
+    pub rollover_vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Claim bitmap of the final results, only for Merkle settlements. Its rent
+    /// goes back to the authority that funded it.
+    #[account(
+        mut,
+        seeds = [
+            CLAIM_BITMAP_SEED,
+            season.key().as_ref(),
+            season.settlement_epoch.to_le_bytes().as_ref()
+        ],
+        bump = claim_bitmap.bump,
+        close = authority
+    )]
+    pub claim_bitmap: Option<Account<'info, ClaimBitmap>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
+/// Close a settled season. Prizes that are still unclaimed stay reserved until
+/// the claim deadline; after it they go to the treasury or roll over into the
+/// next season depending on the season's policy, falling back to the treasury when
+/// that season can't take them. Anything left goes to the authority.
 pub fn handler(ctx: Context<CloseSeason>) -> Result<()> {
-    let season = &ctx.accounts.season;
-    let vault = &ctx.accounts.vault;
+    let season = &mut ctx.accounts.season;
     let authority = &ctx.accounts.authority;
+    let clock = Clock::get()?;
     
     // Validations
-    require!(season.winners_set, GorbageError::WinnersNotSet);
-    require!(!season.is_active, GorbageError::SeasonStillActive);
+    match season.phase {
+        SeasonPhase::Settled => {}
+        SeasonPhase::Settling => return err!(GorbageError::PrizesNotAssigned),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersNotSet),
+    }
+    require!(
+        season.merkle_root.is_none() || ctx.accounts.claim_bitmap.is_some(),
+        GorbageError::ClaimBitmapRequired
+    );
+    
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    
+    // Unclaimed prizes stay reserved for winners until the claim deadline
+    let unclaimed = season.total_awarded
+        .checked_sub(season.total_claimed)
+        .ok_or(GorbageError::Overflow)?;
+    let mut unclaimed_recipient = None;
+    if unclaimed > 0 {
+        require!(
+            !season.is_claim_window_open(clock.unix_timestamp),
+            GorbageError::ClaimWindowOpen
+        );
+        
+        // As with rounding dust at finalization, a rollover season that can no
+        // longer take pool funds passes them to the treasury so the season can
+        // still be closed
+        let rollover = match season.unclaimed_policy {
+            UnclaimedPrizePolicy::Treasury => None,
+            UnclaimedPrizePolicy::Rollover { season_number } => {
+                let rollover_season = ctx.accounts.rollover_season
+                    .as_mut()
+                    .ok_or(GorbageError::RolloverSeasonRequired)?;
+                let rollover_vault = ctx.accounts.rollover_vault
+                    .as_ref()
+                    .ok_or(GorbageError::RolloverSeasonRequired)?;
+                require!(
+                    rollover_season.season_number == season_number,
+                    GorbageError::InvalidRolloverSeason
+                );
+                
+                let rent_exempt = season.mint.is_some()
+                    || rollover_vault.lamports()
+                        .checked_add(unclaimed)
+                        .ok_or(GorbageError::Overflow)?
+                        >= Rent::get()?.minimum_balance(0);
+                let takes_prizes = rollover_season.mint == season.mint
+                    && rollover_season.accepts_pool_funds()
+                    && rent_exempt;
+                takes_prizes.then_some((rollover_season, rollover_vault))
+            }
+        };
+        
+        match rollover {
+            Some((rollover_season, rollover_vault)) => {
+                let received = vault.roll_over(
+                    rollover_season,
+                    &rollover_vault.to_account_info(),
+                    ctx.accounts.rollover_vault_token_account.as_ref(),
+                    unclaimed,
+                )?;
+                unclaimed_recipient = Some(rollover_season.key());
+                
+                msg!(
+                    "Unclaimed prizes of {} rolled over into season {}",
+                    received,
+                    rollover_season.season_number
+                );
+            }
+            None => {
+                let treasury = ctx.accounts.treasury
+                    .as_ref()
+                    .ok_or(GorbageError::TreasuryRequired)?;
+                require_keys_eq!(treasury.key(), season.treasury, GorbageError::TreasuryMismatch);
+                
+                vault.withdraw(
+                    treasury,
+                    ctx.accounts.treasury_token_account.as_ref(),
+                    unclaimed,
+                )?;
+                unclaimed_recipient = Some(treasury.key());
+                
+                msg!("Unclaimed prizes of {} sent to treasury", unclaimed);
+            }
+        }
+    }
     
     // Transfer any remaining vault balance to authority
-    let remaining_balance = vault.lamports();
+    let remaining_balance = vault.balance()?;
     if remaining_balance > 0 {
-        **vault.try_borrow_mut_lamports()? = 0;
-        **authority.try_borrow_mut_lamports()? = authority
-            .lamports()
-            .checked_add(remaining_balance)
-            .ok_or(GorbageError::Overflow)?;
+        vault.withdraw(
+            &authority.to_account_info(),
+            ctx.accounts.authority_token_account.as_ref(),
+            remaining_balance,
+        )?;
         
         msg!("Remaining vault balance {} transferred to authority", remaining_balance);
     }
     
+    season.phase = SeasonPhase::Closed;
+    ctx.accounts.season_registry.update(season, clock.unix_timestamp);
+    
     msg!("Season {} closed", season.season_number);
+    emit!(SeasonClosed {
+        season: season.key(),
+        season_number: season.season_number,
+        total_awarded: season.total_awarded,
+        total_claimed: season.total_claimed,
+        unclaimed,
+        unclaimed_policy: season.unclaimed_policy,
+        unclaimed_recipient,
+        returned_to_authority: remaining_balance,
+    });
     
     Ok(())
 }
@@ -1,14 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::constants::{SEASON_SEED, VAULT_SEED, PLATFORM_FEE_BPS};
+use crate::constants::{SEASON_SEED, VAULT_SEED, SEASON_REGISTRY_SEED};
 use crate::error::GorbageError;
-use crate::state::Season;
+use crate::events::FeeCollected;
+use crate::payout;
+use crate::state::{Season, SeasonMode, SeasonPhase, SeasonRegistry};
+use crate::vault::SeasonVault;
 
 #[derive(Accounts)]
 pub struct CollectFee<'info> {
     /// Authority who can collect the fee (season authority)
     #[account(
+        mut,
         constraint = authority.key() == season.authority @ GorbageError::Unauthorized
     )]
     pub authority: Signer<'info>,
@@ -25,6 +29,13 @@ pub struct CollectFee<'info> {
     )]
     pub season: Account<'info, Season>,
     
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
     /// CHECK: Vault PDA that holds the prize pool
     #[account(
         mut,
@@ -33,77 +44,121 @@ pub struct CollectFee<'info> {
     )]
     pub vault: AccountInfo<'info>,
     
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury token account receiving the fee, only for token-priced seasons
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Authority token account receiving the unused guarantee, only for token-priced seasons
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
 /// Collect platform fee from the prize pool
-/// This should be called after registration ends (when season becomes active)
-/// The fee is 20% of the total prize pool, leaving 80% for winners
+/// This should be called after registration ends and moves the season from
+/// Registration to Active. The fee is 20% of the entry fees, leaving 80% for winners.
+/// Sponsor and rollover funds are not charged, and the guarantee deposit tops the pool up to
+/// `guaranteed_pool` with the unused part returned to the authority.
 pub fn handler(ctx: Context<CollectFee>) -> Result<()> {
     let season = &mut ctx.accounts.season;
-    let vault = &ctx.accounts.vault;
     let treasury = &ctx.accounts.treasury;
     let clock = Clock::get()?;
     
     // Validations
-    require!(!season.fee_collected, GorbageError::FeeAlreadyCollected);
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Registration => {}
+        SeasonPhase::Scheduled => return err!(GorbageError::RegistrationNotEnded),
+        SeasonPhase::Cancelled if season.is_below_minimum() => {
+            return err!(GorbageError::MinParticipantsNotMet)
+        }
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::FeeAlreadyCollected),
+    }
     require!(
         clock.unix_timestamp > season.registration_end,
         GorbageError::RegistrationNotEnded
     );
     require!(season.prize_pool > 0, GorbageError::NoPrizePool);
+    // Raffles fix their draw seed first, so a season whose secret is never
+    // revealed can still be cancelled without the treasury
+    require!(
+        season.mode != SeasonMode::Raffle || season.raffle_seed.is_some(),
+        GorbageError::RaffleNotRevealed
+    );
     
-    // Calculate 20% fee
+    // 20% fee on entry fees only, the guarantee covers any shortfall below guaranteed_pool
     let total_pool = season.prize_pool;
-    let fee_amount = total_pool
-        .checked_mul(PLATFORM_FEE_BPS)
-        .ok_or(GorbageError::Overflow)?
-        .checked_div(10000)
-        .ok_or(GorbageError::Overflow)?;
-    
-    // Check vault has enough balance
-    require!(
-        vault.lamports() >= fee_amount,
-        GorbageError::InsufficientVaultFunds
+    let split = payout::split_entries(
+        total_pool,
+        season.guaranteed_pool,
+        season.guarantee_deposit,
     );
+    let fee_amount = split.fee;
+    let overlay = split.overlay;
+    let unused_guarantee = split.unused_guarantee;
+    let new_prize_pool = payout::prize_pool(&split, season.sponsor_pool, season.rollover_pool)
+        .ok_or(GorbageError::Overflow)?;
     
-    // Transfer fee from vault PDA to treasury using invoke_signed
-    let season_key = season.key();
-    let vault_seeds: &[&[u8]] = &[
-        VAULT_SEED,
-        season_key.as_ref(),
-        &[season.vault_bump],
-    ];
-    
-    invoke_signed(
-        &system_instruction::transfer(
-            vault.key,
-            treasury.key,
-            fee_amount,
-        ),
-        &[
-            vault.to_account_info(),
-            treasury.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[vault_seeds],
+    // Transfer fee from vault PDA to treasury
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    vault.withdraw(
+        treasury,
+        ctx.accounts.treasury_token_account.as_ref(),
+        fee_amount,
     )?;
     
-    // Update season state
-    let new_prize_pool = total_pool
-        .checked_sub(fee_amount)
-        .ok_or(GorbageError::Overflow)?;
+    // Return the part of the guarantee the pool doesn't need
+    if unused_guarantee > 0 {
+        vault.withdraw(
+            &ctx.accounts.authority,
+            ctx.accounts.authority_token_account.as_ref(),
+            unused_guarantee,
+        )?;
+    }
     
+    // Update season state
     season.prize_pool = new_prize_pool;
-    season.fee_collected = true;
+    season.guarantee_deposit = overlay;
     season.fee_amount = fee_amount;
+    season.treasury = treasury.key();
+    season.phase = SeasonPhase::Active;
+    ctx.accounts.season_registry.update(season, clock.unix_timestamp);
     
     msg!(
-        "Platform fee collected: {} lamports to treasury. Prize pool updated from {} to {}",
+        "Platform fee collected: {} to treasury. Prize pool updated from {} to {}",
         fee_amount,
         total_pool,
         new_prize_pool
     );
+    emit!(FeeCollected {
+        season: season.key(),
+        season_number: season.season_number,
+        treasury: season.treasury,
+        fee_amount,
+        guarantee_overlay: overlay,
+        guarantee_returned: unused_guarantee,
+        prize_pool_before: total_pool,
+        prize_pool: new_prize_pool,
+    });
     
     Ok(())
 }
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, VAULT_SEED, SPONSORSHIP_SEED};
+use crate::error::GorbageError;
+use crate::events::SponsorContributed;
+use crate::state::{Season, SeasonPhase, Sponsorship};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct ContributeToPool<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = 8 + Sponsorship::INIT_SPACE,
+        seeds = [SPONSORSHIP_SEED, season.key().as_ref(), sponsor.key().as_ref()],
+        bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    
+    /// CHECK: Vault PDA that holds the prize pool
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Sponsor token account funding the contribution, only for token-priced seasons
+    #[account(mut)]
+    pub sponsor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Add sponsor funds to a season's prize pool. Contributions are exempt from
+/// the platform fee and are refunded if the season is cancelled.
+pub fn handler(ctx: Context<ContributeToPool>, amount: u64) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let sponsorship = &mut ctx.accounts.sponsorship;
+    let sponsor = &ctx.accounts.sponsor;
+    
+    // Validations
+    require!(amount > 0, GorbageError::InvalidContribution);
+    season.sync_phase(Clock::get()?.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => require!(season.accepts_pool_funds(), GorbageError::ContributionsClosed),
+    }
+    
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let received = vault.deposit(
+        &sponsor.to_account_info(),
+        ctx.accounts.sponsor_token_account.as_ref(),
+        amount,
+    )?;
+    
+    // Before fee collection sponsor funds are kept apart from the entry fees,
+    // afterwards they go straight into the prize pool
+    season.sponsor_pool = season.sponsor_pool
+        .checked_add(received)
+        .ok_or(GorbageError::Overflow)?;
+    if season.phase == SeasonPhase::Active {
+        season.prize_pool = season.prize_pool
+            .checked_add(received)
+            .ok_or(GorbageError::Overflow)?;
+    }
+    
+    if sponsorship.sponsor == Pubkey::default() {
+        sponsorship.sponsor = sponsor.key();
+        sponsorship.season = season.key();
+        sponsorship.amount = 0;
+        sponsorship.refunded = false;
+        sponsorship.bump = ctx.bumps.sponsorship;
+    }
+    sponsorship.amount = sponsorship.amount
+        .checked_add(received)
+        .ok_or(GorbageError::Overflow)?;
+    
+    msg!(
+        "Sponsor {} contributed {} to season {}",
+        sponsor.key(),
+        received,
+        season.season_number
+    );
+    emit!(SponsorContributed {
+        season: season.key(),
+        season_number: season.season_number,
+        sponsor: sponsor.key(),
+        amount: received,
+        sponsor_total: sponsorship.amount,
+        sponsor_pool: season.sponsor_pool,
+        prize_pool: season.prize_pool,
+    });
+    
+    Ok(())
+}
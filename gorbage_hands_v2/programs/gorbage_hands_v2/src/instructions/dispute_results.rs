@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, CONFIG_SEED, DISPUTE_SEED, SEASON_REGISTRY_SEED, CLAIM_BITMAP_SEED};
+use crate::error::GorbageError;
+use crate::events::{DisputeOpened, DisputeResolved, ResultsVoided};
+use crate::state::{Season, SeasonPhase, SeasonRegistry, Participant, ParticipantStatus, ProgramConfig, Dispute, ClaimBitmap};
+
+/// Admin decision on a dispute
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeResolution {
+    /// Results stand, the bond goes to the treasury
+    Uphold,
+    
+    /// Results are thrown out and the authority proposes new ones, the bond is returned
+    Void,
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), disputer.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.owner == disputer.key() @ GorbageError::Unauthorized,
+        constraint = participant.season == season.key() @ GorbageError::NotRegistered
+    )]
+    pub participant: Account<'info, Participant>,
+    
+    #[account(
+        init,
+        payer = disputer,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [
+            DISPUTE_SEED,
+            season.key().as_ref(),
+            season.settlement_epoch.to_le_bytes().as_ref(),
+            disputer.key().as_ref()
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        constraint = admin.key() == config.admin @ GorbageError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [
+            DISPUTE_SEED,
+            season.key().as_ref(),
+            dispute.settlement_epoch.to_le_bytes().as_ref(),
+            disputer.key().as_ref()
+        ],
+        bump = dispute.bump,
+        constraint = dispute.season == season.key() @ GorbageError::InvalidDispute,
+        close = disputer
+    )]
+    pub dispute: Account<'info, Dispute>,
+    
+    /// CHECK: Wallet that opened the dispute, receives the rent and any returned bond
+    #[account(
+        mut,
+        address = dispute.disputer @ GorbageError::InvalidDispute
+    )]
+    pub disputer: AccountInfo<'info>,
+    
+    /// CHECK: Treasury the platform fee was sent to, receives forfeited bonds
+    #[account(
+        mut,
+        address = season.treasury @ GorbageError::TreasuryMismatch
+    )]
+    pub treasury: AccountInfo<'info>,
+    
+    /// Season authority, gets back the claim bitmap rent when Merkle results are voided
+    #[account(
+        mut,
+        address = season.authority @ GorbageError::Unauthorized
+    )]
+    pub season_authority: SystemAccount<'info>,
+    
+    /// Claim bitmap of the disputed results, only for Merkle settlements
+    #[account(
+        mut,
+        seeds = [
+            CLAIM_BITMAP_SEED,
+            season.key().as_ref(),
+            season.settlement_epoch.to_le_bytes().as_ref()
+        ],
+        bump = claim_bitmap.bump
+    )]
+    pub claim_bitmap: Option<Account<'info, ClaimBitmap>>,
+}
+
+/// Dispute the proposed results of a season during its challenge window. Any
+/// active participant can open one dispute per proposal by posting the
+/// season's dispute bond. Prizes stay locked while a dispute is open.
+pub fn handler(ctx: Context<OpenDispute>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    // Validations
+    match season.phase {
+        SeasonPhase::Settling => {}
+        SeasonPhase::Settled => return err!(GorbageError::SeasonAlreadySettled),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersNotSet),
+    }
+    require!(
+        clock.unix_timestamp <= season.challenge_deadline,
+        GorbageError::ChallengeWindowClosed
+    );
+    // Refunded or disqualified participants have nothing at stake
+    require!(
+        ctx.accounts.participant.status == ParticipantStatus::Active,
+        GorbageError::ParticipantInactive
+    );
+    
+    let bond = season.dispute_bond;
+    if bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.disputer.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+    
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.season = season.key();
+    dispute.disputer = ctx.accounts.disputer.key();
+    dispute.settlement_epoch = season.settlement_epoch;
+    dispute.bond = bond;
+    dispute.opened_at = clock.unix_timestamp;
+    dispute.bump = ctx.bumps.dispute;
+    
+    season.open_disputes = season.open_disputes
+        .checked_add(1)
+        .ok_or(GorbageError::Overflow)?;
+    
+    msg!(
+        "Dispute opened by {} on season {} results (bond {})",
+        dispute.disputer,
+        season.season_number,
+        bond
+    );
+    emit!(DisputeOpened {
+        season: season.key(),
+        season_number: season.season_number,
+        settlement_epoch: dispute.settlement_epoch,
+        disputer: dispute.disputer,
+        bond,
+        open_disputes: season.open_disputes,
+    });
+    
+    Ok(())
+}
+
+/// Resolve a dispute (admin only). Upholding the results forfeits the bond to
+/// the treasury. Voiding them returns the bond, discards the proposed results,
+/// closes their claim bitmap and moves the season back to Active so the
+/// authority can propose again.
+/// Disputes whose results were already voided or whose season was cancelled
+/// are simply refunded.
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, resolution: DisputeResolution) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let dispute = &ctx.accounts.dispute;
+    
+    let is_live = season.phase == SeasonPhase::Settling
+        && dispute.settlement_epoch == season.settlement_epoch;
+    
+    if !is_live {
+        msg!("Stale dispute by {} refunded", dispute.disputer);
+        emit!(DisputeResolved {
+            season: season.key(),
+            season_number: season.season_number,
+            settlement_epoch: dispute.settlement_epoch,
+            disputer: dispute.disputer,
+            bond: dispute.bond,
+            bond_forfeited: 0,
+            open_disputes: season.open_disputes,
+        });
+        return Ok(());
+    }
+    
+    match resolution {
+        DisputeResolution::Uphold => {
+            // Bond goes to the treasury, the rent goes back to the disputer on close
+            let dispute_info = dispute.to_account_info();
+            **dispute_info.try_borrow_mut_lamports()? = dispute_info
+                .lamports()
+                .checked_sub(dispute.bond)
+                .ok_or(GorbageError::Overflow)?;
+            let treasury = &ctx.accounts.treasury;
+            **treasury.try_borrow_mut_lamports()? = treasury
+                .lamports()
+                .checked_add(dispute.bond)
+                .ok_or(GorbageError::Overflow)?;
+            
+            season.open_disputes = season.open_disputes
+                .checked_sub(1)
+                .ok_or(GorbageError::Overflow)?;
+            
+            msg!(
+                "Dispute by {} rejected, bond of {} forfeited to treasury",
+                dispute.disputer,
+                dispute.bond
+            );
+            emit!(DisputeResolved {
+                season: season.key(),
+                season_number: season.season_number,
+                settlement_epoch: dispute.settlement_epoch,
+                disputer: dispute.disputer,
+                bond: dispute.bond,
+                bond_forfeited: dispute.bond,
+                open_disputes: season.open_disputes,
+            });
+        }
+        DisputeResolution::Void => {
+            // Nothing can be claimed against voided Merkle results
+            if season.merkle_root.is_some() {
+                let claim_bitmap = ctx.accounts.claim_bitmap
+                    .as_ref()
+                    .ok_or(GorbageError::ClaimBitmapRequired)?;
+                claim_bitmap.close(ctx.accounts.season_authority.to_account_info())?;
+            }
+            
+            let settlement_epoch = season.settlement_epoch;
+            season.void_results()?;
+            ctx.accounts.season_registry.update(season, Clock::get()?.unix_timestamp);
+            
+            msg!(
+                "Season {} results voided after dispute by {}",
+                season.season_number,
+                dispute.disputer
+            );
+            emit!(ResultsVoided {
+                season: season.key(),
+                season_number: season.season_number,
+                settlement_epoch,
+                disputer: dispute.disputer,
+                bond: dispute.bond,
+            });
+        }
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, VAULT_SEED, WAITLIST_SEED};
+use crate::error::GorbageError;
+use crate::events::ParticipantDisqualified;
+use crate::state::{Season, SeasonPhase, SeasonMode, Participant, ParticipantStatus, Waitlist};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct DisqualifyParticipant<'info> {
+    #[account(
+        constraint = authority.key() == season.authority @ GorbageError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), participant.owner.as_ref()],
+        bump = participant.bump,
+        constraint = participant.season == season.key() @ GorbageError::NotRegistered
+    )]
+    pub participant: Account<'info, Participant>,
+    
+    /// CHECK: Receives the refunded entry fee, must be the participant owner
+    #[account(
+        mut,
+        address = participant.owner @ GorbageError::Unauthorized
+    )]
+    pub participant_owner: AccountInfo<'info>,
+    
+    /// CHECK: Vault PDA that holds the entry fees
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Waitlist for capped seasons, the freed spot is offered to the next wallet
+    #[account(
+        mut,
+        seeds = [WAITLIST_SEED, season.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Participant token account receiving the refund, only for token-priced seasons
+    #[account(mut)]
+    pub participant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Remove a participant (authority only).
+/// During registration the entry fee is refunded and the spot is offered to
+/// the waitlist. Once the season is active the entry fee stays in the pool.
+pub fn handler(ctx: Context<DisqualifyParticipant>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let participant = &mut ctx.accounts.participant;
+    let clock = Clock::get()?;
+    
+    // Validations
+    let during_registration = match season.phase {
+        SeasonPhase::Registration => true,
+        SeasonPhase::Active => false,
+        SeasonPhase::Scheduled => return err!(GorbageError::RegistrationNotOpen),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersAlreadySet),
+    };
+    // Draws skip inactive participants, so once the draw slot is committed a
+    // disqualification could steer which wallets the revealed seed picks
+    require!(
+        season.mode != SeasonMode::Raffle || season.raffle_slot == 0,
+        GorbageError::RaffleLocked
+    );
+    require!(
+        participant.status == ParticipantStatus::Active,
+        GorbageError::ParticipantInactive
+    );
+    
+    let refund_amount = if during_registration { participant.entry_fee_paid } else { 0 };
+    if during_registration {
+        let vault = SeasonVault::load(
+            season,
+            &ctx.accounts.vault,
+            &ctx.accounts.system_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.token_program,
+        )?;
+        vault.withdraw(
+            &ctx.accounts.participant_owner,
+            ctx.accounts.participant_token_account.as_ref(),
+            refund_amount,
+        )?;
+        
+        season.prize_pool = season.prize_pool
+            .checked_sub(refund_amount)
+            .ok_or(GorbageError::InsufficientVaultFunds)?;
+        season.participant_count = season.participant_count
+            .checked_sub(1)
+            .ok_or(GorbageError::Overflow)?;
+        participant.refunded = true;
+        
+        if season.is_capped() {
+            let waitlist = ctx.accounts.waitlist
+                .as_mut()
+                .ok_or(GorbageError::WaitlistRequired)?;
+            waitlist.refresh_offers(clock.unix_timestamp, season.open_spots());
+        }
+    }
+    
+    if !during_registration {
+        season.disqualified_count = season.disqualified_count
+            .checked_add(1)
+            .ok_or(GorbageError::Overflow)?;
+    }
+    
+    // A disqualified participant no longer counts towards score-based ranking
+    if participant.has_score() {
+        season.scored_count = season.scored_count
+            .checked_sub(1)
+            .ok_or(GorbageError::Overflow)?;
+        season.reset_ranking()?;
+    }
+    participant.status = ParticipantStatus::Disqualified;
+    
+    msg!(
+        "Participant {} disqualified from season {}",
+        participant.owner,
+        season.season_number
+    );
+    emit!(ParticipantDisqualified {
+        season: season.key(),
+        season_number: season.season_number,
+        owner: participant.owner,
+        refund: refund_amount,
+        participant_count: season.participant_count,
+        disqualified_count: season.disqualified_count,
+        prize_pool: season.prize_pool,
+    });
+    
+    Ok(())
+}
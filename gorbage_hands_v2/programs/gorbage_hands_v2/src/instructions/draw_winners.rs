@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::constants::{SEASON_SEED, SEASON_REGISTRY_SEED, RAFFLE_SLOT_DELAY};
+use crate::error::GorbageError;
+use crate::events::{RaffleRevealed, RaffleSlotLocked};
+use crate::instructions::settle_season::propose_results;
+use crate::raffle;
+use crate::state::{Season, SeasonPhase, SeasonMode, SeasonRegistry, Participant, ParticipantStatus};
+
+#[derive(Accounts)]
+pub struct LockRaffle<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+}
+
+#[derive(Accounts)]
+pub struct RevealRaffle<'info> {
+    #[account(
+        constraint = authority.key() == season.authority @ GorbageError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    /// CHECK: SlotHashes sysvar, holding the hash of the committed slot
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawWinners<'info> {
+    #[account(
+        constraint = authority.key() == season.authority @ GorbageError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+}
+
+/// Commit a raffle to a slot a little in the future once registration has
+/// closed (anyone can call). Nobody knows that slot's hash yet, so the authority
+/// can't time the reveal to pick the seed.
+pub fn lock_raffle(ctx: Context<LockRaffle>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    // Validations
+    require!(season.mode == SeasonMode::Raffle, GorbageError::RaffleModeRequired);
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Registration => {}
+        SeasonPhase::Scheduled => return err!(GorbageError::RegistrationNotEnded),
+        SeasonPhase::Cancelled if season.is_below_minimum() => {
+            return err!(GorbageError::MinParticipantsNotMet)
+        }
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::RaffleAlreadyRevealed),
+    }
+    require!(
+        clock.unix_timestamp > season.registration_end,
+        GorbageError::RegistrationNotEnded
+    );
+    require!(season.raffle_slot == 0, GorbageError::RaffleAlreadyLocked);
+    
+    season.raffle_slot = clock.slot
+        .checked_add(RAFFLE_SLOT_DELAY)
+        .ok_or(GorbageError::Overflow)?;
+    
+    msg!(
+        "Raffle for season {} locked to slot {}",
+        season.season_number,
+        season.raffle_slot
+    );
+    emit!(RaffleSlotLocked {
+        season: season.key(),
+        season_number: season.season_number,
+        raffle_slot: season.raffle_slot,
+    });
+    
+    Ok(())
+}
+
+/// Reveal the raffle secret committed at season creation, once the slot the
+/// raffle was locked to has passed and before the platform fee is collected.
+/// The draw seed mixes the secret with that slot's hash, so neither the
+/// authority nor the cluster alone decides the outcome. After
+/// `RAFFLE_REVEAL_WINDOW` slots the secret can no longer be revealed and
+/// anyone can cancel the season.
+pub fn handler(ctx: Context<RevealRaffle>, secret: [u8; 32]) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    // Validations
+    require!(season.mode == SeasonMode::Raffle, GorbageError::RaffleModeRequired);
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {}
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::RaffleAlreadyRevealed),
+    }
+    require!(season.raffle_seed.is_none(), GorbageError::RaffleAlreadyRevealed);
+    require!(season.raffle_slot != 0, GorbageError::RaffleNotLocked);
+    require!(clock.slot > season.raffle_slot, GorbageError::RaffleSlotPending);
+    require!(
+        !season.is_raffle_reveal_expired(clock.slot),
+        GorbageError::RaffleRevealExpired
+    );
+    require!(
+        raffle::commitment(&secret) == season.raffle_commitment,
+        GorbageError::InvalidRaffleSecret
+    );
+    
+    let slot_hashes = ctx.accounts.slot_hashes.try_borrow_data()?;
+    let slot_hash = raffle::find_slot_hash(&slot_hashes, season.raffle_slot)
+        .ok_or(GorbageError::RaffleRevealExpired)?;
+    
+    let raffle_seed = raffle::raffle_seed(&secret, &slot_hash, &season.key());
+    season.raffle_seed = Some(raffle_seed);
+    season.raffle_slot_hash = slot_hash;
+    season.raffle_secret = secret;
+    
+    msg!("Raffle secret revealed for season {}", season.season_number);
+    emit!(RaffleRevealed {
+        season: season.key(),
+        season_number: season.season_number,
+        raffle_slot: season.raffle_slot,
+        raffle_slot_hash: slot_hash,
+        raffle_seed,
+    });
+    
+    Ok(())
+}
+
+/// Propose raffle winners. `remaining_accounts` holds the Participant accounts
+/// hit by each draw of `raffle::draw_index` in order, including draws that land
+/// on inactive or already drawn participants; the program recomputes every
+/// draw and takes the first active participants as winners. Winners must be
+/// writable. Clients resolve each drawn index to a wallet via `ParticipantPage`.
+pub fn draw_winners<'info>(ctx: Context<'_, '_, 'info, 'info, DrawWinners<'info>>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    // Validations
+    match season.phase {
+        SeasonPhase::Active => {}
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {
+            return err!(GorbageError::FeeNotCollected)
+        }
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersAlreadySet),
+    }
+    require!(season.mode == SeasonMode::Raffle, GorbageError::RaffleModeRequired);
+    require!(season.has_ended(clock.unix_timestamp), GorbageError::SeasonNotEnded);
+    let seed = season.raffle_seed.ok_or(GorbageError::RaffleNotRevealed)?;
+    
+    let eligible = season.participant_count.saturating_sub(season.disqualified_count);
+    let winner_target = (season.payout_bps.len() as u64).min(eligible) as usize;
+    require!(winner_target > 0, GorbageError::InvalidWinnerCount);
+    
+    let season_key = season.key();
+    let participant_total = season.next_participant_index;
+    let mut winners: Vec<Account<'info, Participant>> = Vec::with_capacity(winner_target);
+    
+    for (attempt, participant_info) in ctx.remaining_accounts.iter().enumerate() {
+        require!(winners.len() < winner_target, GorbageError::InvalidDrawSequence);
+        
+        let participant: Account<'info, Participant> = Account::try_from(participant_info)?;
+        require!(participant.season == season_key, GorbageError::NotRegistered);
+        require!(
+            participant.index == raffle::draw_index(&seed, attempt as u32, participant_total),
+            GorbageError::InvalidDrawSequence
+        );
+        
+        let already_drawn = winners.iter().any(|winner| winner.key() == participant.key());
+        if participant.status == ParticipantStatus::Active && !already_drawn {
+            winners.push(participant);
+        }
+    }
+    require!(winners.len() == winner_target, GorbageError::InvalidDrawSequence);
+    
+    propose_results(season, &mut winners, ctx.program_id, clock.unix_timestamp)?;
+    ctx.accounts.season_registry.update(season, clock.unix_timestamp);
+    
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, CONFIG_SEED, ORACLE_REGISTRY_SEED, VAULT_SEED, SEASON_REGISTRY_SEED};
+use crate::error::GorbageError;
+use crate::events::SettlementFinalized;
+use crate::state::{Season, SeasonPhase, SeasonRegistry, ProgramConfig, OracleRegistry, DustPolicy};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [ORACLE_REGISTRY_SEED, config.key().as_ref()],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
+    /// CHECK: Vault PDA holding the prize pool
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Treasury receiving rounding dust under the ToTreasury policy, and
+    /// under ToNextSeason when that season can't take it
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    
+    /// Treasury token account, only for token-priced seasons
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Season receiving rounding dust under the ToNextSeason policy
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, rollover_season.season_number.to_le_bytes().as_ref()],
+        bump = rollover_season.bump
+    )]
+    pub rollover_season: Option<Account<'info, Season>>,
+    
+    /// CHECK: Vault PDA of the rollover season, verified in the handler
+    #[account(mut)]
+    pub rollover_vault: Option<UncheckedAccount<'info>>,
+    
+    /// Rollover season vault token account, only for token-priced seasons
+    #[account(mut)]
+    pub rollover_vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Make proposed results final so prizes can be claimed (anyone can call).
+/// Requires the attestation threshold to be met and the challenge window to
+/// have closed with no open disputes. Rounding dust left unassigned by the
+/// settlement leaves the vault here, so the vault ends up holding exactly the
+/// awarded prizes.
+pub fn handler(ctx: Context<FinalizeSettlement>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let oracle_registry = &ctx.accounts.oracle_registry;
+    let clock = Clock::get()?;
+    
+    // Validations
+    match season.phase {
+        SeasonPhase::Settling => {}
+        SeasonPhase::Settled => return err!(GorbageError::SeasonAlreadySettled),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersNotSet),
+    }
+    require!(
+        clock.unix_timestamp > season.challenge_deadline,
+        GorbageError::ChallengeWindowOpen
+    );
+    require!(season.open_disputes == 0, GorbageError::OpenDisputes);
+    
+    // Attestors removed from the registry since attesting no longer count
+    let attestation_count = season.attestations
+        .iter()
+        .filter(|attestor| oracle_registry.contains(attestor))
+        .count();
+    require!(
+        attestation_count >= oracle_registry.threshold as usize,
+        GorbageError::NotEnoughAttestations
+    );
+    
+    let dust = season.dust_amount;
+    let mut dust_recipient = None;
+    if dust > 0 {
+        let vault = SeasonVault::load(
+            season,
+            &ctx.accounts.vault,
+            &ctx.accounts.system_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.token_program,
+        )?;
+        
+        // A season that can no longer take pool funds, or whose empty native
+        // vault the dust alone would leave below rent exemption, passes it to
+        // the treasury so finalization can't get stuck
+        let rollover = match season.dust_policy {
+            // First place already holds its dust, so only ToTreasury gets here
+            DustPolicy::ToFirstPlace | DustPolicy::ToTreasury => None,
+            DustPolicy::ToNextSeason { season_number } => {
+                let rollover_season = ctx.accounts.rollover_season
+                    .as_mut()
+                    .ok_or(GorbageError::RolloverSeasonRequired)?;
+                let rollover_vault = ctx.accounts.rollover_vault
+                    .as_ref()
+                    .ok_or(GorbageError::RolloverSeasonRequired)?;
+                require!(
+                    rollover_season.season_number == season_number,
+                    GorbageError::InvalidRolloverSeason
+                );
+                
+                let rent_exempt = season.mint.is_some()
+                    || rollover_vault.lamports()
+                        .checked_add(dust)
+                        .ok_or(GorbageError::Overflow)?
+                        >= Rent::get()?.minimum_balance(0);
+                let takes_dust = rollover_season.mint == season.mint
+                    && rollover_season.accepts_pool_funds()
+                    && rent_exempt;
+                takes_dust.then_some((rollover_season, rollover_vault))
+            }
+        };
+        
+        match rollover {
+            Some((rollover_season, rollover_vault)) => {
+                let received = vault.roll_over(
+                    rollover_season,
+                    &rollover_vault.to_account_info(),
+                    ctx.accounts.rollover_vault_token_account.as_ref(),
+                    dust,
+                )?;
+                dust_recipient = Some(rollover_season.key());
+                
+                msg!(
+                    "Rounding dust of {} rolled over into season {}",
+                    received,
+                    rollover_season.season_number
+                );
+            }
+            None => {
+                let treasury = ctx.accounts.treasury
+                    .as_ref()
+                    .ok_or(GorbageError::TreasuryRequired)?;
+                require_keys_eq!(treasury.key(), season.treasury, GorbageError::TreasuryMismatch);
+                
+                vault.withdraw(
+                    treasury,
+                    ctx.accounts.treasury_token_account.as_ref(),
+                    dust,
+                )?;
+                dust_recipient = Some(treasury.key());
+                
+                msg!("Rounding dust of {} sent to treasury", dust);
+            }
+        }
+    }
+    
+    season.phase = SeasonPhase::Settled;
+    ctx.accounts.season_registry.update(season, clock.unix_timestamp);
+    
+    msg!(
+        "Season {} settled: {} winners, {} awarded, {} dust",
+        season.season_number,
+        season.winner_count,
+        season.total_awarded,
+        season.dust_amount
+    );
+    emit!(SettlementFinalized {
+        season: season.key(),
+        season_number: season.season_number,
+        settlement_epoch: season.settlement_epoch,
+        winner_count: season.winner_count,
+        total_awarded: season.total_awarded,
+        dust,
+        dust_policy: season.dust_policy,
+        dust_recipient,
+    });
+    
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::CONFIG_SEED;
-use crate::state::ProgramConfig;
+use crate::constants::{CONFIG_SEED, SEASON_REGISTRY_SEED};
+use crate::events::ConfigInitialized;
+use crate::state::{ProgramConfig, SeasonRegistry};
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
@@ -17,6 +18,15 @@ pub struct InitializeConfig<'info> {
     )]
     pub config: Account<'info, ProgramConfig>,
     
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + SeasonRegistry::INIT_SPACE,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -25,8 +35,18 @@ pub struct InitializeConfig<'info> {
 pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
     let config = &mut ctx.accounts.config;
     config.admin = ctx.accounts.admin.key();
+    config.game_server = Pubkey::default();
+    config.next_season_number = 1;
     config.bump = ctx.bumps.config;
     
+    let season_registry = &mut ctx.accounts.season_registry;
+    season_registry.seasons = Vec::new();
+    season_registry.bump = ctx.bumps.season_registry;
+    
     msg!("Program config initialized. Admin: {}", config.admin);
+    emit!(ConfigInitialized {
+        config: config.key(),
+        admin: config.admin,
+    });
     Ok(())
 }
@@ -1,17 +1,53 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::constants::{SEASON_SEED, VAULT_SEED, CONFIG_SEED, MAX_SEASON_NAME_LEN};
+use crate::constants::{SEASON_SEED, VAULT_SEED, CONFIG_SEED, WAITLIST_SEED, SEASON_REGISTRY_SEED, MAX_SEASON_NAME_LEN, MAX_WINNERS, BPS_DENOMINATOR};
 use crate::error::GorbageError;
-use crate::state::{Season, ProgramConfig};
+use crate::events::SeasonCreated;
+use crate::state::{Season, SeasonPhase, SeasonMode, SeasonRegistry, ProgramConfig, Waitlist, UnclaimedPrizePolicy, DustPolicy};
+use crate::vault::SeasonVault;
+
+/// Season settings supplied by the admin at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeSeasonParams {
+    pub name: String,
+    pub entry_fee: u64,
+    pub registration_start: i64,
+    pub registration_end: i64,
+    pub season_end: i64,
+    /// Prize share per placement in basis points, must sum to 10000
+    pub payout_bps: Vec<u16>,
+    /// Participants required by registration_end, otherwise the season is refunded
+    pub min_participants: u64,
+    /// Participant cap (0 = unlimited), sign-ups beyond it go to the waitlist
+    pub max_participants: u64,
+    /// Minimum post-fee prize pool, funded up front by the authority (0 = none)
+    pub guaranteed_pool: u64,
+    /// Winners must claim by this timestamp
+    pub claim_deadline: i64,
+    /// Where prizes unclaimed at the deadline go
+    pub unclaimed_policy: UnclaimedPrizePolicy,
+    /// Where the rounding dust of the settlement goes
+    pub dust_policy: DustPolicy,
+    /// Seconds participants have to dispute proposed results
+    pub challenge_window: i64,
+    /// Lamports a participant posts to open a dispute
+    pub dispute_bond: u64,
+    /// Contest or raffle
+    pub mode: SeasonMode,
+    /// `sha256(secret)` of the raffle secret, required for raffles
+    pub raffle_commitment: [u8; 32],
+}
 
 #[derive(Accounts)]
-#[instruction(season_number: u64)]
 pub struct InitializeSeason<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    /// Program config - verifies caller is the global admin
+    /// Program config - verifies caller is the global admin and numbers the season
     #[account(
+        mut,
         seeds = [CONFIG_SEED],
         bump = config.bump,
         constraint = config.admin == authority.key() @ GorbageError::Unauthorized
@@ -22,34 +58,131 @@ pub struct InitializeSeason<'info> {
         init,
         payer = authority,
         space = 8 + Season::INIT_SPACE,
-        seeds = [SEASON_SEED, season_number.to_le_bytes().as_ref()],
+        seeds = [SEASON_SEED, config.next_season_number.to_le_bytes().as_ref()],
         bump
     )]
     pub season: Account<'info, Season>,
     
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
     /// CHECK: Vault PDA to hold prize pool funds
     #[account(
+        mut,
         seeds = [VAULT_SEED, season.key().as_ref()],
         bump
     )]
     pub vault: SystemAccount<'info>,
     
+    /// Waitlist queue, only created for seasons with a participant cap
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Waitlist::INIT_SPACE,
+        seeds = [WAITLIST_SEED, season.key().as_ref()],
+        bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+    
+    /// Mint for token-priced seasons, omit for native lamport seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's associated token account, created for token-priced seasons
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Authority token account funding the guaranteed pool, only for token-priced seasons
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<InitializeSeason>,
-    season_number: u64,
-    name: String,
-    entry_fee: u64,
-    registration_start: i64,
-    registration_end: i64,
-    season_end: i64,
-) -> Result<()> {
+/// Create the next season, numbered from `ProgramConfig::next_season_number`
+pub fn handler(ctx: Context<InitializeSeason>, params: InitializeSeasonParams) -> Result<()> {
+    let season_number = ctx.accounts.config.next_season_number;
+    let InitializeSeasonParams {
+        name,
+        entry_fee,
+        registration_start,
+        registration_end,
+        season_end,
+        payout_bps,
+        min_participants,
+        max_participants,
+        guaranteed_pool,
+        claim_deadline,
+        unclaimed_policy,
+        dust_policy,
+        challenge_window,
+        dispute_bond,
+        mode,
+        raffle_commitment,
+    } = params;
+    
     require!(name.len() <= MAX_SEASON_NAME_LEN, GorbageError::SeasonNameTooLong);
     require!(entry_fee > 0, GorbageError::InvalidEntryFee);
     require!(registration_start < registration_end, GorbageError::InvalidEntryFee);
     require!(registration_end < season_end, GorbageError::InvalidEntryFee);
+    require!(challenge_window >= 0, GorbageError::InvalidChallengeWindow);
+    require!(
+        challenge_window == 0 || dispute_bond > 0,
+        GorbageError::InvalidDisputeBond
+    );
+    let earliest_claim = season_end
+        .checked_add(challenge_window)
+        .ok_or(GorbageError::Overflow)?;
+    require!(earliest_claim < claim_deadline, GorbageError::InvalidClaimDeadline);
+    if let UnclaimedPrizePolicy::Rollover { season_number: target } = unclaimed_policy {
+        require!(target != season_number, GorbageError::InvalidRolloverSeason);
+    }
+    if let DustPolicy::ToNextSeason { season_number: target } = dust_policy {
+        require!(target != season_number, GorbageError::InvalidRolloverSeason);
+    }
+    
+    // Raffles commit to their secret before registration opens
+    if mode == SeasonMode::Raffle {
+        require!(raffle_commitment != [0; 32], GorbageError::InvalidRaffleCommitment);
+    }
+    
+    // Payout schedule: one non-zero entry per placement, summing to 100%
+    require!(
+        !payout_bps.is_empty() && payout_bps.len() <= MAX_WINNERS,
+        GorbageError::InvalidPayoutSchedule
+    );
+    require!(payout_bps.iter().all(|bps| *bps > 0), GorbageError::InvalidPayoutSchedule);
+    let total_bps: u64 = payout_bps.iter().map(|bps| *bps as u64).sum();
+    require!(total_bps == BPS_DENOMINATOR, GorbageError::PayoutScheduleSumMismatch);
+    
+    // Capped seasons need a waitlist to queue sign-ups beyond the cap
+    require!(
+        max_participants == 0 || max_participants >= min_participants,
+        GorbageError::InvalidParticipantLimits
+    );
+    require!(
+        (max_participants > 0) == ctx.accounts.waitlist.is_some(),
+        GorbageError::WaitlistRequired
+    );
+    
+    // Token-priced seasons need the vault token account alongside the mint
+    require!(
+        ctx.accounts.mint.is_some() == ctx.accounts.vault_token_account.is_some(),
+        GorbageError::TokenAccountsRequired
+    );
     
     let season = &mut ctx.accounts.season;
     
@@ -58,20 +191,109 @@ pub fn handler(
     season.name = name;
     season.entry_fee = entry_fee;
     season.prize_pool = 0;
+    season.sponsor_pool = 0;
+    season.guaranteed_pool = guaranteed_pool;
+    season.guarantee_deposit = 0;
+    season.rollover_pool = 0;
     season.participant_count = 0;
+    season.next_participant_index = 0;
+    season.disqualified_count = 0;
+    season.scored_count = 0;
+    // Round 0 is what unranked participants hold
+    season.ranking_round = 1;
+    season.ranked_count = 0;
+    season.leaderboard = Vec::new();
+    season.min_participants = min_participants;
+    season.max_participants = max_participants;
     season.registration_start = registration_start;
     season.registration_end = registration_end;
     season.season_end = season_end;
-    season.is_active = true;
-    season.winners_set = false;
-    season.winners = [Pubkey::default(); 3];
+    season.claim_deadline = claim_deadline;
+    season.unclaimed_policy = unclaimed_policy;
+    season.dust_policy = dust_policy;
+    season.phase = SeasonPhase::Scheduled;
+    season.payout_bps = payout_bps;
+    season.winners = Vec::new();
     season.winner_count = 0;
+    season.total_awarded = 0;
+    season.dust_amount = 0;
+    season.total_claimed = 0;
     season.bump = ctx.bumps.season;
     season.vault_bump = ctx.bumps.vault;
-    season.fee_collected = false;
+    season.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+    season.vault_token_account = ctx.accounts.vault_token_account
+        .as_ref()
+        .map(|account| account.key())
+        .unwrap_or_default();
     season.fee_amount = 0;
+    season.treasury = Pubkey::default();
+    season.merkle_root = None;
+    season.merkle_leaf_count = 0;
+    season.result_hash = [0; 32];
+    season.attestations = Vec::new();
+    season.challenge_window = challenge_window;
+    season.dispute_bond = dispute_bond;
+    season.challenge_deadline = 0;
+    season.open_disputes = 0;
+    season.settlement_epoch = 0;
+    season.mode = mode;
+    season.raffle_commitment = raffle_commitment;
+    season.raffle_secret = [0; 32];
+    season.raffle_slot = 0;
+    season.raffle_slot_hash = [0; 32];
+    season.raffle_seed = None;
+    season.sync_phase(Clock::get()?.unix_timestamp);
+    
+    if let Some(waitlist) = ctx.accounts.waitlist.as_mut() {
+        waitlist.season = season.key();
+        waitlist.queue = Vec::new();
+        waitlist.offers = Vec::new();
+        waitlist.bump = ctx.bumps.waitlist.ok_or(GorbageError::WaitlistRequired)?;
+    }
+    
+    // Fund the guarantee up front; whatever the entries cover is returned at fee collection
+    if guaranteed_pool > 0 {
+        let vault = SeasonVault::load(
+            season,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.token_program,
+        )?;
+        season.guarantee_deposit = vault.deposit(
+            &ctx.accounts.authority.to_account_info(),
+            ctx.accounts.authority_token_account.as_ref(),
+            vault.gross_up(guaranteed_pool)?,
+        )?;
+    }
+    
+    ctx.accounts.season_registry.update(season, Clock::get()?.unix_timestamp);
+    ctx.accounts.config.next_season_number = season_number
+        .checked_add(1)
+        .ok_or(GorbageError::Overflow)?;
     
     msg!("Season {} initialized: {}", season_number, season.name);
+    emit!(SeasonCreated {
+        season: season.key(),
+        season_number,
+        authority: season.authority,
+        name: season.name.clone(),
+        mode: season.mode,
+        mint: season.mint,
+        entry_fee: season.entry_fee,
+        payout_bps: season.payout_bps.clone(),
+        min_participants: season.min_participants,
+        max_participants: season.max_participants,
+        guaranteed_pool: season.guaranteed_pool,
+        guarantee_deposit: season.guarantee_deposit,
+        registration_start: season.registration_start,
+        registration_end: season.registration_end,
+        season_end: season.season_end,
+        claim_deadline: season.claim_deadline,
+        unclaimed_policy: season.unclaimed_policy,
+        dust_policy: season.dust_policy,
+    });
     
     Ok(())
 }
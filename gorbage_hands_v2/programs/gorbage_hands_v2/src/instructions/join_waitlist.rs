@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, WAITLIST_SEED, MAX_WAITLIST_LEN};
+use crate::error::GorbageError;
+use crate::events::WaitlistJoined;
+use crate::state::{Season, SeasonPhase, Waitlist};
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    pub wallet: Signer<'info>,
+    
+    #[account(
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    /// CHECK: Participant PDA for this wallet, must not exist yet
+    #[account(
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub participant: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [WAITLIST_SEED, season.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+}
+
+/// Queue a wallet for a full season. When a spot frees up it is offered to
+/// the front of the queue, who then has WAITLIST_CONFIRMATION_WINDOW to register.
+pub fn handler(ctx: Context<JoinWaitlist>) -> Result<()> {
+    let season = &ctx.accounts.season;
+    let waitlist = &mut ctx.accounts.waitlist;
+    let wallet = ctx.accounts.wallet.key();
+    let clock = Clock::get()?;
+    
+    // Validations
+    match season.phase {
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {}
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::RegistrationClosed),
+    }
+    require!(
+        clock.unix_timestamp <= season.registration_end,
+        GorbageError::RegistrationClosed
+    );
+    require!(
+        ctx.accounts.participant.data_is_empty(),
+        GorbageError::AlreadyRegistered
+    );
+    require!(!waitlist.contains(&wallet), GorbageError::AlreadyOnWaitlist);
+    
+    let open_spots = season.open_spots();
+    waitlist.refresh_offers(clock.unix_timestamp, open_spots);
+    require!(open_spots <= waitlist.reserved_spots(), GorbageError::SpotsAvailable);
+    require!(waitlist.queue.len() < MAX_WAITLIST_LEN, GorbageError::WaitlistFull);
+    
+    waitlist.queue.push(wallet);
+    
+    msg!(
+        "Wallet {} joined the waitlist for season {} at position {}",
+        wallet,
+        season.season_number,
+        waitlist.queue.len()
+    );
+    emit!(WaitlistJoined {
+        season: season.key(),
+        season_number: season.season_number,
+        wallet,
+        position: waitlist.queue.len() as u32,
+    });
+    
+    Ok(())
+}
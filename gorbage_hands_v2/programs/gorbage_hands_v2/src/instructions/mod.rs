@@ -1,17 +1,60 @@
+// Every instruction module exports its own `handler`; lib.rs calls them by path
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_config;
 pub mod initialize_season;
 pub mod register_participant;
-pub mod set_winners;
+pub mod settle_season;
 pub mod claim_prize;
 pub mod close_season;
 pub mod transfer_admin;
 pub mod collect_fee;
+pub mod cancel_season;
+pub mod claim_refund;
+pub mod join_waitlist;
+pub mod withdraw_participant;
+pub mod disqualify_participant;
+pub mod contribute_to_pool;
+pub mod claim_sponsor_refund;
+pub mod close_participant;
+pub mod settle_season_with_root;
+pub mod claim_prize_with_proof;
+pub mod set_game_server;
+pub mod submit_score;
+pub mod submit_attested_score;
+pub mod oracle_registry;
+pub mod attest_results;
+pub mod dispute_results;
+pub mod finalize_settlement;
+pub mod draw_winners;
+pub mod refresh_season_registry;
+pub mod participant_pages;
 
 pub use initialize_config::*;
 pub use initialize_season::*;
 pub use register_participant::*;
-pub use set_winners::*;
+pub use settle_season::*;
 pub use claim_prize::*;
 pub use close_season::*;
 pub use transfer_admin::*;
 pub use collect_fee::*;
+pub use cancel_season::*;
+pub use claim_refund::*;
+pub use join_waitlist::*;
+pub use withdraw_participant::*;
+pub use disqualify_participant::*;
+pub use contribute_to_pool::*;
+pub use claim_sponsor_refund::*;
+pub use close_participant::*;
+pub use settle_season_with_root::*;
+pub use claim_prize_with_proof::*;
+pub use set_game_server::*;
+pub use submit_score::*;
+pub use submit_attested_score::*;
+pub use oracle_registry::*;
+pub use attest_results::*;
+pub use dispute_results::*;
+pub use finalize_settlement::*;
+pub use draw_winners::*;
+pub use refresh_season_registry::*;
+pub use participant_pages::*;
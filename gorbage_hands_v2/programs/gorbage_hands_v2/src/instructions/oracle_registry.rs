@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, ORACLE_REGISTRY_SEED, MAX_ATTESTORS};
+use crate::error::GorbageError;
+use crate::events::OracleRegistryUpdated;
+use crate::state::{ProgramConfig, OracleRegistry};
+
+#[derive(Accounts)]
+pub struct InitializeOracleRegistry<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == config.admin @ GorbageError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + OracleRegistry::INIT_SPACE,
+        seeds = [ORACLE_REGISTRY_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOracleRegistry<'info> {
+    #[account(
+        constraint = admin.key() == config.admin @ GorbageError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [ORACLE_REGISTRY_SEED, config.key().as_ref()],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+}
+
+/// Create the oracle registry with its initial attestors and threshold (admin only)
+pub fn handler(
+    ctx: Context<InitializeOracleRegistry>,
+    attestors: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(attestors.len() <= MAX_ATTESTORS, GorbageError::InvalidAttestorSet);
+    
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    oracle_registry.attestors = attestors;
+    oracle_registry.threshold = threshold;
+    oracle_registry.bump = ctx.bumps.oracle_registry;
+    require!(oracle_registry.is_valid(), GorbageError::InvalidAttestorSet);
+    
+    msg!(
+        "Oracle registry initialized: {} of {} attestors",
+        threshold,
+        oracle_registry.attestors.len()
+    );
+    emit_registry_update(oracle_registry);
+    Ok(())
+}
+
+/// Add an attestor (admin only)
+pub fn add_attestor(ctx: Context<UpdateOracleRegistry>, attestor: Pubkey) -> Result<()> {
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    require!(
+        oracle_registry.attestors.len() < MAX_ATTESTORS,
+        GorbageError::InvalidAttestorSet
+    );
+    
+    oracle_registry.attestors.push(attestor);
+    require!(oracle_registry.is_valid(), GorbageError::InvalidAttestorSet);
+    
+    msg!("Attestor {} added", attestor);
+    emit_registry_update(oracle_registry);
+    Ok(())
+}
+
+/// Remove an attestor (admin only). The remaining set must still meet the threshold.
+pub fn remove_attestor(ctx: Context<UpdateOracleRegistry>, attestor: Pubkey) -> Result<()> {
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    let index = oracle_registry.attestors
+        .iter()
+        .position(|existing| *existing == attestor)
+        .ok_or(GorbageError::NotAnAttestor)?;
+    
+    oracle_registry.attestors.remove(index);
+    require!(oracle_registry.is_valid(), GorbageError::InvalidAttestorSet);
+    
+    msg!("Attestor {} removed", attestor);
+    emit_registry_update(oracle_registry);
+    Ok(())
+}
+
+/// Replace an attestor key in place, e.g. after a key compromise (admin only)
+pub fn rotate_attestor(
+    ctx: Context<UpdateOracleRegistry>,
+    old_attestor: Pubkey,
+    new_attestor: Pubkey,
+) -> Result<()> {
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    let slot = oracle_registry.attestors
+        .iter_mut()
+        .find(|existing| **existing == old_attestor)
+        .ok_or(GorbageError::NotAnAttestor)?;
+    
+    *slot = new_attestor;
+    require!(oracle_registry.is_valid(), GorbageError::InvalidAttestorSet);
+    
+    msg!("Attestor {} rotated to {}", old_attestor, new_attestor);
+    emit_registry_update(oracle_registry);
+    Ok(())
+}
+
+/// Change how many distinct attestors must confirm results (admin only)
+pub fn set_attestation_threshold(ctx: Context<UpdateOracleRegistry>, threshold: u8) -> Result<()> {
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    oracle_registry.threshold = threshold;
+    require!(oracle_registry.is_valid(), GorbageError::InvalidAttestorSet);
+    
+    msg!("Attestation threshold set to {}", threshold);
+    emit_registry_update(oracle_registry);
+    Ok(())
+}
+
+/// Log the attestor set and threshold as they stand after a change
+fn emit_registry_update(oracle_registry: &Account<OracleRegistry>) {
+    emit!(OracleRegistryUpdated {
+        oracle_registry: oracle_registry.key(),
+        attestors: oracle_registry.attestors.clone(),
+        threshold: oracle_registry.threshold,
+    });
+}
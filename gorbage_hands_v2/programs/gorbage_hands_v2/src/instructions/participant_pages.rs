@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_PAGE_SEED};
+use crate::error::GorbageError;
+use crate::events::{ParticipantPageClosed, ParticipantPageOpened};
+use crate::state::{Season, SeasonPhase, ParticipantPage};
+
+#[derive(Accounts)]
+#[instruction(page: u64)]
+pub struct OpenParticipantPage<'info> {
+    /// Season authority, pays the page rent
+    #[account(
+        mut,
+        constraint = authority.key() == season.authority @ GorbageError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ParticipantPage::INIT_SPACE,
+        seeds = [PARTICIPANT_PAGE_SEED, season.key().as_ref(), page.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: Account<'info, ParticipantPage>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseParticipantPage<'info> {
+    /// Anyone can crank the rent back to the season authority
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    /// Wallet that funded the page
+    #[account(
+        mut,
+        address = participant_page.payer @ GorbageError::Unauthorized
+    )]
+    pub rent_recipient: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PARTICIPANT_PAGE_SEED,
+            season.key().as_ref(),
+            participant_page.page.to_le_bytes().as_ref()
+        ],
+        bump = participant_page.bump,
+        close = rent_recipient
+    )]
+    pub participant_page: Account<'info, ParticipantPage>,
+}
+
+/// Open roster page `page` ahead of the registrations that fill it, so the
+/// season authority rather than a registrant pays its rent. Only the page being
+/// filled or the next one can be opened.
+pub fn handler(ctx: Context<OpenParticipantPage>, page: u64) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {}
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::RegistrationClosed),
+    }
+    require!(
+        clock.unix_timestamp <= season.registration_end,
+        GorbageError::RegistrationClosed
+    );
+    let current_page = ParticipantPage::page_for(season.next_participant_index);
+    require!(
+        page == current_page || page == current_page + 1,
+        GorbageError::InvalidParticipantPage
+    );
+    
+    let participant_page = &mut ctx.accounts.participant_page;
+    participant_page.season = season.key();
+    participant_page.page = page;
+    participant_page.payer = ctx.accounts.authority.key();
+    participant_page.bump = ctx.bumps.participant_page;
+    
+    msg!("Participant page {} opened for season {}", page, season.season_number);
+    emit!(ParticipantPageOpened {
+        season: season.key(),
+        season_number: season.season_number,
+        participant_page: participant_page.key(),
+        page,
+        payer: ctx.accounts.authority.key(),
+    });
+    
+    Ok(())
+}
+
+/// Close a roster page once the season is closed or cancelled, returning its
+/// rent to whoever opened it
+pub fn close_participant_page(ctx: Context<CloseParticipantPage>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    season.sync_phase(clock.unix_timestamp);
+    require!(
+        matches!(season.phase, SeasonPhase::Closed | SeasonPhase::Cancelled),
+        GorbageError::SeasonNotOver
+    );
+    
+    msg!(
+        "Participant page {} closed for season {}",
+        ctx.accounts.participant_page.page,
+        season.season_number
+    );
+    emit!(ParticipantPageClosed {
+        season: season.key(),
+        season_number: season.season_number,
+        participant_page: ctx.accounts.participant_page.key(),
+        page: ctx.accounts.participant_page.page,
+        rent_recipient: ctx.accounts.rent_recipient.key(),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SEASON_REGISTRY_SEED;
+use crate::error::GorbageError;
+use crate::state::{Season, SeasonRegistry};
+
+#[derive(Accounts)]
+pub struct RefreshSeasonRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+}
+
+/// Bring the registry up to date with the seasons passed in `remaining_accounts`,
+/// applying time-driven phase changes and dropping seasons that are over.
+/// Anyone can call.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshSeasonRegistry<'info>>) -> Result<()> {
+    let season_registry = &mut ctx.accounts.season_registry;
+    let clock = Clock::get()?;
+    
+    // Check every season against the registry before updating, since an update
+    // drops stale entries that later seasons in the list may refer to
+    let mut seasons = Vec::with_capacity(ctx.remaining_accounts.len());
+    for season_info in ctx.remaining_accounts {
+        let season: Account<'info, Season> = Account::try_from(season_info)?;
+        let expected_season = season_registry.seasons
+            .iter()
+            .find(|entry| entry.season_number == season.season_number)
+            .map(|entry| entry.season);
+        require!(
+            expected_season == Some(season.key()),
+            GorbageError::InvalidRemainingAccounts
+        );
+        seasons.push(season);
+    }
+    
+    // Only the registry copy is updated, the season itself catches up on its next use
+    for mut season in seasons {
+        season.sync_phase(clock.unix_timestamp);
+        season_registry.update(&season, clock.unix_timestamp);
+    }
+    
+    msg!("Season registry lists {} seasons", season_registry.seasons.len());
+    
+    Ok(())
+}
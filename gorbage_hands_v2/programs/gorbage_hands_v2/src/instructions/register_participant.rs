@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, VAULT_SEED};
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, VAULT_SEED, WAITLIST_SEED, PARTICIPANT_PAGE_SEED};
 use crate::error::GorbageError;
-use crate::state::{Season, Participant};
+use crate::events::{ParticipantPageOpened, ParticipantRegistered};
+use crate::state::{Season, SeasonPhase, Participant, ParticipantStatus, ParticipantPage, Waitlist};
+use crate::vault::SeasonVault;
 
 #[derive(Accounts)]
 pub struct RegisterParticipant<'info> {
@@ -26,6 +28,22 @@ pub struct RegisterParticipant<'info> {
     )]
     pub participant: Account<'info, Participant>,
     
+    /// Roster page the new participant is appended to, usually opened beforehand
+    /// by the season authority. A registrant crossing into a page that isn't
+    /// open yet pays for it.
+    #[account(
+        init_if_needed,
+        payer = participant_owner,
+        space = 8 + ParticipantPage::INIT_SPACE,
+        seeds = [
+            PARTICIPANT_PAGE_SEED,
+            season.key().as_ref(),
+            ParticipantPage::page_for(season.next_participant_index).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub participant_page: Account<'info, ParticipantPage>,
+    
     /// CHECK: Vault PDA to receive entry fee
     #[account(
         mut,
@@ -34,6 +52,27 @@ pub struct RegisterParticipant<'info> {
     )]
     pub vault: SystemAccount<'info>,
     
+    /// Waitlist for capped seasons
+    #[account(
+        mut,
+        seeds = [WAITLIST_SEED, season.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Participant token account paying the entry fee, only for token-priced seasons
+    #[account(mut)]
+    pub participant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -42,23 +81,49 @@ pub fn handler(ctx: Context<RegisterParticipant>) -> Result<()> {
     let participant = &mut ctx.accounts.participant;
     let clock = Clock::get()?;
     
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Registration => {}
+        SeasonPhase::Scheduled => return err!(GorbageError::RegistrationNotOpen),
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::RegistrationClosed),
+    }
+    
     // Check registration is open
     require!(
         season.is_registration_open(clock.unix_timestamp),
         GorbageError::RegistrationClosed
     );
     
-    // Transfer entry fee to vault
-    let entry_fee = season.entry_fee;
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.participant_owner.to_account_info(),
-                to: ctx.accounts.vault.to_account_info(),
-            },
-        ),
-        entry_fee,
+    // Capped seasons: spots held for waitlisted wallets can only be taken by them
+    if season.is_capped() {
+        let waitlist = ctx.accounts.waitlist
+            .as_mut()
+            .ok_or(GorbageError::WaitlistRequired)?;
+        let open_spots = season.open_spots();
+        waitlist.refresh_offers(clock.unix_timestamp, open_spots);
+        
+        let owner = ctx.accounts.participant_owner.key();
+        if !waitlist.take_offer(&owner) {
+            require!(open_spots > waitlist.reserved_spots(), GorbageError::SeasonFull);
+        }
+    }
+    
+    // Transfer entry fee to vault, recording what actually arrived
+    // (Token-2022 transfer fees are withheld from the amount sent)
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let entry_fee = vault.deposit(
+        &ctx.accounts.participant_owner.to_account_info(),
+        ctx.accounts.participant_token_account.as_ref(),
+        season.entry_fee,
     )?;
     
     // Update season
@@ -73,18 +138,58 @@ pub fn handler(ctx: Context<RegisterParticipant>) -> Result<()> {
     participant.owner = ctx.accounts.participant_owner.key();
     participant.season = season.key();
     participant.season_number = season.season_number;
+    participant.index = season.next_participant_index;
     participant.registered_at = clock.unix_timestamp;
     participant.entry_fee_paid = entry_fee;
+    participant.status = ParticipantStatus::Active;
     participant.placement = 0;
     participant.prize_amount = 0;
+    participant.settlement_epoch = 0;
     participant.prize_claimed = false;
+    participant.refunded = false;
+    participant.score = 0;
+    participant.score_submitted_at = 0;
+    participant.score_nonce = 0;
+    participant.ranked_round = 0;
     participant.bump = ctx.bumps.participant;
     
+    // Append to the roster, opening the page if the authority hasn't
+    let participant_page = &mut ctx.accounts.participant_page;
+    if participant_page.season == Pubkey::default() {
+        participant_page.season = season.key();
+        participant_page.page = ParticipantPage::page_for(participant.index);
+        participant_page.payer = participant.owner;
+        participant_page.bump = ctx.bumps.participant_page;
+        
+        emit!(ParticipantPageOpened {
+            season: season.key(),
+            season_number: season.season_number,
+            participant_page: participant_page.key(),
+            page: participant_page.page,
+            payer: participant.owner,
+        });
+    }
+    participant_page.entries.push(participant.owner);
+    
+    season.next_participant_index = season.next_participant_index
+        .checked_add(1)
+        .ok_or(GorbageError::Overflow)?;
+    
     msg!(
         "Participant {} registered for season {}",
         participant.owner,
         season.season_number
     );
+    emit!(ParticipantRegistered {
+        season: season.key(),
+        season_number: season.season_number,
+        participant: participant.key(),
+        owner: participant.owner,
+        index: participant.index,
+        entry_fee_paid: participant.entry_fee_paid,
+        participant_count: season.participant_count,
+        prize_pool: season.prize_pool,
+    });
     
     Ok(())
 }
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::error::GorbageError;
+use crate::events::GameServerSet;
+use crate::state::ProgramConfig;
+
+#[derive(Accounts)]
+pub struct SetGameServer<'info> {
+    #[account(
+        constraint = admin.key() == config.admin @ GorbageError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Register the game server key allowed to submit scores (admin only).
+/// Pass the default pubkey to unregister it.
+pub fn handler(ctx: Context<SetGameServer>, game_server: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_game_server = config.game_server;
+    config.game_server = game_server;
+    
+    msg!("Game server changed from {} to {}", old_game_server, game_server);
+    emit!(GameServerSet {
+        config: config.key(),
+        old_game_server,
+        game_server,
+    });
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SEASON_SEED, SEASON_REGISTRY_SEED};
+use crate::error::GorbageError;
+use crate::events::{PrizeSet, ScoresRanked, WinnersSet};
+use crate::payout;
+use crate::results;
+use crate::state::{Season, SeasonPhase, SeasonMode, SeasonRegistry, Participant, ParticipantStatus, LeaderboardEntry};
+
+#[derive(Accounts)]
+pub struct SettleSeason<'info> {
+    #[account(
+        constraint = authority.key() == season.authority @ GorbageError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+}
+
+/// Propose the results of a finished season in one step. Prizes come from the
+/// payout schedule and their total can never exceed the post-fee prize pool.
+/// They become claimable once enough oracle attestors confirm the result hash
+/// and the challenge window closes without open disputes.
+///
+/// `remaining_accounts` holds the winners' Participant accounts (writable) in
+/// placement order, each active and passed only once. With `rank_by_score`
+/// every scored participant must first have been ranked with `rank_scores`,
+/// and the winners are the leaderboard in order.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleSeason<'info>>,
+    rank_by_score: bool,
+) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    // Validations
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Active => {}
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {
+            return err!(GorbageError::FeeNotCollected)
+        }
+        SeasonPhase::Cancelled if season.is_below_minimum() => {
+            return err!(GorbageError::MinParticipantsNotMet)
+        }
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersAlreadySet),
+    }
+    require!(season.mode == SeasonMode::Contest, GorbageError::ContestModeRequired);
+    require!(season.has_ended(clock.unix_timestamp), GorbageError::SeasonNotEnded);
+    require!(!ctx.remaining_accounts.is_empty(), GorbageError::InvalidWinnerCount);
+    
+    let season_key = season.key();
+    let mut winners: Vec<Account<'info, Participant>> =
+        Vec::with_capacity(ctx.remaining_accounts.len());
+    
+    for participant_info in ctx.remaining_accounts {
+        require!(
+            !winners.iter().any(|winner| winner.key() == participant_info.key()),
+            GorbageError::DuplicateWinner
+        );
+        
+        let participant: Account<'info, Participant> = Account::try_from(participant_info)?;
+        require!(participant.season == season_key, GorbageError::NotRegistered);
+        require!(
+            participant.status == ParticipantStatus::Active,
+            GorbageError::ParticipantInactive
+        );
+        
+        winners.push(participant);
+    }
+    
+    if rank_by_score {
+        // The leaderboard only holds once no scored participant was left out
+        require!(
+            season.ranked_count == season.scored_count,
+            GorbageError::IncompleteScoreboard
+        );
+        require!(
+            winners.len() == season.leaderboard.len()
+                && winners
+                    .iter()
+                    .zip(&season.leaderboard)
+                    .all(|(winner, entry)| winner.owner == entry.owner),
+            GorbageError::LeaderboardMismatch
+        );
+    } else {
+        require!(
+            winners.len() <= season.payout_bps.len(),
+            GorbageError::InvalidWinnerCount
+        );
+    }
+    
+    propose_results(season, &mut winners, ctx.program_id, clock.unix_timestamp)?;
+    ctx.accounts.season_registry.update(season, clock.unix_timestamp);
+    
+    Ok(())
+}
+
+/// Assign placements and prizes to `winners` in order and move the season to
+/// `Settling` with the hash of the proposed results. Shared by every way of
+/// picking winners.
+pub(crate) fn propose_results<'info>(
+    season: &mut Account<'info, Season>,
+    winners: &mut [Account<'info, Participant>],
+    program_id: &Pubkey,
+    current_time: i64,
+) -> Result<()> {
+    let season_key = season.key();
+    let payout_bps = &season.payout_bps[..winners.len()];
+    let mut winner_pubkeys: Vec<Pubkey> = Vec::with_capacity(winners.len());
+    let mut awards: Vec<(Pubkey, u64)> = Vec::with_capacity(winners.len());
+    let (prizes, dust) = payout::settle_prizes(season.prize_pool, payout_bps, season.dust_policy)
+        .ok_or(GorbageError::InvalidPlacement)?;
+    let total_awarded = season.prize_pool - dust;
+    
+    for (index, (participant, prize_amount)) in winners.iter_mut().zip(prizes).enumerate() {
+        require!(
+            participant.to_account_info().is_writable,
+            GorbageError::InvalidRemainingAccounts
+        );
+        
+        let placement = (index + 1) as u8;
+        participant.placement = placement;
+        participant.prize_amount = prize_amount;
+        participant.settlement_epoch = season.settlement_epoch;
+        participant.exit(program_id)?;
+        
+        msg!(
+            "Winner {} set: placement {}, prize {}",
+            participant.owner,
+            placement,
+            prize_amount
+        );
+        emit!(PrizeSet {
+            season: season_key,
+            participant: participant.key(),
+            owner: participant.owner,
+            placement,
+            prize_amount,
+            settlement_epoch: season.settlement_epoch,
+        });
+        
+        winner_pubkeys.push(participant.owner);
+        awards.push((participant.owner, prize_amount));
+    }
+    
+    season.winner_count = winner_pubkeys.len() as u8;
+    season.winners = winner_pubkeys;
+    season.total_awarded = total_awarded;
+    season.dust_amount = dust;
+    season.result_hash = results::winners_result_hash(&season_key, &awards);
+    season.attestations = Vec::new();
+    season.challenge_deadline = current_time
+        .checked_add(season.challenge_window)
+        .ok_or(GorbageError::Overflow)?;
+    season.phase = SeasonPhase::Settling;
+    
+    msg!(
+        "Season {} results proposed: {} winners, {} awarded from a pool of {}, {} dust",
+        season.season_number,
+        season.winner_count,
+        total_awarded,
+        season.prize_pool,
+        dust
+    );
+    emit!(WinnersSet {
+        season: season_key,
+        season_number: season.season_number,
+        settlement_epoch: season.settlement_epoch,
+        winners: season.winners.clone(),
+        prize_amounts: awards.iter().map(|(_, amount)| *amount).collect(),
+        merkle_root: None,
+        leaf_count: 0,
+        total_awarded,
+        prize_pool: season.prize_pool,
+        dust,
+        dust_policy: season.dust_policy,
+        result_hash: season.result_hash,
+        challenge_deadline: season.challenge_deadline,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RankScores<'info> {
+    #[account(
+        constraint = authority.key() == season.authority @ GorbageError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+}
+
+/// Rank a batch of scored participants, passed writable in `remaining_accounts`,
+/// into the season leaderboard. Batches can be split over as many transactions
+/// as the scoreboard needs; `settle_season` with `rank_by_score` needs every
+/// scored participant ranked in the current round. A new score or a
+/// disqualification starts a new round.
+pub fn rank_scores<'info>(ctx: Context<'_, '_, 'info, 'info, RankScores<'info>>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    // Validations
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Active => {}
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {
+            return err!(GorbageError::FeeNotCollected)
+        }
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersAlreadySet),
+    }
+    require!(season.mode == SeasonMode::Contest, GorbageError::ContestModeRequired);
+    require!(season.has_ended(clock.unix_timestamp), GorbageError::SeasonNotEnded);
+    require!(!ctx.remaining_accounts.is_empty(), GorbageError::InvalidRemainingAccounts);
+    
+    let season_key = season.key();
+    for participant_info in ctx.remaining_accounts {
+        let mut participant: Account<'info, Participant> = Account::try_from(participant_info)?;
+        require!(participant.season == season_key, GorbageError::NotRegistered);
+        require!(
+            participant.status == ParticipantStatus::Active,
+            GorbageError::ParticipantInactive
+        );
+        require!(participant.has_score(), GorbageError::ScoreMissing);
+        require!(
+            participant.ranked_round != season.ranking_round,
+            GorbageError::AlreadyRanked
+        );
+        require!(participant_info.is_writable, GorbageError::InvalidRemainingAccounts);
+        
+        season.rank(LeaderboardEntry {
+            owner: participant.owner,
+            score: participant.score,
+            score_submitted_at: participant.score_submitted_at,
+        })?;
+        participant.ranked_round = season.ranking_round;
+        participant.exit(ctx.program_id)?;
+    }
+    
+    msg!(
+        "Season {} ranking round {}: {} of {} scored participants ranked",
+        season.season_number,
+        season.ranking_round,
+        season.ranked_count,
+        season.scored_count
+    );
+    emit!(ScoresRanked {
+        season: season_key,
+        season_number: season.season_number,
+        ranking_round: season.ranking_round,
+        batch_size: ctx.remaining_accounts.len() as u32,
+        ranked_count: season.ranked_count,
+        scored_count: season.scored_count,
+    });
+    
+    Ok(())
+}
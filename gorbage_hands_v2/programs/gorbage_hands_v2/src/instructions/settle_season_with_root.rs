@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SEASON_SEED, CLAIM_BITMAP_SEED, MAX_MERKLE_LEAVES, SEASON_REGISTRY_SEED};
+use crate::error::GorbageError;
+use crate::events::WinnersSet;
+use crate::results;
+use crate::state::{Season, SeasonPhase, SeasonMode, SeasonRegistry, ClaimBitmap, DustPolicy};
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], total_payout: u64, leaf_count: u32)]
+pub struct SettleSeasonWithRoot<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == season.authority @ GorbageError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_REGISTRY_SEED],
+        bump = season_registry.bump
+    )]
+    pub season_registry: Account<'info, SeasonRegistry>,
+    
+    /// Tracks which leaves of the distribution have been claimed
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ClaimBitmap::space(leaf_count),
+        seeds = [
+            CLAIM_BITMAP_SEED,
+            season.key().as_ref(),
+            season.settlement_epoch.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Propose the results of a finished season as a Merkle root over
+/// `(index, wallet, amount)` leaves, for winner sets too large to pass as
+/// accounts. Once attested, winners claim with `claim_prize_with_proof`;
+/// `total_payout` is reserved from the prize pool.
+pub fn handler(
+    ctx: Context<SettleSeasonWithRoot>,
+    merkle_root: [u8; 32],
+    total_payout: u64,
+    leaf_count: u32,
+) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+    
+    // Validations
+    season.sync_phase(clock.unix_timestamp);
+    match season.phase {
+        SeasonPhase::Active => {}
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {
+            return err!(GorbageError::FeeNotCollected)
+        }
+        SeasonPhase::Cancelled if season.is_below_minimum() => {
+            return err!(GorbageError::MinParticipantsNotMet)
+        }
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersAlreadySet),
+    }
+    require!(season.mode == SeasonMode::Contest, GorbageError::ContestModeRequired);
+    require!(season.has_ended(clock.unix_timestamp), GorbageError::SeasonNotEnded);
+    require!(
+        leaf_count > 0 && leaf_count <= MAX_MERKLE_LEAVES,
+        GorbageError::InvalidLeafCount
+    );
+    require!(total_payout <= season.prize_pool, GorbageError::PayoutExceedsPool);
+    
+    // The leaves can't be checked on chain, so a root paying dust to first place
+    // must already have folded it into its amounts
+    let dust = season.prize_pool - total_payout;
+    if season.dust_policy == DustPolicy::ToFirstPlace {
+        require!(dust == 0, GorbageError::PayoutBelowPool);
+    }
+    // Rounding each leaf down loses less than one unit per leaf, anything more
+    // is an underpaying distribution rather than dust
+    require!(dust < u64::from(leaf_count), GorbageError::DustAboveLeafCount);
+    
+    let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+    claim_bitmap.season = season.key();
+    claim_bitmap.leaf_count = leaf_count;
+    claim_bitmap.bits = vec![0; ClaimBitmap::byte_len(leaf_count)];
+    claim_bitmap.bump = ctx.bumps.claim_bitmap;
+    
+    season.merkle_root = Some(merkle_root);
+    season.merkle_leaf_count = leaf_count;
+    season.total_awarded = total_payout;
+    season.dust_amount = dust;
+    season.result_hash =
+        results::merkle_result_hash(&season.key(), &merkle_root, total_payout, leaf_count);
+    season.attestations = Vec::new();
+    season.challenge_deadline = clock.unix_timestamp
+        .checked_add(season.challenge_window)
+        .ok_or(GorbageError::Overflow)?;
+    season.phase = SeasonPhase::Settling;
+    ctx.accounts.season_registry.update(season, clock.unix_timestamp);
+    
+    msg!(
+        "Season {} results proposed with Merkle root: {} leaves, {} awarded from a pool of {}, {} dust",
+        season.season_number,
+        leaf_count,
+        total_payout,
+        season.prize_pool,
+        dust
+    );
+    emit!(WinnersSet {
+        season: season.key(),
+        season_number: season.season_number,
+        settlement_epoch: season.settlement_epoch,
+        winners: Vec::new(),
+        prize_amounts: Vec::new(),
+        merkle_root: Some(merkle_root),
+        leaf_count,
+        total_awarded: total_payout,
+        prize_pool: season.prize_pool,
+        dust,
+        dust_policy: season.dust_policy,
+        result_hash: season.result_hash,
+        challenge_deadline: season.challenge_deadline,
+    });
+    
+    Ok(())
+}
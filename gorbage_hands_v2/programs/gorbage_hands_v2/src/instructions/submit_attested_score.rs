@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, CONFIG_SEED};
+use crate::ed25519;
+use crate::error::GorbageError;
+use crate::events::ScoreSubmitted;
+use crate::instructions::submit_score::record_score;
+use crate::state::{Season, Participant, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct SubmitAttestedScore<'info> {
+    pub participant_owner: Signer<'info>,
+    
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.game_server != Pubkey::default() @ GorbageError::GameServerNotSet
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), participant_owner.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.owner == participant_owner.key() @ GorbageError::Unauthorized,
+        constraint = participant.season == season.key() @ GorbageError::NotRegistered
+    )]
+    pub participant: Account<'info, Participant>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the Ed25519 precompile instruction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+/// Submit a score signed off-chain by the game server. The transaction must
+/// carry an Ed25519 precompile instruction right before this one, verifying
+/// the game server's signature over `score_attestation_message`.
+/// `nonce` must be higher than the last one used for this participant.
+pub fn handler(ctx: Context<SubmitAttestedScore>, score: u64, nonce: u64) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let participant = &mut ctx.accounts.participant;
+    let clock = Clock::get()?;
+    
+    let message = score_attestation_message(&season.key(), &participant.key(), score, nonce);
+    ed25519::verify_signed_message(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.config.game_server,
+        &message,
+    )?;
+    
+    record_score(season, participant, score, nonce, clock.unix_timestamp)?;
+    
+    msg!(
+        "Attested score {} submitted for {} in season {} (nonce {})",
+        score,
+        participant.owner,
+        season.season_number,
+        nonce
+    );
+    emit!(ScoreSubmitted {
+        season: season.key(),
+        season_number: season.season_number,
+        participant: participant.key(),
+        owner: participant.owner,
+        score,
+        nonce,
+        submitted_at: participant.score_submitted_at,
+        attested: true,
+        scored_count: season.scored_count,
+    });
+    
+    Ok(())
+}
+
+/// Bytes the game server signs: `season || participant || score || nonce`,
+/// with the participant account address and little-endian integers
+pub fn score_attestation_message(
+    season: &Pubkey,
+    participant: &Pubkey,
+    score: u64,
+    nonce: u64,
+) -> [u8; 80] {
+    let mut message = [0u8; 80];
+    message[..32].copy_from_slice(season.as_ref());
+    message[32..64].copy_from_slice(participant.as_ref());
+    message[64..72].copy_from_slice(&score.to_le_bytes());
+    message[72..].copy_from_slice(&nonce.to_le_bytes());
+    message
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, CONFIG_SEED};
+use crate::error::GorbageError;
+use crate::events::ScoreSubmitted;
+use crate::state::{Season, SeasonPhase, Participant, ParticipantStatus, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct SubmitScore<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.game_server != Pubkey::default() @ GorbageError::GameServerNotSet
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Game server registered in the program config
+    #[account(
+        constraint = game_server.key() == config.game_server @ GorbageError::Unauthorized
+    )]
+    pub game_server: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), participant.owner.as_ref()],
+        bump = participant.bump,
+        constraint = participant.season == season.key() @ GorbageError::NotRegistered
+    )]
+    pub participant: Account<'info, Participant>,
+}
+
+/// Record a participant's score while the season is Active. A later submission
+/// replaces the earlier one. `nonce` comes from the same sequence the game
+/// server signs attested scores with and must be higher than the last one used
+/// for this participant, so attestations issued before this write can't
+/// replace it.
+pub fn handler(ctx: Context<SubmitScore>, score: u64, nonce: u64) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let participant = &mut ctx.accounts.participant;
+    let clock = Clock::get()?;
+    
+    record_score(season, participant, score, nonce, clock.unix_timestamp)?;
+    
+    msg!(
+        "Score {} submitted for {} in season {} (nonce {})",
+        score,
+        participant.owner,
+        season.season_number,
+        nonce
+    );
+    emit!(ScoreSubmitted {
+        season: season.key(),
+        season_number: season.season_number,
+        participant: participant.key(),
+        owner: participant.owner,
+        score,
+        nonce,
+        submitted_at: participant.score_submitted_at,
+        attested: false,
+        scored_count: season.scored_count,
+    });
+    
+    Ok(())
+}
+
+/// Store `score` for an active participant of an Active season, shared by the
+/// game server and attested submission paths. Every write moves the
+/// participant's score nonce forward.
+pub(crate) fn record_score(
+    season: &mut Season,
+    participant: &mut Participant,
+    score: u64,
+    nonce: u64,
+    current_time: i64,
+) -> Result<()> {
+    season.sync_phase(current_time);
+    match season.phase {
+        SeasonPhase::Active => {}
+        SeasonPhase::Scheduled | SeasonPhase::Registration => {
+            return err!(GorbageError::FeeNotCollected)
+        }
+        SeasonPhase::Cancelled => return err!(GorbageError::SeasonCancelled),
+        SeasonPhase::Closed => return err!(GorbageError::SeasonClosed),
+        _ => return err!(GorbageError::WinnersAlreadySet),
+    }
+    require!(!season.has_ended(current_time), GorbageError::SeasonEnded);
+    require!(
+        participant.status == ParticipantStatus::Active,
+        GorbageError::ParticipantInactive
+    );
+    require!(nonce > participant.score_nonce, GorbageError::StaleScoreNonce);
+    
+    participant.score_nonce = nonce;
+    if participant.record_score(score, current_time) {
+        season.scored_count = season.scored_count
+            .checked_add(1)
+            .ok_or(GorbageError::Overflow)?;
+    }
+    season.reset_ranking()?;
+    
+    Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::CONFIG_SEED;
 use crate::error::GorbageError;
+use crate::events::AdminTransferred;
 use crate::state::ProgramConfig;
 
 #[derive(Accounts)]
@@ -26,5 +27,10 @@ pub fn handler(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
     config.admin = new_admin;
     
     msg!("Admin transferred from {} to {}", old_admin, new_admin);
+    emit!(AdminTransferred {
+        config: config.key(),
+        old_admin,
+        new_admin,
+    });
     Ok(())
 }
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{SEASON_SEED, PARTICIPANT_SEED, VAULT_SEED, WAITLIST_SEED};
+use crate::error::GorbageError;
+use crate::events::ParticipantWithdrawn;
+use crate::state::{Season, Participant, ParticipantStatus, Waitlist};
+use crate::vault::SeasonVault;
+
+#[derive(Accounts)]
+pub struct WithdrawParticipant<'info> {
+    #[account(mut)]
+    pub participant_owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, season.season_number.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PARTICIPANT_SEED, season.key().as_ref(), participant_owner.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.owner == participant_owner.key() @ GorbageError::Unauthorized,
+        constraint = participant.season == season.key() @ GorbageError::NotRegistered
+    )]
+    pub participant: Account<'info, Participant>,
+    
+    /// CHECK: Vault PDA that holds the entry fees
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, season.key().as_ref()],
+        bump = season.vault_bump
+    )]
+    pub vault: AccountInfo<'info>,
+    
+    /// Waitlist for capped seasons, the freed spot is offered to the next wallet
+    #[account(
+        mut,
+        seeds = [WAITLIST_SEED, season.key().as_ref()],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Option<Account<'info, Waitlist>>,
+    
+    /// Season mint, only for token-priced seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Vault PDA's token account, only for token-priced seasons
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Participant token account receiving the refund, only for token-priced seasons
+    #[account(mut)]
+    pub participant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Leave a season during registration and get the entry fee back
+pub fn handler(ctx: Context<WithdrawParticipant>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let participant = &mut ctx.accounts.participant;
+    let owner = &ctx.accounts.participant_owner;
+    let clock = Clock::get()?;
+    
+    // Validations
+    require!(
+        season.is_registration_open(clock.unix_timestamp),
+        GorbageError::RegistrationClosed
+    );
+    require!(
+        participant.status == ParticipantStatus::Active,
+        GorbageError::ParticipantInactive
+    );
+    
+    let refund_amount = participant.entry_fee_paid;
+    let vault = SeasonVault::load(
+        season,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    vault.withdraw(
+        &owner.to_account_info(),
+        ctx.accounts.participant_token_account.as_ref(),
+        refund_amount,
+    )?;
+    
+    season.prize_pool = season.prize_pool
+        .checked_sub(refund_amount)
+        .ok_or(GorbageError::InsufficientVaultFunds)?;
+    season.participant_count = season.participant_count
+        .checked_sub(1)
+        .ok_or(GorbageError::Overflow)?;
+    participant.status = ParticipantStatus::Withdrawn;
+    participant.refunded = true;
+    
+    if season.is_capped() {
+        let waitlist = ctx.accounts.waitlist
+            .as_mut()
+            .ok_or(GorbageError::WaitlistRequired)?;
+        waitlist.refresh_offers(clock.unix_timestamp, season.open_spots());
+    }
+    
+    msg!(
+        "Participant {} withdrew from season {}, refunded {}",
+        owner.key(),
+        season.season_number,
+        refund_amount
+    );
+    emit!(ParticipantWithdrawn {
+        season: season.key(),
+        season_number: season.season_number,
+        owner: owner.key(),
+        refund: refund_amount,
+        participant_count: season.participant_count,
+        prize_pool: season.prize_pool,
+    });
+    
+    Ok(())
+}
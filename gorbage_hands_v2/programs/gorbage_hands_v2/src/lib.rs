@@ -1,9 +1,16 @@
 use anchor_lang::prelude::*;
 
 pub mod constants;
+pub mod ed25519;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod merkle;
+pub mod payout;
+pub mod raffle;
+pub mod results;
 pub mod state;
+pub mod vault;
 
 use instructions::*;
 
@@ -24,25 +31,71 @@ pub mod gorbage_hands_v2 {
         instructions::transfer_admin::handler(ctx, new_admin)
     }
 
+    /// Register the game server allowed to submit scores (admin only)
+    pub fn set_game_server(ctx: Context<SetGameServer>, game_server: Pubkey) -> Result<()> {
+        instructions::set_game_server::handler(ctx, game_server)
+    }
+
+    /// Create the oracle registry of result attestors (admin only)
+    pub fn initialize_oracle_registry(
+        ctx: Context<InitializeOracleRegistry>,
+        attestors: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::oracle_registry::handler(ctx, attestors, threshold)
+    }
+
+    /// Add a result attestor (admin only)
+    pub fn add_attestor(ctx: Context<UpdateOracleRegistry>, attestor: Pubkey) -> Result<()> {
+        instructions::oracle_registry::add_attestor(ctx, attestor)
+    }
+
+    /// Remove a result attestor (admin only)
+    pub fn remove_attestor(ctx: Context<UpdateOracleRegistry>, attestor: Pubkey) -> Result<()> {
+        instructions::oracle_registry::remove_attestor(ctx, attestor)
+    }
+
+    /// Replace a result attestor key (admin only)
+    pub fn rotate_attestor(
+        ctx: Context<UpdateOracleRegistry>,
+        old_attestor: Pubkey,
+        new_attestor: Pubkey,
+    ) -> Result<()> {
+        instructions::oracle_registry::rotate_attestor(ctx, old_attestor, new_attestor)
+    }
+
+    /// Set how many attestors must confirm season results (admin only)
+    pub fn set_attestation_threshold(
+        ctx: Context<UpdateOracleRegistry>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::oracle_registry::set_attestation_threshold(ctx, threshold)
+    }
+
     /// Initialize a new season for the Gorbage Hands game (admin only)
+    /// `params.payout_bps` lists the prize share per placement and must sum to 10000
+    /// If fewer than `params.min_participants` register, the season is refunded instead
+    /// Pass a mint (and the vault token account to create) to price the season in an
+    /// SPL Token or Token-2022 mint instead of native lamports
+    /// A non-zero `params.guaranteed_pool` is funded by the authority in the same call
+    /// The season number is taken from the program config's counter
     pub fn initialize_season(
         ctx: Context<InitializeSeason>,
-        season_number: u64,
-        name: String,
-        entry_fee: u64,
-        registration_start: i64,
-        registration_end: i64,
-        season_end: i64,
+        params: InitializeSeasonParams,
     ) -> Result<()> {
-        instructions::initialize_season::handler(
-            ctx,
-            season_number,
-            name,
-            entry_fee,
-            registration_start,
-            registration_end,
-            season_end,
-        )
+        instructions::initialize_season::handler(ctx, params)
+    }
+
+    /// Open a roster page ahead of the registrations that fill it (authority only)
+    /// Registration needs the page holding the next participant index to exist
+    pub fn open_participant_page(ctx: Context<OpenParticipantPage>, page: u64) -> Result<()> {
+        instructions::participant_pages::handler(ctx, page)
+    }
+
+    /// Close a roster page once the season is closed or cancelled, returning the rent
+    /// to the season authority (anyone can call)
+    pub fn close_participant_page(ctx: Context<CloseParticipantPage>) -> Result<()> {
+        instructions::participant_pages::close_participant_page(ctx)
     }
 
     /// Register a participant for the current season
@@ -57,14 +110,38 @@ pub mod gorbage_hands_v2 {
         instructions::collect_fee::handler(ctx)
     }
 
-    /// Set the winners for a completed season (authority only)
-    pub fn set_winners(ctx: Context<SetWinners>, winner_pubkeys: Vec<Pubkey>) -> Result<()> {
-        instructions::set_winners::handler(ctx, winner_pubkeys)
+    /// Record a participant's score during the Active phase (game server only)
+    /// `nonce` shares the sequence of attested scores and must increase
+    pub fn submit_score(ctx: Context<SubmitScore>, score: u64, nonce: u64) -> Result<()> {
+        instructions::submit_score::handler(ctx, score, nonce)
     }
 
-    /// Set prize amount for a specific winner participant
-    pub fn set_winner_prize(ctx: Context<SetWinnerParticipant>, placement: u8) -> Result<()> {
-        instructions::set_winners::set_winner_prize(ctx, placement)
+    /// Record a score signed off-chain by the game server, submitted by the participant
+    /// Requires an Ed25519 precompile instruction right before this one
+    pub fn submit_attested_score(
+        ctx: Context<SubmitAttestedScore>,
+        score: u64,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::submit_attested_score::handler(ctx, score, nonce)
+    }
+
+    /// Rank scored participants passed via remaining_accounts into the season
+    /// leaderboard, in as many batches as needed (authority only)
+    pub fn rank_scores<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RankScores<'info>>,
+    ) -> Result<()> {
+        instructions::settle_season::rank_scores(ctx)
+    }
+
+    /// Propose the results of a completed season (authority only)
+    /// The winners' participant accounts are passed via remaining_accounts in placement
+    /// order; with rank_by_score they must match the ranked leaderboard
+    pub fn settle_season<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleSeason<'info>>,
+        rank_by_score: bool,
+    ) -> Result<()> {
+        instructions::settle_season::handler(ctx, rank_by_score)
     }
 
     /// Claim prize as a winner
@@ -73,7 +150,126 @@ pub mod gorbage_hands_v2 {
     }
 
     /// Close the season and reclaim remaining funds (authority only)
+    /// Unclaimed prizes block closing until the claim deadline, then follow the unclaimed policy
     pub fn close_season(ctx: Context<CloseSeason>) -> Result<()> {
         instructions::close_season::handler(ctx)
     }
+
+    /// Cancel a season before it is settled (season authority or config admin)
+    /// If the platform fee was already collected, the treasury must sign to return it
+    pub fn cancel_season(ctx: Context<CancelSeason>) -> Result<()> {
+        instructions::cancel_season::handler(ctx)
+    }
+
+    /// Reclaim the entry fee from a cancelled season
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund::handler(ctx)
+    }
+
+    /// Join the waitlist of a season that has reached max_participants
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>) -> Result<()> {
+        instructions::join_waitlist::handler(ctx)
+    }
+
+    /// Withdraw from a season during registration and get the entry fee back
+    pub fn withdraw_participant(ctx: Context<WithdrawParticipant>) -> Result<()> {
+        instructions::withdraw_participant::handler(ctx)
+    }
+
+    /// Disqualify a participant (authority only)
+    pub fn disqualify_participant(ctx: Context<DisqualifyParticipant>) -> Result<()> {
+        instructions::disqualify_participant::handler(ctx)
+    }
+
+    /// Add sponsor funds to a season's prize pool (any wallet, exempt from the platform fee)
+    pub fn contribute_to_pool(ctx: Context<ContributeToPool>, amount: u64) -> Result<()> {
+        instructions::contribute_to_pool::handler(ctx, amount)
+    }
+
+    /// Reclaim a sponsor contribution from a cancelled season
+    pub fn claim_sponsor_refund(ctx: Context<ClaimSponsorRefund>) -> Result<()> {
+        instructions::claim_sponsor_refund::handler(ctx)
+    }
+
+    /// Close your participant account once the season is settled and get the rent back
+    pub fn close_participant(ctx: Context<CloseParticipant>) -> Result<()> {
+        instructions::close_participant::handler(ctx)
+    }
+
+    /// Close many participant accounts, passed as (participant, owner) pairs in
+    /// remaining_accounts, returning each rent to its owner (anyone can call)
+    pub fn close_participants<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseParticipants<'info>>,
+    ) -> Result<()> {
+        instructions::close_participant::close_participants(ctx)
+    }
+
+    /// Propose the results of a completed season as a Merkle root over the prize distribution (authority only)
+    pub fn settle_season_with_root(
+        ctx: Context<SettleSeasonWithRoot>,
+        merkle_root: [u8; 32],
+        total_payout: u64,
+        leaf_count: u32,
+    ) -> Result<()> {
+        instructions::settle_season_with_root::handler(ctx, merkle_root, total_payout, leaf_count)
+    }
+
+    /// Claim a prize from a Merkle-settled season with a proof of (index, wallet, amount)
+    pub fn claim_prize_with_proof(
+        ctx: Context<ClaimPrizeWithProof>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_prize_with_proof::handler(ctx, index, amount, proof)
+    }
+
+    /// Confirm the proposed results of a season (registered attestors only)
+    /// Results take effect once the registry threshold is reached
+    pub fn attest_results(ctx: Context<AttestResults>, result_hash: [u8; 32]) -> Result<()> {
+        instructions::attest_results::handler(ctx, result_hash)
+    }
+
+    /// Dispute proposed results during the challenge window by posting a bond (participants only)
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        instructions::dispute_results::handler(ctx)
+    }
+
+    /// Uphold or void disputed results (admin only)
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        resolution: DisputeResolution,
+    ) -> Result<()> {
+        instructions::dispute_results::resolve_dispute(ctx, resolution)
+    }
+
+    /// Make attested, undisputed results final once the challenge window closes (anyone can call)
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        instructions::finalize_settlement::handler(ctx)
+    }
+
+    /// Commit a raffle to a future slot once registration ends (anyone can call)
+    pub fn lock_raffle(ctx: Context<LockRaffle>) -> Result<()> {
+        instructions::draw_winners::lock_raffle(ctx)
+    }
+
+    /// Reveal the committed raffle secret once the locked slot has passed (authority only)
+    pub fn reveal_raffle(ctx: Context<RevealRaffle>, secret: [u8; 32]) -> Result<()> {
+        instructions::draw_winners::handler(ctx, secret)
+    }
+
+    /// Propose raffle winners drawn from the revealed seed (authority only)
+    /// Participant accounts hit by each draw are passed in order via remaining_accounts
+    pub fn draw_winners<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DrawWinners<'info>>,
+    ) -> Result<()> {
+        instructions::draw_winners::draw_winners(ctx)
+    }
+
+    /// Refresh the phases of listed seasons passed via remaining_accounts (anyone can call)
+    pub fn refresh_season_registry<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshSeasonRegistry<'info>>,
+    ) -> Result<()> {
+        instructions::refresh_season_registry::handler(ctx)
+    }
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Domain prefix for leaf hashes, so a leaf can never be passed off as an inner node
+pub const LEAF_PREFIX: &[u8] = &[0x00];
+
+/// Domain prefix for inner node hashes
+pub const NODE_PREFIX: &[u8] = &[0x01];
+
+/// Hash of one prize entry: `sha256(0x00 || index || wallet || amount)`,
+/// with `index` and `amount` little-endian
+pub fn leaf_hash(index: u32, wallet: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        wallet.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Hash of an inner node: `sha256(0x01 || min(a, b) || max(a, b))`.
+/// Sorting the pair means proofs don't need to carry left/right flags.
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Fold `proof` over `leaf` and compare the result with `root`
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| node_hash(&node, sibling));
+    
+    computed == *root
+}
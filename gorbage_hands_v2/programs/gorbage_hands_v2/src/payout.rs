@@ -0,0 +1,275 @@
+use crate::constants::{BPS_DENOMINATOR, PLATFORM_FEE_BPS};
+use crate::state::DustPolicy;
+
+/// Entry fees and guarantee deposit split up when the platform fee is collected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSplit {
+    /// Platform fee sent to the treasury
+    pub fee: u64,
+    
+    /// Entry fees left for the prize pool
+    pub post_fee_entries: u64,
+    
+    /// Part of the guarantee deposit topping the pool up to the guarantee
+    pub overlay: u64,
+    
+    /// Part of the guarantee deposit returned to the authority
+    pub unused_guarantee: u64,
+}
+
+/// Platform fee on `entry_total`, rounded down. The u128 product can't overflow
+/// and the result never exceeds `entry_total`.
+pub fn platform_fee(entry_total: u64) -> u64 {
+    (entry_total as u128 * PLATFORM_FEE_BPS as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Take the platform fee from `entry_total` and use as much of `guarantee_deposit`
+/// as the post-fee entries need to reach `guaranteed_pool`
+pub fn split_entries(entry_total: u64, guaranteed_pool: u64, guarantee_deposit: u64) -> FeeSplit {
+    let fee = platform_fee(entry_total);
+    let post_fee_entries = entry_total - fee;
+    let overlay = guaranteed_pool
+        .saturating_sub(post_fee_entries)
+        .min(guarantee_deposit);
+    
+    FeeSplit {
+        fee,
+        post_fee_entries,
+        overlay,
+        unused_guarantee: guarantee_deposit - overlay,
+    }
+}
+
+/// Prize pool once the fee is collected: post-fee entries, the guarantee overlay
+/// and the fee-exempt sponsor and rollover funds. `None` on overflow.
+pub fn prize_pool(split: &FeeSplit, sponsor_pool: u64, rollover_pool: u64) -> Option<u64> {
+    split
+        .post_fee_entries
+        .checked_add(split.overlay)?
+        .checked_add(sponsor_pool)?
+        .checked_add(rollover_pool)
+}
+
+/// Prize for `placement` (1 = first) out of `prize_pool`, rounded down.
+/// `payout_bps` is the schedule truncated to the number of winners actually set;
+/// if fewer winners were set than the schedule has placements, the filled
+/// placements are rescaled so the whole pool is still paid out.
+/// `None` if `placement` is outside the schedule or the schedule sums to zero.
+pub fn prize(prize_pool: u64, placement: u8, payout_bps: &[u16]) -> Option<u64> {
+    let bps = *payout_bps.get(usize::from(placement).checked_sub(1)?)? as u128;
+    let total_bps: u128 = payout_bps.iter().map(|bps| *bps as u128).sum();
+    if total_bps == 0 {
+        return None;
+    }
+    
+    // bps <= total_bps, so the prize never exceeds the pool
+    Some((prize_pool as u128 * bps / total_bps) as u64)
+}
+
+/// Prizes for every placement of `payout_bps`, in placement order. Their sum is
+/// at most `prize_pool` and falls short of it by less than one unit per placement.
+pub fn prizes(prize_pool: u64, payout_bps: &[u16]) -> Option<Vec<u64>> {
+    (1..=payout_bps.len())
+        .map(|placement| prize(prize_pool, u8::try_from(placement).ok()?, payout_bps))
+        .collect()
+}
+
+/// Part of `prize_pool` that `prizes` leave unassigned. `None` if the prizes add
+/// up to more than the pool.
+pub fn dust(prize_pool: u64, prizes: &[u64]) -> Option<u64> {
+    let awarded = prizes
+        .iter()
+        .try_fold(0u64, |total, prize| total.checked_add(*prize))?;
+    prize_pool.checked_sub(awarded)
+}
+
+/// Prizes for `payout_bps` with the rounding dust assigned by `dust_policy`.
+/// Under `ToFirstPlace` the dust is added to the first prize and 0 is returned,
+/// otherwise it is returned to be routed at finalization. Either way the prizes
+/// plus the returned dust add up to exactly `prize_pool`.
+pub fn settle_prizes(
+    prize_pool: u64,
+    payout_bps: &[u16],
+    dust_policy: DustPolicy,
+) -> Option<(Vec<u64>, u64)> {
+    let mut prizes = prizes(prize_pool, payout_bps)?;
+    let dust = dust(prize_pool, &prizes)?;
+    
+    match dust_policy {
+        DustPolicy::ToFirstPlace => {
+            let first = prizes.first_mut()?;
+            *first = first.checked_add(dust)?;
+            Some((prizes, 0))
+        }
+        DustPolicy::ToTreasury | DustPolicy::ToNextSeason { .. } => Some((prizes, dust)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Properties of the fee and prize arithmetic, checked over the full u64 range
+    
+    use proptest::prelude::*;
+    
+    use crate::constants::{BPS_DENOMINATOR, MAX_WINNERS, PLATFORM_FEE_BPS};
+    use crate::payout;
+    use crate::state::DustPolicy;
+    
+    /// Entry fee and participant count whose entry total fits in a u64, which
+    /// registration enforces with a checked add
+    fn entries() -> impl Strategy<Value = (u64, u64)> {
+        (1..=u64::MAX).prop_flat_map(|entry_fee| (Just(entry_fee), 1..=u64::MAX / entry_fee))
+    }
+    
+    fn dust_policy() -> impl Strategy<Value = DustPolicy> {
+        prop_oneof![
+            Just(DustPolicy::ToFirstPlace),
+            Just(DustPolicy::ToTreasury),
+            any::<u64>().prop_map(|season_number| DustPolicy::ToNextSeason { season_number }),
+        ]
+    }
+    
+    /// A payout schedule of nonzero placements, not necessarily summing to 10000
+    /// since truncated schedules are rescaled
+    fn payout_bps() -> impl Strategy<Value = Vec<u16>> {
+        prop::collection::vec(1..=BPS_DENOMINATOR as u16, 1..=MAX_WINNERS)
+    }
+    
+    proptest! {
+        #[test]
+        fn fee_and_prizes_never_exceed_the_entries(
+            (entry_fee, participant_count) in entries(),
+            payout_bps in payout_bps(),
+        ) {
+            let entry_total = entry_fee * participant_count;
+            let split = payout::split_entries(entry_total, 0, 0);
+            let prize_pool = payout::prize_pool(&split, 0, 0).unwrap();
+            let prizes = payout::prizes(prize_pool, &payout_bps).unwrap();
+            
+            let awarded: u128 = prizes.iter().map(|prize| *prize as u128).sum();
+            prop_assert!(split.fee as u128 + awarded <= entry_total as u128);
+        }
+        
+        #[test]
+        fn fee_prizes_and_dust_add_up_to_the_entries(
+            (entry_fee, participant_count) in entries(),
+            payout_bps in payout_bps(),
+            dust_policy in dust_policy(),
+        ) {
+            let entry_total = entry_fee * participant_count;
+            let split = payout::split_entries(entry_total, 0, 0);
+            let prize_pool = payout::prize_pool(&split, 0, 0).unwrap();
+            let (prizes, dust) =
+                payout::settle_prizes(prize_pool, &payout_bps, dust_policy).unwrap();
+            
+            let awarded: u128 = prizes.iter().map(|prize| *prize as u128).sum();
+            prop_assert_eq!(split.fee as u128 + awarded + dust as u128, entry_total as u128);
+        }
+        
+        #[test]
+        fn dust_follows_the_policy(
+            prize_pool in any::<u64>(),
+            payout_bps in payout_bps(),
+            dust_policy in dust_policy(),
+        ) {
+            let unassigned = payout::prizes(prize_pool, &payout_bps).unwrap();
+            let (prizes, dust) =
+                payout::settle_prizes(prize_pool, &payout_bps, dust_policy).unwrap();
+            let rounding = payout::dust(prize_pool, &unassigned).unwrap();
+            
+            if dust_policy == DustPolicy::ToFirstPlace {
+                prop_assert_eq!(dust, 0);
+                prop_assert_eq!(prizes[0], unassigned[0] + rounding);
+                prop_assert_eq!(&prizes[1..], &unassigned[1..]);
+            } else {
+                prop_assert_eq!(dust, rounding);
+                prop_assert_eq!(prizes, unassigned);
+            }
+        }
+        
+        #[test]
+        fn fee_is_exact_up_to_rounding(entry_total in any::<u64>()) {
+            let split = payout::split_entries(entry_total, 0, 0);
+            prop_assert_eq!(split.fee + split.post_fee_entries, entry_total);
+            
+            // Truncation keeps the fee within one unit below the exact 20%
+            let exact = entry_total as u128 * PLATFORM_FEE_BPS as u128;
+            let fee = split.fee as u128 * BPS_DENOMINATOR as u128;
+            prop_assert!(fee <= exact && exact - fee < BPS_DENOMINATOR as u128);
+        }
+        
+        #[test]
+        fn prize_dust_is_less_than_one_unit_per_placement(
+            prize_pool in any::<u64>(),
+            payout_bps in payout_bps(),
+        ) {
+            let prizes = payout::prizes(prize_pool, &payout_bps).unwrap();
+            let awarded: u128 = prizes.iter().map(|prize| *prize as u128).sum();
+            
+            prop_assert!(awarded <= prize_pool as u128);
+            prop_assert!((prize_pool as u128 - awarded) < payout_bps.len() as u128);
+        }
+        
+        #[test]
+        fn prizes_follow_the_schedule_order(
+            prize_pool in any::<u64>(),
+            payout_bps in payout_bps(),
+        ) {
+            let prizes = payout::prizes(prize_pool, &payout_bps).unwrap();
+            for i in 0..payout_bps.len() {
+                for j in 0..payout_bps.len() {
+                    if payout_bps[i] >= payout_bps[j] {
+                        prop_assert!(prizes[i] >= prizes[j]);
+                    }
+                }
+            }
+        }
+        
+        #[test]
+        fn guarantee_tops_up_the_pool_and_returns_the_rest(
+            entry_total in any::<u64>(),
+            guaranteed_pool in any::<u64>(),
+            guarantee_deposit in any::<u64>(),
+        ) {
+            let split = payout::split_entries(entry_total, guaranteed_pool, guarantee_deposit);
+            prop_assert_eq!(split.overlay + split.unused_guarantee, guarantee_deposit);
+            
+            // The pool reaches the guarantee when the deposit allows, and never goes past it
+            let pool = split.post_fee_entries as u128 + split.overlay as u128;
+            let reachable = (split.post_fee_entries as u128 + guarantee_deposit as u128)
+                .min(guaranteed_pool as u128);
+            prop_assert!(pool >= reachable);
+            prop_assert!(split.overlay == 0 || pool <= guaranteed_pool as u128);
+        }
+        
+        #[test]
+        fn prize_pool_overflow_is_reported(
+            entry_total in any::<u64>(),
+            sponsor_pool in any::<u64>(),
+            rollover_pool in any::<u64>(),
+        ) {
+            let split = payout::split_entries(entry_total, 0, 0);
+            let expected =
+                split.post_fee_entries as u128 + sponsor_pool as u128 + rollover_pool as u128;
+            let prize_pool = payout::prize_pool(&split, sponsor_pool, rollover_pool);
+            prop_assert_eq!(prize_pool, u64::try_from(expected).ok());
+        }
+    }
+    
+    #[test]
+    fn prizes_outside_the_schedule_are_rejected() {
+        assert_eq!(payout::prize(1_000, 0, &[5000, 5000]), None);
+        assert_eq!(payout::prize(1_000, 3, &[5000, 5000]), None);
+        assert_eq!(payout::prize(1_000, 1, &[0, 0]), None);
+        assert_eq!(payout::prizes(1_000, &[5000, 3000]), Some(vec![625, 375]));
+        assert_eq!(payout::dust(1_000, &[600, 401]), None);
+        assert_eq!(
+            payout::settle_prizes(1_001, &[5000, 3000, 2000], DustPolicy::ToFirstPlace),
+            Some((vec![501, 300, 200], 0))
+        );
+        assert_eq!(
+            payout::settle_prizes(1_001, &[5000, 3000, 2000], DustPolicy::ToTreasury),
+            Some((vec![500, 300, 200], 1))
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::{hash, hashv};
+
+/// Commitment the authority publishes at season creation: `sha256(secret)`
+pub fn commitment(secret: &[u8; 32]) -> [u8; 32] {
+    hash(secret).to_bytes()
+}
+
+/// Draw seed: `sha256(secret || slot_hash || season)`, where `slot_hash` is the
+/// hash of the slot committed when the raffle was locked
+pub fn raffle_seed(secret: &[u8; 32], slot_hash: &[u8; 32], season: &Pubkey) -> [u8; 32] {
+    hashv(&[secret, slot_hash, season.as_ref()]).to_bytes()
+}
+
+/// Participant index picked by draw number `attempt`: the first 8 bytes of
+/// `sha256(seed || attempt)` as a little-endian u64, modulo `participant_total`.
+/// Attempts landing on an inactive or already drawn participant are skipped.
+pub fn draw_index(seed: &[u8; 32], attempt: u32, participant_total: u64) -> u64 {
+    let digest = hashv(&[seed, &attempt.to_le_bytes()]).to_bytes();
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&digest[..8]);
+    
+    u64::from_le_bytes(value) % participant_total
+}
+
+/// Hash of `slot` in SlotHashes sysvar data: a u64 entry count followed by
+/// `(u64 slot, 32-byte hash)` entries, newest first. `None` once the slot has
+/// left the sysvar's history.
+pub fn find_slot_hash(slot_hashes: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let count = u64::from_le_bytes(slot_hashes.get(..8)?.try_into().ok()?);
+    
+    slot_hashes
+        .get(8..)?
+        .chunks_exact(40)
+        .take(count as usize)
+        .find(|entry| entry[..8] == slot.to_le_bytes())
+        .and_then(|entry| entry[8..].try_into().ok())
+}
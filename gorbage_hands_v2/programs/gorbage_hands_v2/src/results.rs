@@ -0,0 +1,43 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Domain prefix for results settled with explicit winner accounts
+pub const WINNERS_RESULT_PREFIX: &[u8] = &[0x02];
+
+/// Domain prefix for results settled with a Merkle root
+pub const MERKLE_RESULT_PREFIX: &[u8] = &[0x03];
+
+/// Hash attestors sign off on for an account-based settlement:
+/// `sha256(0x02 || season || (wallet || amount)*)` in placement order,
+/// amounts little-endian
+pub fn winners_result_hash(season: &Pubkey, winners: &[(Pubkey, u64)]) -> [u8; 32] {
+    let amounts: Vec<[u8; 8]> = winners.iter().map(|(_, amount)| amount.to_le_bytes()).collect();
+    
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(2 + winners.len() * 2);
+    parts.push(WINNERS_RESULT_PREFIX);
+    parts.push(season.as_ref());
+    for ((wallet, _), amount) in winners.iter().zip(&amounts) {
+        parts.push(wallet.as_ref());
+        parts.push(amount);
+    }
+    
+    hashv(&parts).to_bytes()
+}
+
+/// Hash attestors sign off on for a Merkle settlement:
+/// `sha256(0x03 || season || root || total || leaf_count)`, integers little-endian
+pub fn merkle_result_hash(
+    season: &Pubkey,
+    merkle_root: &[u8; 32],
+    total_payout: u64,
+    leaf_count: u32,
+) -> [u8; 32] {
+    hashv(&[
+        MERKLE_RESULT_PREFIX,
+        season.as_ref(),
+        merkle_root,
+        &total_payout.to_le_bytes(),
+        &leaf_count.to_le_bytes(),
+    ])
+    .to_bytes()
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// One bit per leaf of a season's Merkle distribution, set once the leaf is claimed
+#[account]
+pub struct ClaimBitmap {
+    /// Season this bitmap belongs to
+    pub season: Pubkey,
+    
+    /// Number of leaves in the distribution
+    pub leaf_count: u32,
+    
+    /// Claim bits, leaf `i` is bit `i % 8` of byte `i / 8`
+    pub bits: Vec<u8>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ClaimBitmap {
+    /// Account space (without discriminator) for `leaf_count` leaves
+    pub fn space(leaf_count: u32) -> usize {
+        32 + 4 + 4 + Self::byte_len(leaf_count) + 1
+    }
+    
+    pub fn byte_len(leaf_count: u32) -> usize {
+        (leaf_count as usize).div_ceil(8)
+    }
+    
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.bits[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+    
+    pub fn set_claimed(&mut self, index: u32) {
+        self.bits[index as usize / 8] |= 1 << (index % 8);
+    }
+}
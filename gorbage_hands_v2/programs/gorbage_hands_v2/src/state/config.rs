@@ -6,6 +6,12 @@ pub struct ProgramConfig {
     /// The global admin who can create seasons and transfer admin rights
     pub admin: Pubkey,
     
+    /// Game server allowed to submit scores (default = none registered)
+    pub game_server: Pubkey,
+    
+    /// Number the next season created gets
+    pub next_season_number: u64,
+    
     /// Bump seed for the PDA
     pub bump: u8,
 }
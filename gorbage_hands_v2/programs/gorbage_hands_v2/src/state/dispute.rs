@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    /// Season whose proposed results are disputed
+    pub season: Pubkey,
+    
+    /// Participant wallet that opened the dispute
+    pub disputer: Pubkey,
+    
+    /// Settlement epoch of the disputed results
+    pub settlement_epoch: u32,
+    
+    /// Bond in lamports held by this account on top of its rent
+    pub bond: u64,
+    
+    /// Timestamp the dispute was opened
+    pub opened_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
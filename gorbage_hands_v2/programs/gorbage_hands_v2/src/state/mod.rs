@@ -1,7 +1,21 @@
 pub mod season;
 pub mod participant;
 pub mod config;
+pub mod waitlist;
+pub mod sponsorship;
+pub mod claim_bitmap;
+pub mod oracle_registry;
+pub mod dispute;
+pub mod participant_page;
+pub mod season_registry;
 
 pub use season::*;
 pub use participant::*;
 pub use config::*;
+pub use waitlist::*;
+pub use sponsorship::*;
+pub use claim_bitmap::*;
+pub use oracle_registry::*;
+pub use dispute::*;
+pub use participant_page::*;
+pub use season_registry::*;
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_ATTESTORS;
+
+#[account]
+#[derive(InitSpace)]
+pub struct OracleRegistry {
+    /// Attestors allowed to confirm season results
+    #[max_len(MAX_ATTESTORS)]
+    pub attestors: Vec<Pubkey>,
+    
+    /// Distinct attestors needed before proposed results take effect
+    pub threshold: u8,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl OracleRegistry {
+    pub fn contains(&self, attestor: &Pubkey) -> bool {
+        self.attestors.contains(attestor)
+    }
+    
+    /// Whether the attestor set is non-empty, has no duplicates and can meet the threshold
+    pub fn is_valid(&self) -> bool {
+        let no_duplicates = self.attestors
+            .iter()
+            .enumerate()
+            .all(|(index, attestor)| !self.attestors[..index].contains(attestor));
+        
+        self.threshold > 0 && self.threshold as usize <= self.attestors.len() && no_duplicates
+    }
+}
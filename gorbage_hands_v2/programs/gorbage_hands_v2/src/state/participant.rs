@@ -1,5 +1,19 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{Season, SeasonPhase};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ParticipantStatus {
+    /// Registered and competing
+    Active,
+    
+    /// Left during registration and was refunded
+    Withdrawn,
+    
+    /// Removed by the season authority
+    Disqualified,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Participant {
@@ -12,21 +26,78 @@ pub struct Participant {
     /// Season number for easy lookup
     pub season_number: u64,
     
+    /// Stable position in the season's registration order, see `ParticipantPage`
+    pub index: u64,
+    
     /// Registration timestamp
     pub registered_at: i64,
     
     /// Entry fee paid
     pub entry_fee_paid: u64,
     
+    /// Whether the participant is still competing
+    pub status: ParticipantStatus,
+    
     /// Winner placement (0 = not a winner, 1 = first, 2 = second, etc.)
     pub placement: u8,
     
     /// Prize amount won (0 if not a winner)
     pub prize_amount: u64,
     
+    /// Season settlement epoch the placement was set in
+    pub settlement_epoch: u32,
+    
     /// Whether prize has been claimed
     pub prize_claimed: bool,
     
+    /// Whether the entry fee has been refunded after a cancellation
+    pub refunded: bool,
+    
+    /// Latest score submitted by the game server
+    pub score: u64,
+    
+    /// When the score was last submitted (0 = no score yet)
+    pub score_submitted_at: i64,
+    
+    /// Highest nonce of any score written, by the game server or attested
+    pub score_nonce: u64,
+    
+    /// Season ranking round the score was last ranked in (0 = never)
+    pub ranked_round: u32,
+    
     /// Bump seed for PDA
     pub bump: u8,
 }
+
+impl Participant {
+    /// Whether the participant placed in the season's current results
+    pub fn is_winner(&self, season: &Season) -> bool {
+        self.placement > 0 && self.settlement_epoch == season.settlement_epoch
+    }
+    
+    pub fn has_score(&self) -> bool {
+        self.score_submitted_at != 0
+    }
+    
+    /// Record a new score, returning whether it is the participant's first
+    pub fn record_score(&mut self, score: u64, current_time: i64) -> bool {
+        let first_score = !self.has_score();
+        self.score = score;
+        self.score_submitted_at = current_time;
+        first_score
+    }
+    
+    /// Whether the account can be closed and its rent returned: the season is
+    /// over and nothing is still owed to this participant
+    pub fn is_closable(&self, season: &Season, current_time: i64) -> bool {
+        match season.phase {
+            SeasonPhase::Settled | SeasonPhase::Closed => {
+                !self.is_winner(season)
+                    || self.prize_claimed
+                    || !season.is_claim_window_open(current_time)
+            }
+            SeasonPhase::Cancelled => self.refunded,
+            _ => false,
+        }
+    }
+}
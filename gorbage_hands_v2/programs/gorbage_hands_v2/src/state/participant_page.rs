@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::PARTICIPANT_PAGE_SIZE;
+
+/// One page of a season's roster. Participant `index` lives at position
+/// `index % PARTICIPANT_PAGE_SIZE` of page `index / PARTICIPANT_PAGE_SIZE`.
+/// Entries are never removed, so indices stay stable after withdrawals.
+#[account]
+#[derive(InitSpace)]
+pub struct ParticipantPage {
+    /// Season this page belongs to
+    pub season: Pubkey,
+    
+    /// Page number, starting at 0
+    pub page: u64,
+    
+    /// Wallet that paid the rent, refunded when the page is closed
+    pub payer: Pubkey,
+    
+    /// Participant wallets in registration order
+    #[max_len(PARTICIPANT_PAGE_SIZE)]
+    pub entries: Vec<Pubkey>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ParticipantPage {
+    /// Page holding participant `index`
+    pub fn page_for(index: u64) -> u64 {
+        index / PARTICIPANT_PAGE_SIZE as u64
+    }
+    
+    /// Stable participant index of the entry at `position`
+    pub fn participant_index(&self, position: usize) -> u64 {
+        self.page * PARTICIPANT_PAGE_SIZE as u64 + position as u64
+    }
+}
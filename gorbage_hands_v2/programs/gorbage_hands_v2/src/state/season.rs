@@ -1,6 +1,80 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::MAX_WINNERS;
+use crate::constants::{MAX_WINNERS, MAX_ATTESTORS, RAFFLE_REVEAL_WINDOW};
+use crate::error::GorbageError;
+
+/// Lifecycle of a season. Transitions only ever move forward, except for
+/// voided results which send the season back to `Active`:
+///
+/// - `Scheduled` -> `Registration`: automatically once `registration_start` is reached
+/// - `Registration` -> `Active`: `collect_fee`, after `registration_end`
+/// - `Active` -> `Settling`: `settle_season` or `settle_season_with_root` (contests) or
+///   `draw_winners` (raffles), after `season_end`
+/// - `Settling` -> `Settled`: `finalize_settlement`, once the oracle registry threshold is
+///   met and the challenge window closed with no open disputes
+/// - `Settling` -> `Active`: `resolve_dispute` voiding the proposed results
+/// - `Settled` -> `Closed`: `close_season`
+/// - `Scheduled` | `Registration` -> `Cancelled`: automatically once `registration_end`
+///   passes with fewer than `min_participants` registered
+/// - `Scheduled` | `Registration` | `Active` | `Settling` -> `Cancelled`: `cancel_season`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum SeasonPhase {
+    /// Created, registration has not opened yet
+    Scheduled,
+    
+    /// Accepting participants
+    Registration,
+    
+    /// Registration closed and platform fee collected
+    Active,
+    
+    /// Results proposed, waiting for oracle attestations and the challenge window
+    Settling,
+    
+    /// Winners and prizes final, winners can claim
+    Settled,
+    
+    /// Remaining vault balance swept, nothing else can happen
+    Closed,
+    
+    /// Season aborted, participants can claim refunds
+    Cancelled,
+}
+
+/// How winners are picked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum SeasonMode {
+    /// Skill contest, winners proposed from results or scores
+    Contest,
+    
+    /// Raffle, winners drawn with commit-reveal randomness
+    Raffle,
+}
+
+/// Where prizes that are still unclaimed at the claim deadline go on close
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum UnclaimedPrizePolicy {
+    /// Sent to the treasury the platform fee was collected to
+    Treasury,
+    
+    /// Added to the prize pool of the given season
+    Rollover { season_number: u64 },
+}
+
+/// Where the rounding dust of a settlement goes: the part of the prize pool
+/// that truncating each prize to whole units leaves unassigned
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum DustPolicy {
+    /// Added to the first-place prize
+    ToFirstPlace,
+    
+    /// Sent to the treasury the platform fee was collected to
+    ToTreasury,
+    
+    /// Added to the prize pool of the given season, or sent to the treasury
+    /// if that season can no longer take it
+    ToNextSeason { season_number: u64 },
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -15,15 +89,54 @@ pub struct Season {
     #[max_len(32)]
     pub name: String,
     
-    /// Entry fee in lamports
+    /// Entry fee in lamports, or in base units of the season mint
     pub entry_fee: u64,
     
-    /// Total prize pool collected (after fee deduction)
+    /// Prize pool: entry fees until the platform fee is collected, then the
+    /// post-fee entries plus any guarantee overlay and sponsor funds
     pub prize_pool: u64,
     
+    /// Sponsor contributions, exempt from the platform fee
+    pub sponsor_pool: u64,
+    
+    /// Minimum post-fee prize pool promised by the authority
+    pub guaranteed_pool: u64,
+    
+    /// Authority funds backing the guarantee that are still in the vault
+    pub guarantee_deposit: u64,
+    
+    /// Unclaimed prizes rolled over from an earlier season, exempt from the platform fee
+    pub rollover_pool: u64,
+    
     /// Number of participants registered
     pub participant_count: u64,
     
+    /// Index the next registered participant gets; withdrawn participants keep theirs
+    pub next_participant_index: u64,
+    
+    /// Participants disqualified after registration closed
+    pub disqualified_count: u64,
+    
+    /// Number of active participants with a submitted score
+    pub scored_count: u64,
+    
+    /// Bumped whenever the scoreboard changes after ranking started, so
+    /// participants ranked in an earlier round are ranked again
+    pub ranking_round: u32,
+    
+    /// Scored participants ranked in the current round
+    pub ranked_count: u64,
+    
+    /// Best scores ranked so far, highest first (up to payout_bps.len())
+    #[max_len(MAX_WINNERS)]
+    pub leaderboard: Vec<LeaderboardEntry>,
+    
+    /// Minimum participants required by registration_end, otherwise the season is refunded
+    pub min_participants: u64,
+    
+    /// Maximum participants (0 = unlimited), further sign-ups go to the waitlist
+    pub max_participants: u64,
+    
     /// Registration start timestamp
     pub registration_start: i64,
     
@@ -33,34 +146,160 @@ pub struct Season {
     /// Season end timestamp (when winners can be set)
     pub season_end: i64,
     
-    /// Whether the season is active
-    pub is_active: bool,
+    /// Winners must claim by this timestamp, afterwards unclaimed prizes are released
+    pub claim_deadline: i64,
     
-    /// Whether winners have been set
-    pub winners_set: bool,
+    /// What happens to prizes unclaimed at the deadline
+    pub unclaimed_policy: UnclaimedPrizePolicy,
     
-    /// Winner pubkeys (up to MAX_WINNERS)
-    pub winners: [Pubkey; MAX_WINNERS],
+    /// What happens to the rounding dust left over when prizes are assigned
+    pub dust_policy: DustPolicy,
+    
+    /// Current lifecycle phase
+    pub phase: SeasonPhase,
+    
+    /// Payout schedule in basis points, one entry per placement (sums to 10000)
+    #[max_len(MAX_WINNERS)]
+    pub payout_bps: Vec<u16>,
+    
+    /// Winner pubkeys in placement order (up to payout_bps.len())
+    #[max_len(MAX_WINNERS)]
+    pub winners: Vec<Pubkey>,
     
     /// Number of actual winners
     pub winner_count: u8,
     
+    /// Sum of all assigned prizes
+    pub total_awarded: u64,
+    
+    /// Part of the prize pool left unassigned by rounding, routed by
+    /// `dust_policy` when the settlement is finalized
+    pub dust_amount: u64,
+    
+    /// Sum of all claimed prizes
+    pub total_claimed: u64,
+    
     /// Bump seed for PDA
     pub bump: u8,
     
     /// Vault bump seed
     pub vault_bump: u8,
     
-    /// Whether platform fee has been collected
-    pub fee_collected: bool,
+    /// Mint the season is priced in (None = native lamports)
+    pub mint: Option<Pubkey>,
+    
+    /// Vault PDA's token account for token-priced seasons
+    pub vault_token_account: Pubkey,
     
     /// Amount of fee collected (for record-keeping)
     pub fee_amount: u64,
+    
+    /// Treasury wallet the platform fee was sent to
+    pub treasury: Pubkey,
+    
+    /// Root of the prize distribution for seasons settled with a Merkle root
+    pub merkle_root: Option<[u8; 32]>,
+    
+    /// Number of leaves in the Merkle distribution
+    pub merkle_leaf_count: u32,
+    
+    /// Hash of the proposed results that attestors confirm
+    pub result_hash: [u8; 32],
+    
+    /// Attestors that confirmed `result_hash`
+    #[max_len(MAX_ATTESTORS)]
+    pub attestations: Vec<Pubkey>,
+    
+    /// Seconds after results are proposed during which participants can dispute them
+    pub challenge_window: i64,
+    
+    /// Lamports a participant posts to open a dispute
+    pub dispute_bond: u64,
+    
+    /// End of the challenge window for the current proposal
+    pub challenge_deadline: i64,
+    
+    /// Disputes open against the current proposal
+    pub open_disputes: u32,
+    
+    /// Incremented every time proposed results are voided
+    pub settlement_epoch: u32,
+    
+    /// How winners are picked
+    pub mode: SeasonMode,
+    
+    /// `sha256(secret)` committed by the authority for raffle seasons
+    pub raffle_commitment: [u8; 32],
+    
+    /// Revealed raffle secret
+    pub raffle_secret: [u8; 32],
+    
+    /// Future slot committed once registration closed, whose hash seeds the draw
+    /// (0 = not locked yet)
+    pub raffle_slot: u64,
+    
+    /// Hash of `raffle_slot` mixed into the raffle seed at reveal
+    pub raffle_slot_hash: [u8; 32],
+    
+    /// Seed the raffle draws from, set on reveal
+    pub raffle_seed: Option<[u8; 32]>,
+}
+
+/// A ranked score on the season leaderboard
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct LeaderboardEntry {
+    pub owner: Pubkey,
+    pub score: u64,
+    pub score_submitted_at: i64,
+}
+
+impl LeaderboardEntry {
+    /// Highest score first; ties go to the earlier submission, then the lower wallet
+    fn ranks_before(&self, other: &Self) -> bool {
+        other.score
+            .cmp(&self.score)
+            .then(self.score_submitted_at.cmp(&other.score_submitted_at))
+            .then(self.owner.cmp(&other.owner))
+            .is_lt()
+    }
 }
 
 impl Season {
+    /// Apply the time-driven transitions: `Scheduled` to `Registration` once
+    /// registration opens, and into `Cancelled` if registration closes short
+    /// of `min_participants`
+    pub fn sync_phase(&mut self, current_time: i64) {
+        if self.phase == SeasonPhase::Scheduled && current_time >= self.registration_start {
+            self.phase = SeasonPhase::Registration;
+        }
+        
+        let registration_phase = matches!(
+            self.phase,
+            SeasonPhase::Scheduled | SeasonPhase::Registration
+        );
+        if registration_phase
+            && current_time > self.registration_end
+            && self.is_below_minimum()
+        {
+            self.phase = SeasonPhase::Cancelled;
+        }
+    }
+    
+    pub fn is_below_minimum(&self) -> bool {
+        self.participant_count < self.min_participants
+    }
+    
+    pub fn is_capped(&self) -> bool {
+        self.max_participants > 0
+    }
+    
+    /// Spots left before `max_participants` is reached
+    pub fn open_spots(&self) -> u64 {
+        self.max_participants.saturating_sub(self.participant_count)
+    }
+    
     pub fn is_registration_open(&self, current_time: i64) -> bool {
-        self.is_active 
+        self.phase == SeasonPhase::Registration
             && current_time >= self.registration_start 
             && current_time <= self.registration_end
     }
@@ -68,4 +307,76 @@ impl Season {
     pub fn has_ended(&self, current_time: i64) -> bool {
         current_time > self.season_end
     }
+    
+    pub fn is_claim_window_open(&self, current_time: i64) -> bool {
+        current_time <= self.claim_deadline
+    }
+    
+    /// Place `entry` on the leaderboard, keeping only as many entries as there are placements
+    pub fn rank(&mut self, entry: LeaderboardEntry) -> Result<()> {
+        let position = self.leaderboard
+            .iter()
+            .position(|ranked| entry.ranks_before(ranked))
+            .unwrap_or(self.leaderboard.len());
+        self.leaderboard.insert(position, entry);
+        self.leaderboard.truncate(self.payout_bps.len());
+        self.ranked_count = self.ranked_count
+            .checked_add(1)
+            .ok_or(GorbageError::Overflow)?;
+        
+        Ok(())
+    }
+    
+    /// Discard a ranking in progress, after a score changed or a scored
+    /// participant left the scoreboard
+    pub fn reset_ranking(&mut self) -> Result<()> {
+        if self.ranked_count == 0 {
+            return Ok(());
+        }
+        self.ranking_round = self.ranking_round
+            .checked_add(1)
+            .ok_or(GorbageError::Overflow)?;
+        self.ranked_count = 0;
+        self.leaderboard = Vec::new();
+        
+        Ok(())
+    }
+    
+    /// Discard the proposed results and return to `Active` for a new proposal.
+    /// Placements recorded under the old epoch no longer count.
+    pub fn void_results(&mut self) -> Result<()> {
+        self.settlement_epoch = self.settlement_epoch
+            .checked_add(1)
+            .ok_or(GorbageError::Overflow)?;
+        self.winners = Vec::new();
+        self.winner_count = 0;
+        self.total_awarded = 0;
+        self.dust_amount = 0;
+        self.merkle_root = None;
+        self.merkle_leaf_count = 0;
+        self.result_hash = [0; 32];
+        self.attestations = Vec::new();
+        self.challenge_deadline = 0;
+        self.open_disputes = 0;
+        self.phase = SeasonPhase::Active;
+        
+        Ok(())
+    }
+    
+    /// Whether a locked raffle's secret can no longer be revealed, letting
+    /// anyone cancel the season
+    pub fn is_raffle_reveal_expired(&self, current_slot: u64) -> bool {
+        self.mode == SeasonMode::Raffle
+            && self.raffle_seed.is_none()
+            && self.raffle_slot != 0
+            && current_slot > self.raffle_slot.saturating_add(RAFFLE_REVEAL_WINDOW)
+    }
+    
+    /// Whether fee-exempt funds can still be added to the prize pool
+    pub fn accepts_pool_funds(&self) -> bool {
+        matches!(
+            self.phase,
+            SeasonPhase::Scheduled | SeasonPhase::Registration | SeasonPhase::Active
+        )
+    }
 }
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_LISTED_SEASONS;
+use crate::state::{Season, SeasonPhase};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct SeasonEntry {
+    pub season_number: u64,
+    
+    /// Season PDA
+    pub season: Pubkey,
+    
+    /// Phase as of the last update, time-driven transitions show up after a refresh
+    pub phase: SeasonPhase,
+    
+    /// Registration deadline, past which a still-registering entry may have been
+    /// cancelled for missing its minimum
+    pub registration_end: i64,
+}
+
+impl SeasonEntry {
+    /// Listed as registering after registration closed: the season was either
+    /// cancelled below its minimum or is waiting for its fee to be collected,
+    /// which lists it again
+    pub fn is_stale(&self, current_time: i64) -> bool {
+        matches!(self.phase, SeasonPhase::Scheduled | SeasonPhase::Registration)
+            && current_time > self.registration_end
+    }
+}
+
+/// Active and upcoming seasons, so clients can find every open season with one fetch.
+/// Seasons drop out once settled, closed or cancelled.
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonRegistry {
+    #[max_len(MAX_LISTED_SEASONS)]
+    pub seasons: Vec<SeasonEntry>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SeasonRegistry {
+    /// Record the current phase of `season`, dropping it once it is over along
+    /// with any stale entries. A season that finds the registry full is left
+    /// unlisted rather than failing the instruction.
+    pub fn update(&mut self, season: &Account<Season>, current_time: i64) {
+        self.seasons.retain(|entry| !entry.is_stale(current_time));
+        
+        let entry = SeasonEntry {
+            season_number: season.season_number,
+            season: season.key(),
+            phase: season.phase,
+            registration_end: season.registration_end,
+        };
+        let listed = matches!(
+            season.phase,
+            SeasonPhase::Scheduled
+                | SeasonPhase::Registration
+                | SeasonPhase::Active
+                | SeasonPhase::Settling
+        ) && !entry.is_stale(current_time);
+        let position = self.seasons
+            .iter()
+            .position(|entry| entry.season_number == season.season_number);
+        
+        match (position, listed) {
+            (Some(index), true) => self.seasons[index].phase = season.phase,
+            (Some(index), false) => {
+                self.seasons.remove(index);
+            }
+            (None, true) if self.seasons.len() < MAX_LISTED_SEASONS => {
+                self.seasons.push(entry);
+            }
+            (None, true) => {
+                msg!("Season registry is full, season {} is not listed", season.season_number);
+            }
+            (None, false) => {}
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Sponsorship {
+    /// Sponsor wallet
+    pub sponsor: Pubkey,
+    
+    /// Season the contribution went to
+    pub season: Pubkey,
+    
+    /// Total amount received by the vault from this sponsor
+    pub amount: u64,
+    
+    /// Whether the contribution was returned after a cancellation
+    pub refunded: bool,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
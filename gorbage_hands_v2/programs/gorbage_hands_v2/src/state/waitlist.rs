@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_WAITLIST_LEN, WAITLIST_CONFIRMATION_WINDOW};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct WaitlistOffer {
+    /// Waitlisted wallet the spot is held for
+    pub wallet: Pubkey,
+    
+    /// Timestamp after which the spot moves on to the next wallet
+    pub expires_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Waitlist {
+    /// Season this waitlist belongs to
+    pub season: Pubkey,
+    
+    /// Wallets waiting for a spot, in arrival order
+    #[max_len(MAX_WAITLIST_LEN)]
+    pub queue: Vec<Pubkey>,
+    
+    /// Freed spots currently held for waitlisted wallets
+    #[max_len(MAX_WAITLIST_LEN)]
+    pub offers: Vec<WaitlistOffer>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Waitlist {
+    /// Drop lapsed offers, then hold every open spot for the next queued wallets
+    pub fn refresh_offers(&mut self, current_time: i64, open_spots: u64) {
+        self.offers.retain(|offer| offer.expires_at >= current_time);
+        
+        while (self.offers.len() as u64) < open_spots && !self.queue.is_empty() {
+            let wallet = self.queue.remove(0);
+            self.offers.push(WaitlistOffer {
+                wallet,
+                expires_at: current_time.saturating_add(WAITLIST_CONFIRMATION_WINDOW),
+            });
+        }
+    }
+    
+    /// Consume the offer held for `wallet`, returning whether one existed
+    pub fn take_offer(&mut self, wallet: &Pubkey) -> bool {
+        match self.offers.iter().position(|offer| offer.wallet == *wallet) {
+            Some(index) => {
+                self.offers.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    pub fn contains(&self, wallet: &Pubkey) -> bool {
+        self.queue.contains(wallet) || self.offers.iter().any(|offer| offer.wallet == *wallet)
+    }
+    
+    pub fn reserved_spots(&self) -> u64 {
+        self.offers.len() as u64
+    }
+}
@@ -0,0 +1,305 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::VAULT_SEED;
+use crate::error::GorbageError;
+use crate::state::{Season, SeasonPhase};
+
+/// Byte offset of the `amount` field in an SPL Token / Token-2022 account
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Token accounts backing the vault of a token-priced season
+struct VaultToken<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    vault_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// The funds of a season: lamports held by the vault PDA for native seasons,
+/// or the vault PDA's token account for seasons priced in an SPL / Token-2022 mint.
+/// All movements in and out of the prize pool go through here.
+pub struct SeasonVault<'a, 'info> {
+    season_key: Pubkey,
+    vault_bump: u8,
+    vault: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token: Option<VaultToken<'a, 'info>>,
+}
+
+impl<'a, 'info> SeasonVault<'a, 'info> {
+    /// Build the vault for `season`, checking the optional token accounts
+    /// against the mint and vault token account recorded at season creation
+    pub fn load(
+        season: &Account<'info, Season>,
+        vault: &AccountInfo<'info>,
+        system_program: &Program<'info, System>,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        vault_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let token = match season.mint {
+            None => None,
+            Some(season_mint) => {
+                let (Some(mint), Some(vault_token_account), Some(token_program)) =
+                    (mint.as_ref(), vault_token_account.as_ref(), token_program.as_ref())
+                else {
+                    return err!(GorbageError::TokenAccountsRequired);
+                };
+                
+                require_keys_eq!(mint.key(), season_mint, GorbageError::InvalidMint);
+                require_keys_eq!(
+                    vault_token_account.key(),
+                    season.vault_token_account,
+                    GorbageError::InvalidTokenAccount
+                );
+                require_keys_eq!(
+                    token_program.key(),
+                    *mint.to_account_info().owner,
+                    GorbageError::InvalidTokenProgram
+                );
+                
+                Some(VaultToken {
+                    mint,
+                    vault_token_account,
+                    token_program,
+                })
+            }
+        };
+        
+        Ok(Self {
+            season_key: season.key(),
+            vault_bump: season.vault_bump,
+            vault: vault.clone(),
+            system_program: system_program.to_account_info(),
+            token,
+        })
+    }
+    
+    /// Current vault balance in lamports or token base units
+    pub fn balance(&self) -> Result<u64> {
+        match &self.token {
+            None => Ok(self.vault.lamports()),
+            Some(token) => token_balance(&token.vault_token_account.to_account_info()),
+        }
+    }
+    
+    /// Amount that has to be sent for the vault to receive `net_amount`,
+    /// grossing up for a Token-2022 transfer fee when the mint has one
+    pub fn gross_up(&self, net_amount: u64) -> Result<u64> {
+        let Some(token) = &self.token else {
+            return Ok(net_amount);
+        };
+        
+        let mint_info = token.mint.to_account_info();
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+        let Ok(fee_config) = mint.get_extension::<TransferFeeConfig>() else {
+            return Ok(net_amount);
+        };
+        
+        let fee = fee_config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+            .ok_or(GorbageError::Overflow)?;
+        
+        Ok(net_amount.checked_add(fee).ok_or(GorbageError::Overflow)?)
+    }
+    
+    /// Move `amount` from `from` into the vault and return what the vault actually
+    /// received. For Token-2022 mints with a transfer fee this is less than `amount`.
+    pub fn deposit(
+        &self,
+        from: &AccountInfo<'info>,
+        from_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<u64> {
+        match &self.token {
+            None => {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.clone(),
+                        system_program::Transfer {
+                            from: from.clone(),
+                            to: self.vault.clone(),
+                        },
+                    ),
+                    amount,
+                )?;
+                
+                Ok(amount)
+            }
+            Some(token) => {
+                let from_token_account =
+                    from_token_account.ok_or(GorbageError::TokenAccountsRequired)?;
+                let vault_token_account = token.vault_token_account.to_account_info();
+                let balance_before = token_balance(&vault_token_account)?;
+                
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token.token_program.to_account_info(),
+                        TransferChecked {
+                            from: from_token_account.to_account_info(),
+                            mint: token.mint.to_account_info(),
+                            to: vault_token_account.clone(),
+                            authority: from.clone(),
+                        },
+                    ),
+                    amount,
+                    token.mint.decimals,
+                )?;
+                
+                let balance_after = token_balance(&vault_token_account)?;
+                let received = balance_after
+                    .checked_sub(balance_before)
+                    .ok_or(GorbageError::Overflow)?;
+                
+                Ok(received)
+            }
+        }
+    }
+    
+    /// Pay `amount` out of the vault to `to`. Token-priced seasons pay into
+    /// `to_token_account`, which must be owned by `to`.
+    pub fn withdraw(
+        &self,
+        to: &AccountInfo<'info>,
+        to_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(self.balance()? >= amount, GorbageError::InsufficientVaultFunds);
+        
+        let vault_seeds: &[&[u8]] = &[
+            VAULT_SEED,
+            self.season_key.as_ref(),
+            &[self.vault_bump],
+        ];
+        
+        match &self.token {
+            None => {
+                invoke_signed(
+                    &system_instruction::transfer(
+                        self.vault.key,
+                        to.key,
+                        amount,
+                    ),
+                    &[
+                        self.vault.clone(),
+                        to.clone(),
+                        self.system_program.clone(),
+                    ],
+                    &[vault_seeds],
+                )?;
+            }
+            Some(token) => {
+                let to_token_account =
+                    to_token_account.ok_or(GorbageError::TokenAccountsRequired)?;
+                require_keys_eq!(to_token_account.owner, to.key(), GorbageError::InvalidTokenAccount);
+                require_keys_eq!(to_token_account.mint, token.mint.key(), GorbageError::InvalidMint);
+                
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token.token_program.to_account_info(),
+                        TransferChecked {
+                            from: token.vault_token_account.to_account_info(),
+                            mint: token.mint.to_account_info(),
+                            to: to_token_account.to_account_info(),
+                            authority: self.vault.clone(),
+                        },
+                        &[vault_seeds],
+                    ),
+                    amount,
+                    token.mint.decimals,
+                )?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Pay `amount` out of the vault into the vault of `rollover_season` and add
+    /// what it actually receives to that season's rollover pool, and to its prize
+    /// pool once the fee is collected. Returns the amount received.
+    pub fn roll_over(
+        &self,
+        rollover_season: &mut Account<'info, Season>,
+        rollover_vault: &AccountInfo<'info>,
+        rollover_vault_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<u64> {
+        let rollover_season_key = rollover_season.key();
+        let expected_vault = Pubkey::create_program_address(
+            &[
+                VAULT_SEED,
+                rollover_season_key.as_ref(),
+                &[rollover_season.vault_bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| GorbageError::InvalidRolloverSeason)?;
+        require_keys_eq!(
+            rollover_vault.key(),
+            expected_vault,
+            GorbageError::InvalidRolloverSeason
+        );
+        
+        let token = match &self.token {
+            None => None,
+            Some(token) => {
+                let vault_token_account = rollover_vault_token_account
+                    .ok_or(GorbageError::TokenAccountsRequired)?;
+                require_keys_eq!(
+                    vault_token_account.key(),
+                    rollover_season.vault_token_account,
+                    GorbageError::InvalidTokenAccount
+                );
+                
+                Some(VaultToken {
+                    mint: token.mint,
+                    vault_token_account,
+                    token_program: token.token_program,
+                })
+            }
+        };
+        let target = Self {
+            season_key: rollover_season_key,
+            vault_bump: rollover_season.vault_bump,
+            vault: rollover_vault.clone(),
+            system_program: self.system_program.clone(),
+            token,
+        };
+        
+        // Credit what the rollover vault actually receives
+        let balance_before = target.balance()?;
+        self.withdraw(rollover_vault, rollover_vault_token_account, amount)?;
+        let received = target.balance()?
+            .checked_sub(balance_before)
+            .ok_or(GorbageError::Overflow)?;
+        
+        rollover_season.rollover_pool = rollover_season.rollover_pool
+            .checked_add(received)
+            .ok_or(GorbageError::Overflow)?;
+        if rollover_season.phase == SeasonPhase::Active {
+            rollover_season.prize_pool = rollover_season.prize_pool
+                .checked_add(received)
+                .ok_or(GorbageError::Overflow)?;
+        }
+        
+        Ok(received)
+    }
+}
+
+/// Read the balance of a token account straight from its data, so it reflects
+/// transfers made earlier in the same instruction
+fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    let amount = data
+        .get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+        .ok_or(GorbageError::InvalidTokenAccount)?;
+    
+    Ok(u64::from_le_bytes(amount.try_into().unwrap()))
+}
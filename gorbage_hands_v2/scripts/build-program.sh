@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+# Build target/deploy/gorbage_hands_v2.so, the program binary the LiteSVM tests
+# in crates/gorbage_hands_tests load, and optionally run the workspace tests.
+#
+# Needs `cargo build-sbf` from the Solana CLI tools (2.2, matching solana-sdk).
+# `anchor build` produces the same binary along with the IDL.
+#
+#   scripts/build-program.sh           build the program
+#   scripts/build-program.sh --test    build it, then run cargo test --workspace
+set -euo pipefail
+
+cd "$(dirname "${BASH_SOURCE[0]}")/.."
+
+cargo build-sbf \
+    --manifest-path programs/gorbage_hands_v2/Cargo.toml \
+    --sbf-out-dir target/deploy
+
+if [[ "${1:-}" == "--test" ]]; then
+    cargo test --workspace
+fi